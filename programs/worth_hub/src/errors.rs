@@ -11,11 +11,20 @@ pub enum WorthHubError {
     #[msg("Reveal phase has ended")]
     RevealPhaseEnded,
 
+    #[msg("Topic has already been finalized by the oracle; reveals are closed")]
+    RevealAfterFinalize,
+
     #[msg("Reveal phase has not ended yet")]
     RevealPhaseNotEnded,
 
-    #[msg("Topic is not in the correct state for this operation")]
-    InvalidTopicState,
+    #[msg("Reveal phase has not been opened by the oracle")]
+    RevealNotOpened,
+
+    #[msg("Topic is no longer open")]
+    TopicNotOpen,
+
+    #[msg("Topic has not been finalized yet")]
+    TopicNotFinalized,
 
     #[msg("Commitment hash does not match the revealed values")]
     HashMismatch,
@@ -23,6 +32,45 @@ pub enum WorthHubError {
     #[msg("Commitment has already been revealed")]
     AlreadyRevealed,
 
+    #[msg("No commitment exists for this participant on this topic")]
+    CommitmentNotFound,
+
+    #[msg("Interval low bound exceeds its high bound")]
+    InvalidInterval,
+
+    #[msg("Value must lie strictly between i64::MIN and i64::MAX")]
+    ValueOutOfRange,
+
+    #[msg("Reveal batch must hold 1 to 8 entries, one commitment account each")]
+    InvalidRevealBatch,
+
+    #[msg("Topic has too many commitments to finalize and settle in one transaction")]
+    TooManyCommitmentsForAutoSettle,
+
+    #[msg("Consensus has already been snapshotted for this topic")]
+    ConsensusAlreadySnapshotted,
+
+    #[msg("Every commitment on the topic must be passed exactly once")]
+    IncompleteCommitmentSet,
+
+    #[msg("Commitment was withdrawn from scoring by abstaining")]
+    AlreadyAbstained,
+
+    #[msg("Topic requires its cosigner to sign every commit")]
+    InvalidCosigner,
+
+    #[msg("Truth band must give both bounds with low <= truth <= high")]
+    InvalidTruthBand,
+
+    #[msg("Truth value lies outside the topic's prediction bounds")]
+    TruthOutOfRange,
+
+    #[msg("Minimum prediction exceeds the maximum prediction")]
+    InvalidPredictionBounds,
+
+    #[msg("Number of values does not match the topic's truth dimensions")]
+    InvalidDimensionCount,
+
     #[msg("Commitment has not been revealed")]
     NotRevealed,
 
@@ -32,30 +80,159 @@ pub enum WorthHubError {
     #[msg("Stake amount is below the minimum required")]
     StakeTooLow,
 
+    #[msg("Minimum stake must cover the vault's rent-exempt balance")]
+    MinStakeBelowRentFloor,
+
+    #[msg("Scoring weight must be between 1 and Commitment::MAX_WEIGHT")]
+    InvalidWeight,
+
     #[msg("Unauthorized: only the oracle authority can call this")]
     UnauthorizedOracle,
 
+    #[msg("Oracle authority cannot be the default pubkey")]
+    InvalidOracle,
+
     #[msg("Unauthorized: only the topic authority can call this")]
     UnauthorizedAuthority,
 
+    #[msg("Unauthorized: only the topic, oracle, or settle authority can settle")]
+    UnauthorizedSettler,
+
+    #[msg("Account does not match the topic authority")]
+    TopicAuthorityMismatch,
+
     #[msg("Topic has already been settled")]
     AlreadySettled,
 
+    #[msg("Commitment has already been paid out")]
+    CommitmentAlreadySettled,
+
     #[msg("Topic has already been finalized")]
     AlreadyFinalized,
 
     #[msg("No revealed commitments to settle")]
     NoRevealedCommitments,
 
+    #[msg("Topic already has commitments and can no longer be reopened or reconfigured")]
+    TopicHasCommitments,
+
+    #[msg("Every commitment of the topic must be passed")]
+    MissingCommitments,
+
+    #[msg("Account is not a commitment for this topic and participant")]
+    InvalidCommitmentAccount,
+
+    #[msg("Account is too small to be a commitment")]
+    CommitmentAccountTooSmall,
+
     #[msg("Arithmetic overflow in reward calculation")]
     ArithmeticOverflow,
 
+    #[msg("Arithmetic overflow while computing the weighted consensus")]
+    ConsensusOverflow,
+
+    #[msg("Arithmetic overflow while computing consensus deviation percentages")]
+    EdgePctOverflow,
+
+    #[msg("Arithmetic overflow while computing participant scores")]
+    ScoreOverflow,
+
+    #[msg("Arithmetic overflow while computing payouts")]
+    PayoutOverflow,
+
     #[msg("Description too long (max 256 bytes)")]
     DescriptionTooLong,
 
     #[msg("Symbol too long (max 32 bytes)")]
     SymbolTooLong,
 
+    #[msg("Metadata URI too long (max 128 bytes)")]
+    MetadataUriTooLong,
+
+    #[msg("Symbol may only contain uppercase ASCII letters, digits and '-'")]
+    InvalidSymbol,
+
     #[msg("Invalid deadline configuration")]
     InvalidDeadlines,
+
+    #[msg("Too many allowed prediction values (max 512)")]
+    TooManyAllowedValues,
+
+    #[msg("Prediction value is not one of the topic's allowed values or lies outside its bounds")]
+    InvalidPredictionValue,
+
+    #[msg("Basis-point option out of range")]
+    InvalidBasisPoints,
+
+    #[msg("Topic accounting invariant violated")]
+    InvariantViolated,
+
+    #[msg("Refund timeout after finalization has not elapsed yet")]
+    RefundTimeoutNotReached,
+
+    #[msg("An earlier commitment's reveal turn is still pending")]
+    RevealOutOfOrder,
+
+    #[msg("Topic does not enforce reveal order")]
+    OrderedRevealDisabled,
+
+    #[msg("The current reveal turn has not expired yet")]
+    RevealTurnNotExpired,
+
+    #[msg("Every commitment's reveal turn has already passed")]
+    NoRevealTurnToSkip,
+
+    #[msg("Salt is all zeros or lacks the topic's required prefix")]
+    InvalidSalt,
+
+    #[msg("Vault account does not match the topic's vault")]
+    VaultMismatch,
+
+    #[msg("Recurring topic count must be 1 to MAX_RECURRING_TOPICS, with one topic account each")]
+    InvalidRecurringCount,
+
+    #[msg("Topic account does not match the PDA for its topic id")]
+    InvalidTopicAccount,
+
+    #[msg("Price feed account is missing, not the topic's feed, or unreadable")]
+    InvalidPriceFeed,
+
+    #[msg("Truth value does not match the price feed within the topic's tolerance")]
+    TruthOracleMismatch,
+
+    #[msg("Price feed's price was published longer ago than the topic's price_max_age")]
+    StalePrice,
+
+    #[msg("A revealed commitment cannot be canceled")]
+    CannotCancelAfterReveal,
+
+    #[msg("Leaderboard account is repeated, read-only, or matches no settled participant")]
+    InvalidLeaderboardAccount,
+
+    #[msg("A topic with this topic_id already exists")]
+    TopicIdAlreadyExists,
+
+    #[msg("Reveal window is shorter than the topic's minimum stake requires")]
+    RevealWindowTooShort,
+
+    #[msg("Topic config account is missing or not the topic's config")]
+    InvalidTopicConfig,
+
+    #[msg("Commitment has no aux commitment to check a range proof against")]
+    MissingAuxCommitment,
+
+    #[msg("Range proof is malformed or its range is empty")]
+    InvalidRangeProof,
+
+    #[msg("Topic has fewer distinct participants than its min_participants")]
+    NotEnoughParticipants,
+
+    #[msg("Refund recipient is not the rent or stake payer recorded on the commitment")]
+    InvalidRefundRecipient,
+
+    #[msg("Account is not a participant state for this topic")]
+    InvalidParticipantState,
+
+    #[msg("Truth passes the finalize sanity checks, so there is no rejection to record")]
+    TruthPassesChecks,
 }
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+
+/// Emitted when a topic is created
+#[event]
+pub struct TopicCreated {
+    pub topic_id: u64,
+    pub symbol: String,
+    /// Off-chain rules URI (empty if unset)
+    pub metadata_uri: String,
+}
+
+/// Emitted when the authority voids a topic before finalization and
+/// refunds every committer
+#[event]
+pub struct TopicVoided {
+    pub topic_id: u64,
+    pub refunded_count: u32,
+    pub refunded_stake: u64,
+}
+
+/// Emitted when a topic's rewards have been distributed
+#[event]
+pub struct TopicSettled {
+    pub topic_id: u64,
+    pub truth_value: i64,
+    pub consensus: i128,
+    pub participant_count: u32,
+    pub loser_pool: u64,
+    /// Lamports transferred to participants (excludes the protocol fee)
+    pub total_settled_payout: u64,
+    /// Lamports paid to the settle signer as keeper reward
+    pub keeper_reward: u64,
+    /// Lamports swept to the topic authority
+    pub protocol_fee: u64,
+    /// Stake-weighted variance of the revealed predictions (fixed-point, 1e6 precision)
+    pub prediction_variance: u64,
+}
+
+/// Emitted when settle finds the vault holding less than the participants'
+/// stakes and refunds everyone pro-rata instead of paying rewards
+#[event]
+pub struct VaultUnderfunded {
+    pub topic_id: u64,
+    pub vault_balance: u64,
+    /// Stake of the commitments being settled
+    pub total_stake: u64,
+}
+
+/// Emitted by `ping_reveal_window` while the reveal window is open
+#[event]
+pub struct RevealWindowOpen {
+    pub topic_id: u64,
+    pub reveal_deadline: i64,
+    pub reveal_count: u32,
+    pub commitment_count: u32,
+}
+
+/// Emitted when `skip_reveal_turn` passes over a commitment that did not
+/// reveal within its ordered-reveal turn
+#[event]
+pub struct RevealTurnSkipped {
+    pub topic_id: u64,
+    pub skipped_order: u32,
+    pub next_expected_reveal_order: u32,
+}
+
+/// Emitted when a topic's authority hands the topic to a new key
+#[event]
+pub struct AuthorityTransferred {
+    pub topic_id: u64,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+/// Emitted when the oracle records a truth that fails a finalize sanity
+/// check
+#[event]
+pub struct FinalizeRejected {
+    pub topic_id: u64,
+    pub truth_value: i64,
+    /// Error code of the failed check
+    pub reason: u32,
+    pub failed_finalize_count: u32,
+}
+
+/// Emitted when a topic's authority replaces its oracle
+#[event]
+pub struct OracleReplaced {
+    pub topic_id: u64,
+    pub previous_oracle: Pubkey,
+    pub new_oracle: Pubkey,
+    pub failed_finalize_count: u32,
+}
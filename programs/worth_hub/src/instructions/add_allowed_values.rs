@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::errors::WorthHubError;
+use crate::state::{Topic, TopicConfig, TopicStatus};
+
+#[derive(Accounts)]
+pub struct AddAllowedValues<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = topic.authority == authority.key() @ WorthHubError::UnauthorizedAuthority,
+        constraint = topic.status == TopicStatus::Open @ WorthHubError::TopicNotOpen,
+        constraint = topic.commitment_count == 0 @ WorthHubError::TopicHasCommitments,
+    )]
+    pub topic: Account<'info, Topic>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = TopicConfig::MAX_SIZE,
+        seeds = [b"topic_config", topic.key().as_ref()],
+        bump,
+    )]
+    pub topic_config: Account<'info, TopicConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Append to the topic's allowed prediction grid, creating its config PDA
+/// on the first call. Grids too long for one transaction are built over
+/// several calls, all before the first commit.
+pub fn handle_add_allowed_values(ctx: Context<AddAllowedValues>, values: Vec<i64>) -> Result<()> {
+    let config = &mut ctx.accounts.topic_config;
+    require!(
+        config.allowed_values.len() + values.len() <= TopicConfig::MAX_ALLOWED_VALUES,
+        WorthHubError::TooManyAllowedValues
+    );
+    require!(
+        values.iter().all(|&v| Topic::is_value_in_range(v)),
+        WorthHubError::ValueOutOfRange
+    );
+
+    let topic = &mut ctx.accounts.topic;
+    config.topic = topic.key();
+    config.bump = ctx.bumps.topic_config;
+    config.allowed_values.extend_from_slice(&values);
+    topic.config = config.key();
+
+    msg!(
+        "Allowed values added: id={}, added={}, total={}",
+        topic.topic_id,
+        values.len(),
+        config.allowed_values.len()
+    );
+    Ok(())
+}
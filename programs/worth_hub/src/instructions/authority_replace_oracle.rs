@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::errors::WorthHubError;
+use crate::events::OracleReplaced;
+use crate::state::{Topic, TopicStatus};
+
+#[derive(Accounts)]
+pub struct AuthorityReplaceOracle<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = topic.authority == authority.key() @ WorthHubError::UnauthorizedAuthority,
+        constraint = topic.status != TopicStatus::Settled @ WorthHubError::AlreadySettled,
+    )]
+    pub topic: Account<'info, Topic>,
+}
+
+/// Replace the topic's oracle with `new_oracle`, e.g. after the current one
+/// kept submitting truths the sanity checks rejected. The count of rejected
+/// finalizes is kept as the record behind the swap.
+pub fn handle_authority_replace_oracle(
+    ctx: Context<AuthorityReplaceOracle>,
+    new_oracle: Pubkey,
+) -> Result<()> {
+    let topic = &mut ctx.accounts.topic;
+    let previous_oracle = topic.oracle_authority;
+    topic.oracle_authority = new_oracle;
+
+    emit!(OracleReplaced {
+        topic_id: topic.topic_id,
+        previous_oracle,
+        new_oracle,
+        failed_finalize_count: topic.failed_finalize_count,
+    });
+    Ok(())
+}
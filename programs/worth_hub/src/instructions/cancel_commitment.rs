@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::errors::WorthHubError;
+use crate::state::{Commitment, ParticipantState, Topic};
+
+#[derive(Accounts)]
+pub struct CancelCommitment<'info> {
+    #[account(mut)]
+    pub participant: Signer<'info>,
+
+    #[account(mut)]
+    pub topic: Account<'info, Topic>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"commitment",
+            topic.key().as_ref(),
+            participant.key().as_ref(),
+            commitment.nonce.to_le_bytes().as_ref(),
+        ],
+        bump = commitment.bump,
+        close = rent_payer,
+    )]
+    pub commitment: Account<'info, Commitment>,
+
+    /// CHECK: Must be the rent payer recorded on the commitment; gets the
+    /// commitment's rent back
+    #[account(mut, address = commitment.rent_payer @ WorthHubError::InvalidRefundRecipient)]
+    pub rent_payer: UncheckedAccount<'info>,
+
+    /// CHECK: Must be the stake payer recorded on the commitment; gets the
+    /// stake back
+    #[account(mut, address = commitment.stake_payer @ WorthHubError::InvalidRefundRecipient)]
+    pub stake_payer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"participant", topic.key().as_ref(), participant.key().as_ref()],
+        bump = participant_state.bump,
+    )]
+    pub participant_state: Account<'info, ParticipantState>,
+
+    /// The vault PDA holding staked SOL
+    /// CHECK: Validated by seeds and against `topic.vault`
+    #[account(
+        mut,
+        seeds = [b"vault", topic.key().as_ref()],
+        bump = topic.vault_bump,
+        address = topic.vault @ WorthHubError::VaultMismatch,
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Withdraw a commitment before the commit deadline: the stake is returned
+/// to whoever supplied it and the commitment closed, its rent going back
+/// to whoever paid it. Its nonce is never reused, so the next commit
+/// gets a fresh PDA. A revealed commitment is locked: it can never be
+/// canceled, so a participant cannot reveal, see a poor score and withdraw.
+pub fn handle_cancel_commitment(ctx: Context<CancelCommitment>) -> Result<()> {
+    ctx.accounts
+        .commitment
+        .check_cancelable(&ctx.accounts.topic, Clock::get()?.unix_timestamp)?;
+
+    let stake = ctx.accounts.commitment.stake_amount;
+    let topic_key = ctx.accounts.topic.key();
+    let vault_bump = ctx.accounts.topic.vault_bump;
+    let vault_signer_seeds: &[&[u8]] = &[b"vault", topic_key.as_ref(), &[vault_bump]];
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.stake_payer.to_account_info(),
+            },
+            &[vault_signer_seeds],
+        ),
+        stake,
+    )?;
+
+    // A participant canceling their last commitment no longer counts
+    // toward min_participants
+    let participant_state = &mut ctx.accounts.participant_state;
+    participant_state.open_commitments = participant_state
+        .open_commitments
+        .checked_sub(1)
+        .ok_or(WorthHubError::InvariantViolated)?;
+    let left_topic = participant_state.open_commitments == 0;
+
+    let topic = &mut ctx.accounts.topic;
+    topic.commitment_count -= 1;
+    if left_topic {
+        topic.participant_count = topic
+            .participant_count
+            .checked_sub(1)
+            .ok_or(WorthHubError::InvariantViolated)?;
+    }
+    topic.total_stake = topic
+        .total_stake
+        .checked_sub(stake)
+        .ok_or(WorthHubError::InvariantViolated)?;
+    topic.escrowed_stake = topic
+        .escrowed_stake
+        .checked_sub(stake)
+        .ok_or(WorthHubError::InvariantViolated)?;
+
+    msg!(
+        "Commitment canceled: participant={}, nonce={}, refunded={}",
+        ctx.accounts.participant.key(),
+        ctx.accounts.commitment.nonce,
+        stake
+    );
+    Ok(())
+}
@@ -1,37 +1,60 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
 use crate::errors::WorthHubError;
-use crate::state::{Commitment, Topic, TopicStatus};
+use crate::state::{Commitment, ParticipantState, Phase, Topic};
 
 #[derive(Accounts)]
 pub struct CommitPrediction<'info> {
+    /// Owner of the position; pays the stake unless the rent payer sponsors it
     #[account(mut)]
     pub participant: Signer<'info>,
 
+    /// Funds the rent of the new accounts; a relayer in sponsored setups,
+    /// otherwise the participant again
+    #[account(mut)]
+    pub rent_payer: Signer<'info>,
+
+    /// Phase is checked in `handle_commit` against the clock as well as the
+    /// status
+    #[account(mut)]
+    pub topic: Account<'info, Topic>,
+
     #[account(
-        mut,
-        constraint = topic.status == TopicStatus::Open @ WorthHubError::InvalidTopicState,
+        init_if_needed,
+        payer = rent_payer,
+        space = ParticipantState::MAX_SIZE,
+        seeds = [b"participant", topic.key().as_ref(), participant.key().as_ref()],
+        bump,
     )]
-    pub topic: Account<'info, Topic>,
+    pub participant_state: Account<'info, ParticipantState>,
 
     #[account(
         init,
-        payer = participant,
+        payer = rent_payer,
         space = Commitment::MAX_SIZE,
-        seeds = [b"commitment", topic.key().as_ref(), participant.key().as_ref()],
+        seeds = [
+            b"commitment",
+            topic.key().as_ref(),
+            participant.key().as_ref(),
+            participant_state.commit_nonce.to_le_bytes().as_ref(),
+        ],
         bump,
     )]
     pub commitment: Account<'info, Commitment>,
 
     /// The vault PDA that holds staked SOL
-    /// CHECK: Validated by seeds constraint
+    /// CHECK: Validated by seeds and against `topic.vault`
     #[account(
         mut,
         seeds = [b"vault", topic.key().as_ref()],
         bump = topic.vault_bump,
+        address = topic.vault @ WorthHubError::VaultMismatch,
     )]
     pub vault: UncheckedAccount<'info>,
 
+    /// Custodian co-signing the commit; required when the topic has a cosigner
+    pub cosigner: Option<Signer<'info>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -39,15 +62,31 @@ pub fn handle_commit(
     ctx: Context<CommitPrediction>,
     commitment_hash: [u8; 32],
     stake_amount: u64,
+    weight: Option<u64>,
+    encrypted_prediction: Option<[u8; 64]>,
+    stake_from_rent_payer: bool,
+    aux_commitment: Option<[u8; 32]>,
 ) -> Result<()> {
     let topic = &ctx.accounts.topic;
 
-    // Check commit deadline
+    // Open past the commit deadline is already the reveal phase
     let clock = Clock::get()?;
-    require!(
-        clock.unix_timestamp < topic.commit_deadline,
-        WorthHubError::CommitPhaseEnded
-    );
+    match topic.effective_phase(clock.unix_timestamp) {
+        Phase::Commit => {}
+        Phase::Reveal | Phase::AwaitingFinalize => return err!(WorthHubError::CommitPhaseEnded),
+        Phase::Finalized | Phase::Settled => return err!(WorthHubError::TopicNotOpen),
+    }
+
+    // Custodial topics need the configured cosigner's signature as well
+    if topic.cosigner != Pubkey::default() {
+        require!(
+            ctx.accounts
+                .cosigner
+                .as_ref()
+                .is_some_and(|c| c.key() == topic.cosigner),
+            WorthHubError::InvalidCosigner
+        );
+    }
 
     // Check minimum stake
     require!(stake_amount > 0, WorthHubError::ZeroStake);
@@ -56,42 +95,99 @@ pub fn handle_commit(
         WorthHubError::StakeTooLow
     );
 
-    // Transfer SOL from participant to vault
+    // Scoring weight defaults to the lamports staked
+    let weight = weight.unwrap_or(stake_amount);
+    require!(
+        weight > 0 && weight <= Commitment::MAX_WEIGHT,
+        WorthHubError::InvalidWeight
+    );
+
+    // Transfer SOL to the vault from the participant, or from the rent
+    // payer when it sponsors the stake too
+    let staker = if stake_from_rent_payer {
+        ctx.accounts.rent_payer.to_account_info()
+    } else {
+        ctx.accounts.participant.to_account_info()
+    };
     system_program::transfer(
         CpiContext::new(
             ctx.accounts.system_program.to_account_info(),
             system_program::Transfer {
-                from: ctx.accounts.participant.to_account_info(),
+                from: staker,
                 to: ctx.accounts.vault.to_account_info(),
             },
         ),
         stake_amount,
     )?;
 
+    // First commit on this topic initializes the participant state
+    let participant_state = &mut ctx.accounts.participant_state;
+    if participant_state.participant == Pubkey::default() {
+        participant_state.topic = ctx.accounts.topic.key();
+        participant_state.participant = ctx.accounts.participant.key();
+        participant_state.commit_nonce = 0;
+        participant_state.bump = ctx.bumps.participant_state;
+        participant_state.rent_payer = ctx.accounts.rent_payer.key();
+    }
+
     // Record commitment
     let commitment = &mut ctx.accounts.commitment;
     commitment.topic = ctx.accounts.topic.key();
     commitment.participant = ctx.accounts.participant.key();
     commitment.commitment_hash = commitment_hash;
     commitment.stake_amount = stake_amount;
-    commitment.submit_order = ctx.accounts.topic.commitment_count;
+    commitment.submit_order = ctx.accounts.topic.next_submit_order;
     commitment.prediction_value = 0;
     commitment.revealed = false;
     commitment.salt = [0u8; 32];
     commitment.settled = false;
     commitment.bump = ctx.bumps.commitment;
+    commitment.weight = weight;
+    commitment.encrypted_prediction = encrypted_prediction.unwrap_or([0u8; 64]);
+    commitment.interval_low = 0;
+    commitment.interval_high = 0;
+    commitment.nonce = participant_state.commit_nonce;
+    commitment.abstained = false;
+    commitment.extra_predictions = [0; 3];
+    commitment.aux_commitment = aux_commitment.unwrap_or([0u8; 32]);
+    commitment.rent_payer = ctx.accounts.rent_payer.key();
+    commitment.stake_payer = if stake_from_rent_payer {
+        ctx.accounts.rent_payer.key()
+    } else {
+        ctx.accounts.participant.key()
+    };
+    participant_state.commit_nonce = participant_state
+        .commit_nonce
+        .checked_add(1)
+        .ok_or(WorthHubError::ArithmeticOverflow)?;
+    participant_state.open_commitments = participant_state
+        .open_commitments
+        .checked_add(1)
+        .ok_or(WorthHubError::ArithmeticOverflow)?;
+    let new_participant = participant_state.open_commitments == 1;
 
     // Update topic
     let topic = &mut ctx.accounts.topic;
     topic.commitment_count += 1;
+    topic.next_submit_order = topic
+        .next_submit_order
+        .checked_add(1)
+        .ok_or(WorthHubError::ArithmeticOverflow)?;
+    if new_participant {
+        topic.participant_count += 1;
+    }
     topic.total_stake = topic
         .total_stake
         .checked_add(stake_amount)
         .ok_or(WorthHubError::ArithmeticOverflow)?;
+    topic.escrowed_stake = topic
+        .escrowed_stake
+        .checked_add(stake_amount)
+        .ok_or(WorthHubError::ArithmeticOverflow)?;
 
     msg!(
         "Commitment #{} received, stake={} lamports",
-        topic.commitment_count - 1,
+        topic.next_submit_order - 1,
         stake_amount
     );
     Ok(())
@@ -0,0 +1,241 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::errors::WorthHubError;
+use crate::events::TopicCreated;
+use crate::instructions::create_topic::{
+    validate_deadlines, validate_price_feed, validate_reveal_window, validate_topic_params,
+};
+use crate::state::{Topic, TopicOptions, TopicStatus};
+
+/// Most topics one `create_recurring_topics` call creates, keeping every
+/// account creation within one transaction's compute and account limits
+pub const MAX_RECURRING_TOPICS: u8 = 8;
+
+#[derive(Accounts)]
+pub struct CreateRecurringTopics<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The oracle authority that will finalize every topic in the series
+    /// CHECK: This is just stored as a pubkey, no validation needed
+    pub oracle_authority: UncheckedAccount<'info>,
+
+    /// Optional keeper that may settle but not create or finalize
+    /// CHECK: This is just stored as a pubkey, no validation needed
+    pub settle_authority: Option<UncheckedAccount<'info>>,
+
+    /// Optional custodian who must co-sign every commit
+    /// CHECK: This is just stored as a pubkey, no validation needed
+    pub cosigner: Option<UncheckedAccount<'info>>,
+
+    /// Optional price account every truth must match
+    /// CHECK: This is just stored as a pubkey; finalize parses it
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+
+    // Remaining accounts: the `count` uninitialized topic PDAs for
+    // base_topic_id, base_topic_id + 1, ..., all writable
+}
+
+/// Create `count` topics sharing one template: topic `i` gets id
+/// `base_topic_id + i` (the same PDA create_topic would derive) and
+/// deadlines `period` seconds after topic `i - 1`'s. Every topic is
+/// validated exactly as create_topic validates it.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_create_recurring_topics<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateRecurringTopics<'info>>,
+    base_topic_id: u64,
+    count: u8,
+    period: i64,
+    description: String,
+    symbol: String,
+    first_commit_deadline: i64,
+    first_reveal_deadline: i64,
+    min_stake: u64,
+    options: TopicOptions,
+    metadata_uri: Option<String>,
+) -> Result<()> {
+    require!(
+        (1..=MAX_RECURRING_TOPICS).contains(&count),
+        WorthHubError::InvalidRecurringCount
+    );
+    require!(period > 0, WorthHubError::InvalidDeadlines);
+    require!(
+        ctx.remaining_accounts.len() == count as usize,
+        WorthHubError::InvalidRecurringCount
+    );
+    let oracle_authority = ctx.accounts.oracle_authority.key();
+    let metadata_uri = metadata_uri.unwrap_or_default();
+    validate_topic_params(
+        &oracle_authority,
+        &description,
+        &symbol,
+        &metadata_uri,
+        min_stake,
+        &options,
+    )?;
+    validate_price_feed(ctx.accounts.price_feed.is_some(), &options)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let rent = Rent::get()?;
+    let authority = ctx.accounts.authority.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+
+    for (i, topic_info) in ctx.remaining_accounts.iter().enumerate() {
+        let topic_id = base_topic_id
+            .checked_add(i as u64)
+            .ok_or(WorthHubError::ArithmeticOverflow)?;
+        let offset = period
+            .checked_mul(i as i64)
+            .ok_or(WorthHubError::ArithmeticOverflow)?;
+        let commit_deadline = first_commit_deadline
+            .checked_add(offset)
+            .ok_or(WorthHubError::ArithmeticOverflow)?;
+        let reveal_deadline = first_reveal_deadline
+            .checked_add(offset)
+            .ok_or(WorthHubError::ArithmeticOverflow)?;
+        validate_deadlines(commit_deadline, reveal_deadline, now)?;
+        validate_reveal_window(commit_deadline, reveal_deadline, min_stake, &options)?;
+
+        let id_bytes = topic_id.to_le_bytes();
+        let (topic_key, bump) =
+            Pubkey::find_program_address(&[b"topic", id_bytes.as_ref()], &crate::ID);
+        require!(
+            topic_info.key() == topic_key,
+            WorthHubError::InvalidTopicAccount
+        );
+        require!(
+            topic_info.data_is_empty(),
+            WorthHubError::TopicIdAlreadyExists
+        );
+        let (vault, vault_bump) =
+            Pubkey::find_program_address(&[b"vault", topic_key.as_ref()], &crate::ID);
+
+        create_pda(
+            &authority,
+            topic_info,
+            &system_program,
+            &[b"topic", id_bytes.as_ref(), &[bump]],
+            rent.minimum_balance(Topic::MAX_SIZE),
+            Topic::MAX_SIZE,
+        )?;
+
+        let topic = Topic {
+            authority: authority.key(),
+            oracle_authority,
+            settle_authority: ctx
+                .accounts
+                .settle_authority
+                .as_ref()
+                .map(|a| a.key())
+                .unwrap_or_default(),
+            topic_id,
+            status: TopicStatus::Open,
+            description: description.clone(),
+            symbol: symbol.clone(),
+            commit_deadline,
+            reveal_deadline,
+            min_stake,
+            vault_bump,
+            vault,
+            bump,
+            metadata_uri: metadata_uri.clone(),
+            cosigner: ctx
+                .accounts
+                .cosigner
+                .as_ref()
+                .map(|a| a.key())
+                .unwrap_or_default(),
+            price_feed: ctx
+                .accounts
+                .price_feed
+                .as_ref()
+                .map(|a| a.key())
+                .unwrap_or_default(),
+            options: options.clone(),
+            ..Topic::default()
+        };
+        topic.try_serialize(&mut &mut topic_info.try_borrow_mut_data()?[..])?;
+
+        emit!(TopicCreated {
+            topic_id,
+            symbol: symbol.clone(),
+            metadata_uri: metadata_uri.clone(),
+        });
+    }
+
+    msg!(
+        "Recurring topics created: ids={}..={}, symbol={}, period={}",
+        base_topic_id,
+        base_topic_id + count as u64 - 1,
+        symbol,
+        period
+    );
+    Ok(())
+}
+
+/// Create the program-owned PDA `account` with `space` bytes, funded to
+/// `lamports` by `payer`. Anyone can send lamports to a predictable PDA,
+/// and `create_account` refuses an account that already has some, so a
+/// pre-funded one is topped up, allocated and assigned instead, as
+/// Anchor's `init` does.
+fn create_pda<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+    lamports: u64,
+    space: usize,
+) -> Result<()> {
+    let current = account.lamports();
+    if current == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+                &[seeds],
+            ),
+            lamports,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    let top_up = lamports.saturating_sub(current);
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Allocate {
+                account_to_allocate: account.clone(),
+            },
+            &[seeds],
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Assign {
+                account_to_assign: account.clone(),
+            },
+            &[seeds],
+        ),
+        &crate::ID,
+    )
+}
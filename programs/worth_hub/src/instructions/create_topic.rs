@@ -1,6 +1,136 @@
 use anchor_lang::prelude::*;
 use crate::errors::WorthHubError;
-use crate::state::{Topic, TopicStatus};
+use crate::events::TopicCreated;
+use crate::state::{Topic, TopicOptions, TopicStatus};
+
+/// Longest allowed gap between commit and reveal deadlines (30 days),
+/// bounding how stale predictions get and how long stakes stay locked
+pub const MAX_REVEAL_WINDOW: i64 = 30 * 24 * 60 * 60;
+
+/// Symbols are canonical: uppercase ASCII letters, digits and '-' only, so
+/// look-alikes such as fullwidth "ＢＴＣ" cannot fragment a market
+pub fn is_canonical_symbol(symbol: &str) -> bool {
+    symbol
+        .bytes()
+        .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || b == b'-')
+}
+
+/// Deadline rules shared by create_topic and reopen_topic: the commit
+/// deadline lies in the future and the reveal window is non-empty and at
+/// most MAX_REVEAL_WINDOW long
+pub fn validate_deadlines(commit_deadline: i64, reveal_deadline: i64, now: i64) -> Result<()> {
+    require!(commit_deadline > now, WorthHubError::InvalidDeadlines);
+    require!(
+        reveal_deadline > commit_deadline,
+        WorthHubError::InvalidDeadlines
+    );
+    require!(
+        reveal_deadline - commit_deadline <= MAX_REVEAL_WINDOW,
+        WorthHubError::InvalidDeadlines
+    );
+    Ok(())
+}
+
+/// Shortest reveal window a topic staking at least `min_stake` may have:
+/// `reveal_seconds_per_sol` seconds per SOL of minimum stake, rounded up
+/// and capped at MAX_REVEAL_WINDOW so every stake leaves a valid window
+pub fn min_reveal_window(min_stake: u64, reveal_seconds_per_sol: u32) -> i64 {
+    const LAMPORTS_PER_SOL: u128 = 1_000_000_000;
+    let seconds = (min_stake as u128 * reveal_seconds_per_sol as u128).div_ceil(LAMPORTS_PER_SOL);
+    seconds.min(MAX_REVEAL_WINDOW as u128) as i64
+}
+
+/// Stake-scaled reveal window rule shared by every path that sets a
+/// topic's deadlines or minimum stake
+pub fn validate_reveal_window(
+    commit_deadline: i64,
+    reveal_deadline: i64,
+    min_stake: u64,
+    options: &TopicOptions,
+) -> Result<()> {
+    require!(
+        reveal_deadline - commit_deadline
+            >= min_reveal_window(min_stake, options.reveal_seconds_per_sol),
+        WorthHubError::RevealWindowTooShort
+    );
+    Ok(())
+}
+
+/// Minimum stake rule shared by create_topic and set_min_stake: the first
+/// commit funds the empty vault, which the system program only accepts at
+/// or above the rent-exempt balance
+pub fn validate_min_stake(min_stake: u64) -> Result<()> {
+    require!(
+        min_stake >= Rent::get()?.minimum_balance(0),
+        WorthHubError::MinStakeBelowRentFloor
+    );
+    Ok(())
+}
+
+/// A topic with a price feed must accept some price age, or no price would
+/// ever be fresh enough to finalize against
+pub fn validate_price_feed(has_price_feed: bool, options: &TopicOptions) -> Result<()> {
+    require!(
+        !has_price_feed || options.price_max_age > 0,
+        WorthHubError::InvalidPriceFeed
+    );
+    Ok(())
+}
+
+/// Everything create_topic checks besides the deadlines, shared with
+/// create_recurring_topics
+pub fn validate_topic_params(
+    oracle_authority: &Pubkey,
+    description: &str,
+    symbol: &str,
+    metadata_uri: &str,
+    min_stake: u64,
+    options: &TopicOptions,
+) -> Result<()> {
+    // Nobody can sign for the default pubkey, so the topic could never finalize
+    require!(
+        *oracle_authority != Pubkey::default(),
+        WorthHubError::InvalidOracle
+    );
+    require!(description.len() <= 256, WorthHubError::DescriptionTooLong);
+    require!(metadata_uri.len() <= 128, WorthHubError::MetadataUriTooLong);
+    require!(symbol.len() <= 32, WorthHubError::SymbolTooLong);
+    require!(is_canonical_symbol(symbol), WorthHubError::InvalidSymbol);
+
+    validate_min_stake(min_stake)?;
+    require!(
+        options.refund_timeout >= TopicOptions::MIN_REFUND_TIMEOUT,
+        WorthHubError::InvalidDeadlines
+    );
+    require!(
+        options.self_refund_timeout >= options.refund_timeout,
+        WorthHubError::InvalidDeadlines
+    );
+    require!(
+        options.reveal_rebate_bps <= TopicOptions::MAX_BPS
+            && options.keeper_reward_bps <= TopicOptions::MAX_BPS
+            && options.accuracy_weight_bps <= TopicOptions::MAX_WEIGHT_BPS
+            && options.boldness_weight_bps <= TopicOptions::MAX_WEIGHT_BPS
+            && options.max_payout_bps <= TopicOptions::MAX_BPS
+            && options.early_finalize_threshold_bps <= TopicOptions::MAX_BPS
+            && options.reveal_bond_keeper_bps <= TopicOptions::MAX_BPS
+            && options.wrong_direction_penalty_bps <= TopicOptions::MAX_BPS
+            && options.price_tolerance_bps <= TopicOptions::MAX_BPS,
+        WorthHubError::InvalidBasisPoints
+    );
+    require!(
+        (1..=TopicOptions::MAX_DIMENSIONS).contains(&options.dimensions),
+        WorthHubError::InvalidDimensionCount
+    );
+    require!(
+        !options.ordered_reveal || options.reveal_turn_seconds > 0,
+        WorthHubError::InvalidDeadlines
+    );
+    if let (Some(min), Some(max)) = (options.min_prediction, options.max_prediction) {
+        require!(min <= max, WorthHubError::InvalidPredictionBounds);
+    }
+    Ok(())
+}
 
 #[derive(Accounts)]
 #[instruction(topic_id: u64, description: String, symbol: String)]
@@ -12,8 +142,22 @@ pub struct CreateTopic<'info> {
     /// CHECK: This is just stored as a pubkey, no validation needed
     pub oracle_authority: UncheckedAccount<'info>,
 
+    /// Optional keeper that may settle but not create or finalize
+    /// CHECK: This is just stored as a pubkey, no validation needed
+    pub settle_authority: Option<UncheckedAccount<'info>>,
+
+    /// Optional custodian who must co-sign every commit
+    /// CHECK: This is just stored as a pubkey, no validation needed
+    pub cosigner: Option<UncheckedAccount<'info>>,
+
+    /// Optional price account every truth must match
+    /// CHECK: This is just stored as a pubkey; finalize parses it
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    /// Existing topics are loaded rather than failing the account creation,
+    /// so a reused id reports `TopicIdAlreadyExists`
     #[account(
-        init,
+        init_if_needed,
         payer = authority,
         space = Topic::MAX_SIZE,
         seeds = [b"topic", topic_id.to_le_bytes().as_ref()],
@@ -32,6 +176,7 @@ pub struct CreateTopic<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn handle_create_topic(
     ctx: Context<CreateTopic>,
     topic_id: u64,
@@ -40,23 +185,37 @@ pub fn handle_create_topic(
     commit_deadline: i64,
     reveal_deadline: i64,
     min_stake: u64,
+    options: TopicOptions,
+    metadata_uri: Option<String>,
 ) -> Result<()> {
-    require!(description.len() <= 256, WorthHubError::DescriptionTooLong);
-    require!(symbol.len() <= 32, WorthHubError::SymbolTooLong);
-
-    let clock = Clock::get()?;
+    // Every created topic stores its vault PDA, never the default pubkey
     require!(
-        commit_deadline > clock.unix_timestamp,
-        WorthHubError::InvalidDeadlines
-    );
-    require!(
-        reveal_deadline > commit_deadline,
-        WorthHubError::InvalidDeadlines
+        ctx.accounts.topic.vault == Pubkey::default(),
+        WorthHubError::TopicIdAlreadyExists
     );
+    let metadata_uri = metadata_uri.unwrap_or_default();
+    validate_topic_params(
+        &ctx.accounts.oracle_authority.key(),
+        &description,
+        &symbol,
+        &metadata_uri,
+        min_stake,
+        &options,
+    )?;
+    validate_price_feed(ctx.accounts.price_feed.is_some(), &options)?;
+    let clock = Clock::get()?;
+    validate_deadlines(commit_deadline, reveal_deadline, clock.unix_timestamp)?;
+    validate_reveal_window(commit_deadline, reveal_deadline, min_stake, &options)?;
 
     let topic = &mut ctx.accounts.topic;
     topic.authority = ctx.accounts.authority.key();
     topic.oracle_authority = ctx.accounts.oracle_authority.key();
+    topic.settle_authority = ctx
+        .accounts
+        .settle_authority
+        .as_ref()
+        .map(|a| a.key())
+        .unwrap_or_default();
     topic.topic_id = topic_id;
     topic.description = description;
     topic.symbol = symbol;
@@ -69,8 +228,48 @@ pub fn handle_create_topic(
     topic.reveal_count = 0;
     topic.min_stake = min_stake;
     topic.vault_bump = ctx.bumps.vault;
+    topic.vault = ctx.accounts.vault.key();
     topic.bump = ctx.bumps.topic;
+    topic.finalized_at = 0;
+    topic.reveal_opened_at = 0;
+    topic.finalize_seed = [0u8; 32];
+    topic.total_settled_payout = 0;
+    topic.truth_low = 0;
+    topic.truth_high = 0;
+    topic.consensus = 0;
+    topic.total_revealed_stake = 0;
+    topic.total_unrevealed_stake = 0;
+    topic.consensus_snapshot_at = 0;
+    topic.metadata_uri = metadata_uri;
+    topic.escrowed_stake = 0;
+    topic.escrowed_fees = 0;
+    topic.escrowed_prize = 0;
+    topic.cosigner = ctx
+        .accounts
+        .cosigner
+        .as_ref()
+        .map(|a| a.key())
+        .unwrap_or_default();
+    topic.prediction_variance = 0;
+    topic.settle_fund = 0;
+    topic.extra_truths = [0; 3];
+    topic.next_expected_reveal_order = 0;
+    topic.next_submit_order = 0;
+    topic.reveal_turn_started_at = 0;
+    topic.failed_finalize_count = 0;
+    topic.price_feed = ctx
+        .accounts
+        .price_feed
+        .as_ref()
+        .map(|a| a.key())
+        .unwrap_or_default();
+    topic.options = options;
 
+    emit!(TopicCreated {
+        topic_id,
+        symbol: topic.symbol.clone(),
+        metadata_uri: topic.metadata_uri.clone(),
+    });
     msg!("Topic created: id={}, symbol={}", topic_id, topic.symbol);
     Ok(())
 }
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::state::Topic;
+
+/// Read-only access to a topic, shared by the view instructions
+#[derive(Accounts)]
+pub struct ReadTopic<'info> {
+    pub topic: Account<'info, Topic>,
+}
+
+/// Seconds from `now` until `deadline`, or 0 once it has passed
+fn seconds_until(deadline: i64, now: i64) -> u64 {
+    deadline.saturating_sub(now).max(0) as u64
+}
+
+/// Seconds left in the commit phase (0 once it has ended)
+pub fn handle_time_until_commit_deadline(ctx: Context<ReadTopic>) -> Result<u64> {
+    let clock = Clock::get()?;
+    Ok(seconds_until(
+        ctx.accounts.topic.commit_deadline,
+        clock.unix_timestamp,
+    ))
+}
+
+/// Seconds left in the reveal window (0 once it has ended)
+pub fn handle_time_until_reveal_deadline(ctx: Context<ReadTopic>) -> Result<u64> {
+    let clock = Clock::get()?;
+    Ok(seconds_until(
+        ctx.accounts.topic.reveal_deadline,
+        clock.unix_timestamp,
+    ))
+}
@@ -1,5 +1,9 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_lang::solana_program::sysvar::slot_hashes;
 use crate::errors::WorthHubError;
+use crate::events::FinalizeRejected;
+use crate::price_feed::{read_price, within_tolerance};
 use crate::state::{Topic, TopicStatus};
 
 #[derive(Accounts)]
@@ -14,26 +18,283 @@ pub struct FinalizeTopic<'info> {
             @ WorthHubError::AlreadyFinalized,
     )]
     pub topic: Account<'info, Topic>,
+
+    /// Recent blockhashes, used to seed the random tie-break
+    /// CHECK: Address is pinned to the SlotHashes sysvar
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
+    /// The topic's price feed, required when it has one
+    /// CHECK: Compared against `topic.price_feed` and parsed by `read_price`
+    pub price_feed: Option<UncheckedAccount<'info>>,
+}
+
+/// Most recent blockhash from the SlotHashes sysvar. Layout is
+/// len(u64) followed by (slot(u64), hash([u8; 32])) entries, newest first.
+fn most_recent_blockhash(slot_hashes: &AccountInfo) -> Result<[u8; 32]> {
+    let data = slot_hashes.try_borrow_data()?;
+    let mut hash = [0u8; 32];
+    if data.len() >= 48 {
+        hash.copy_from_slice(&data[16..48]);
+    }
+    Ok(hash)
+}
+
+/// Confidence band around the truth: both bounds or neither, with
+/// `low <= truth <= high`. Without one the band collapses to the point.
+fn truth_band(
+    truth_value: i64,
+    low: Option<i64>,
+    high: Option<i64>,
+) -> std::result::Result<(i64, i64), WorthHubError> {
+    match (low, high) {
+        (None, None) => Ok((truth_value, truth_value)),
+        (Some(low), Some(high)) => {
+            if !(Topic::is_value_in_range(low) && Topic::is_value_in_range(high)) {
+                return Err(WorthHubError::ValueOutOfRange);
+            }
+            if !(low <= truth_value && truth_value <= high) {
+                return Err(WorthHubError::InvalidTruthBand);
+            }
+            Ok((low, high))
+        }
+        _ => Err(WorthHubError::InvalidTruthBand),
+    }
+}
+
+/// The price of the topic's price feed in fixed point, or None for a topic
+/// without one
+fn feed_price(topic: &Topic, price_feed: Option<&AccountInfo>) -> Result<Option<i128>> {
+    if topic.price_feed == Pubkey::default() {
+        return Ok(None);
+    }
+    let feed = price_feed
+        .filter(|feed| feed.key() == topic.price_feed)
+        .ok_or(WorthHubError::InvalidPriceFeed)?;
+    let price = read_price(
+        feed.owner,
+        &feed.try_borrow_data()?,
+        &topic.options.price_feed_id,
+        Clock::get()?.unix_timestamp,
+        topic.options.price_max_age,
+    )?
+    .to_fixed_point()
+    .ok_or(WorthHubError::InvalidPriceFeed)?;
+    Ok(Some(price))
+}
+
+/// Sanity checks on the oracle's truth, returning the confidence band
+fn check_truth(
+    topic: &Topic,
+    truth_value: i64,
+    truth_low: Option<i64>,
+    truth_high: Option<i64>,
+    extra_truths: &[i64],
+    feed_price: Option<i128>,
+) -> std::result::Result<(i64, i64), WorthHubError> {
+    if !(Topic::is_value_in_range(truth_value)
+        && extra_truths.iter().all(|&v| Topic::is_value_in_range(v)))
+    {
+        return Err(WorthHubError::ValueOutOfRange);
+    }
+    if !topic.options.within_bounds(truth_value) {
+        return Err(WorthHubError::TruthOutOfRange);
+    }
+    if let Some(price) = feed_price {
+        if !within_tolerance(truth_value, price, topic.options.price_tolerance_bps) {
+            return Err(WorthHubError::TruthOracleMismatch);
+        }
+    }
+    truth_band(truth_value, truth_low, truth_high)
+}
+
+pub fn handle_finalize(
+    ctx: Context<FinalizeTopic>,
+    truth_value: i64,
+    truth_low: Option<i64>,
+    truth_high: Option<i64>,
+) -> Result<()> {
+    let slot_hashes = ctx.accounts.slot_hashes.to_account_info();
+    let price_feed = ctx
+        .accounts
+        .price_feed
+        .as_ref()
+        .map(|f| f.to_account_info());
+    apply_finalize(
+        &mut ctx.accounts.topic,
+        &slot_hashes,
+        price_feed.as_ref(),
+        truth_value,
+        truth_low,
+        truth_high,
+        &[],
+    )?;
+    Ok(())
+}
+
+/// Finalize a multi-dimension topic with one truth per dimension, in the
+/// order predictions were revealed
+pub fn handle_finalize_vector(ctx: Context<FinalizeTopic>, truths: Vec<i64>) -> Result<()> {
+    let (&first, extra) = truths
+        .split_first()
+        .ok_or(WorthHubError::InvalidDimensionCount)?;
+    let slot_hashes = ctx.accounts.slot_hashes.to_account_info();
+    let price_feed = ctx
+        .accounts
+        .price_feed
+        .as_ref()
+        .map(|f| f.to_account_info());
+    apply_finalize(
+        &mut ctx.accounts.topic,
+        &slot_hashes,
+        price_feed.as_ref(),
+        first,
+        None,
+        None,
+        extra,
+    )?;
+    Ok(())
 }
 
-pub fn handle_finalize(ctx: Context<FinalizeTopic>, truth_value: i64) -> Result<()> {
-    let topic = &ctx.accounts.topic;
+/// Record a truth the sanity checks reject: `failed_finalize_count` goes up
+/// and `FinalizeRejected` names the failed check, so the authority has
+/// grounds to replace the oracle. A rejected `finalize` fails its
+/// transaction and so cannot record anything itself. A truth that passes
+/// the checks fails with `TruthPassesChecks`.
+pub fn handle_report_rejected_truth(
+    ctx: Context<FinalizeTopic>,
+    truth_value: i64,
+    truth_low: Option<i64>,
+    truth_high: Option<i64>,
+    extra_truths: Vec<i64>,
+) -> Result<()> {
+    let price_feed = ctx
+        .accounts
+        .price_feed
+        .as_ref()
+        .map(|f| f.to_account_info());
+    let topic = &mut ctx.accounts.topic;
+    require!(
+        extra_truths.len() + 1 == topic.options.dimensions as usize,
+        WorthHubError::InvalidDimensionCount
+    );
+
+    let feed_price = feed_price(topic, price_feed.as_ref())?;
+    let reason = match check_truth(
+        topic,
+        truth_value,
+        truth_low,
+        truth_high,
+        &extra_truths,
+        feed_price,
+    ) {
+        Ok(_) => return err!(WorthHubError::TruthPassesChecks),
+        Err(reason) => reason,
+    };
+    topic.failed_finalize_count = topic
+        .failed_finalize_count
+        .checked_add(1)
+        .ok_or(WorthHubError::ArithmeticOverflow)?;
+    msg!(
+        "Finalize rejected: id={}, reason={}",
+        topic.topic_id,
+        reason
+    );
+    emit!(FinalizeRejected {
+        topic_id: topic.topic_id,
+        truth_value,
+        reason: reason.into(),
+        failed_finalize_count: topic.failed_finalize_count,
+    });
+    Ok(())
+}
+
+/// Validate the truth and timing and record the finalization, shared by
+/// `finalize`, `finalize_vector` and `finalize_and_settle`.
+/// `extra_truths` holds one truth per dimension after the first.
+///
+/// A truth failing its sanity checks fails with the check's error; the
+/// oracle can record it with `report_rejected_truth`. Returns whether the
+/// topic was finalized.
+///
+/// A topic with a price feed also needs `price_feed`, and its first truth
+/// must match the feed's price within `options.price_tolerance_bps`. The
+/// price must be for `options.price_feed_id`, fully verified, and at most
+/// `options.price_max_age` seconds old.
+///
+/// A topic with fewer distinct participants than
+/// `options.min_participants` fails with `NotEnoughParticipants`.
+///
+/// Before the reveal phase is over a `soft_finalize` topic is left as it
+/// is, with the seconds to the reveal deadline as return data, instead of
+/// failing with `RevealPhaseNotEnded`.
+pub(crate) fn apply_finalize(
+    topic: &mut Account<Topic>,
+    slot_hashes: &AccountInfo,
+    price_feed: Option<&AccountInfo>,
+    truth_value: i64,
+    truth_low: Option<i64>,
+    truth_high: Option<i64>,
+    extra_truths: &[i64],
+) -> Result<bool> {
+    require!(
+        extra_truths.len() + 1 == topic.options.dimensions as usize,
+        WorthHubError::InvalidDimensionCount
+    );
+    topic.check_pre_finalize_invariants()?;
 
-    // Oracle can finalize after the reveal deadline
+    // Oracle can finalize from the reveal deadline on, the first second
+    // reveals are rejected, or earlier once enough commitments are revealed
     let clock = Clock::get()?;
+    if !(topic.is_reveal_phase_over(clock.unix_timestamp) || topic.is_early_finalize_ready()) {
+        require!(
+            topic.options.soft_finalize,
+            WorthHubError::RevealPhaseNotEnded
+        );
+        let seconds_left = topic
+            .reveal_deadline
+            .saturating_sub(clock.unix_timestamp)
+            .max(0) as u64;
+        set_return_data(&seconds_left.to_le_bytes());
+        msg!(
+            "Finalize too early: id={}, seconds_left={}",
+            topic.topic_id,
+            seconds_left
+        );
+        return Ok(false);
+    }
+
+    // Too few distinct committers for a meaningful result: the topic can
+    // only be voided, refunding everyone
     require!(
-        clock.unix_timestamp >= topic.reveal_deadline,
-        WorthHubError::RevealPhaseNotEnded
+        topic.has_min_participants(),
+        WorthHubError::NotEnoughParticipants
     );
 
-    let topic = &mut ctx.accounts.topic;
+    let feed_price = feed_price(topic, price_feed)?;
+    let (truth_low, truth_high) = check_truth(
+        topic,
+        truth_value,
+        truth_low,
+        truth_high,
+        extra_truths,
+        feed_price,
+    )?;
+
     topic.truth_value = truth_value;
+    topic.truth_low = truth_low;
+    topic.truth_high = truth_high;
+    topic.extra_truths[..extra_truths.len()].copy_from_slice(extra_truths);
     topic.status = TopicStatus::Finalized;
+    topic.finalized_at = clock.unix_timestamp;
+    topic.finalize_seed = most_recent_blockhash(slot_hashes)?;
 
     msg!(
-        "Topic finalized: id={}, truth_value={}",
+        "Topic finalized: id={}, truth_value={}, band=[{}, {}]",
         topic.topic_id,
-        truth_value
+        truth_value,
+        truth_low,
+        truth_high
     );
-    Ok(())
+    Ok(true)
 }
@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::slot_hashes;
+use crate::errors::WorthHubError;
+use crate::instructions::finalize::apply_finalize;
+use crate::instructions::settle::{record_truth, settle_topic};
+use crate::state::{SettlementRecord, Topic, TopicStatus, TruthHistory};
+
+/// Most commitments `finalize_and_settle` handles, keeping finalize plus
+/// every payout transfer within one transaction's compute budget
+pub const MAX_AUTO_SETTLE_COMMITMENTS: u32 = 8;
+
+#[derive(Accounts)]
+pub struct FinalizeAndSettle<'info> {
+    /// The oracle; also the settle signer, so it receives the keeper reward
+    #[account(mut)]
+    pub oracle_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = topic.oracle_authority == oracle_authority.key()
+            @ WorthHubError::UnauthorizedOracle,
+        constraint = (topic.status == TopicStatus::Open || topic.status == TopicStatus::Revealing)
+            @ WorthHubError::AlreadyFinalized,
+        constraint = topic.commitment_count <= MAX_AUTO_SETTLE_COMMITMENTS
+            @ WorthHubError::TooManyCommitmentsForAutoSettle,
+    )]
+    pub topic: Account<'info, Topic>,
+
+    /// Recent blockhashes, used to seed the random tie-break
+    /// CHECK: Address is pinned to the SlotHashes sysvar
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
+    /// The topic's price feed, required when it has one
+    /// CHECK: Compared against `topic.price_feed` and parsed by `read_price`
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    /// The topic creator, who receives the protocol fee
+    /// CHECK: Only receives lamports; address checked against the topic
+    #[account(mut, address = topic.authority @ WorthHubError::TopicAuthorityMismatch)]
+    pub topic_authority: UncheckedAccount<'info>,
+
+    /// The vault PDA holding staked SOL
+    /// CHECK: Validated by seeds and against `topic.vault`
+    #[account(
+        mut,
+        seeds = [b"vault", topic.key().as_ref()],
+        bump = topic.vault_bump,
+        address = topic.vault @ WorthHubError::VaultMismatch,
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Recent settled truths for the topic's symbol, as for settle
+    #[account(
+        init_if_needed,
+        payer = oracle_authority,
+        space = TruthHistory::MAX_SIZE,
+        seeds = [b"truth_history", topic.symbol.as_bytes()],
+        bump,
+    )]
+    pub truth_history: Account<'info, TruthHistory>,
+
+    /// Per-participant outcomes of the settle, as for settle
+    #[account(
+        init_if_needed,
+        payer = oracle_authority,
+        space = SettlementRecord::space(0),
+        seeds = [b"settlement", topic.key().as_ref()],
+        bump,
+    )]
+    pub settlement_record: Account<'info, SettlementRecord>,
+
+    pub system_program: Program<'info, System>,
+
+    // Remaining accounts: pairs of (commitment_account, participant_account)
+    // and any leaderboard entries, as for settle
+}
+
+/// Finalize a small topic and settle it in the same transaction, taking it
+/// straight to Settled. Runs exactly `finalize` and then `settle`; a
+/// rejected truth fails with its check's error, and a call before the
+/// reveal phase is over fails with `RevealPhaseNotEnded` even on a
+/// `soft_finalize` topic, so the truth history and settlement record
+/// created for the settle are rolled back with their rent.
+pub fn handle_finalize_and_settle<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FinalizeAndSettle<'info>>,
+    truth_value: i64,
+    truth_low: Option<i64>,
+    truth_high: Option<i64>,
+) -> Result<u64> {
+    let slot_hashes = ctx.accounts.slot_hashes.to_account_info();
+    let price_feed = ctx
+        .accounts
+        .price_feed
+        .as_ref()
+        .map(|f| f.to_account_info());
+    let finalized = apply_finalize(
+        &mut ctx.accounts.topic,
+        &slot_hashes,
+        price_feed.as_ref(),
+        truth_value,
+        truth_low,
+        truth_high,
+        &[],
+    )?;
+    require!(finalized, WorthHubError::RevealPhaseNotEnded);
+
+    let oracle_authority = ctx.accounts.oracle_authority.to_account_info();
+    let topic_authority = ctx.accounts.topic_authority.to_account_info();
+    let vault = ctx.accounts.vault.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let paid = settle_topic(
+        &mut ctx.accounts.topic,
+        &oracle_authority,
+        &topic_authority,
+        &vault,
+        &system_program,
+        &mut ctx.accounts.settlement_record,
+        ctx.bumps.settlement_record,
+        ctx.remaining_accounts,
+    )?;
+    record_truth(
+        &mut ctx.accounts.truth_history,
+        &ctx.accounts.topic,
+        ctx.bumps.truth_history,
+    )?;
+    Ok(paid)
+}
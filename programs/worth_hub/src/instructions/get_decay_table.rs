@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::instructions::settle::{LN_TABLE, MAX_PCT, PRECISION};
+
+/// LN_TABLE entries per page; the whole table would not fit in return data
+pub const DECAY_TABLE_PAGE_SIZE: usize = 32;
+
+/// Needs no accounts: the table is compiled into the program
+#[derive(Accounts)]
+pub struct GetDecayTable {}
+
+/// One page of the time-decay table, with the constants needed to apply it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct DecayTablePage {
+    /// Index in LN_TABLE of `entries[0]`, i.e. the N of ln(N + e)
+    pub offset: u32,
+    /// ln(N + e) × PRECISION for N = offset..offset + entries.len();
+    /// empty past the end of the table
+    pub entries: Vec<u128>,
+    pub table_len: u32,
+    pub precision: u128,
+    pub max_pct: i128,
+}
+
+/// Return page `page` of LN_TABLE, exactly as settle uses it
+pub fn handle_get_decay_table(_ctx: Context<GetDecayTable>, page: u32) -> Result<DecayTablePage> {
+    let start = (page as usize)
+        .saturating_mul(DECAY_TABLE_PAGE_SIZE)
+        .min(LN_TABLE.len());
+    let end = (start + DECAY_TABLE_PAGE_SIZE).min(LN_TABLE.len());
+    Ok(DecayTablePage {
+        offset: start as u32,
+        entries: LN_TABLE[start..end].to_vec(),
+        table_len: LN_TABLE.len() as u32,
+        precision: PRECISION,
+        max_pct: MAX_PCT,
+    })
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::state::Leaderboard;
+
+#[derive(Accounts)]
+pub struct InitLeaderboard<'info> {
+    #[account(mut)]
+    pub participant: Signer<'info>,
+
+    #[account(
+        init,
+        payer = participant,
+        space = Leaderboard::MAX_SIZE,
+        seeds = [b"leaderboard", participant.key().as_ref()],
+        bump,
+    )]
+    pub leaderboard: Account<'info, Leaderboard>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the participant's leaderboard entry. Settle credits it whenever
+/// it is passed after the (commitment, participant) pairs.
+pub fn handle_init_leaderboard(ctx: Context<InitLeaderboard>) -> Result<()> {
+    let leaderboard = &mut ctx.accounts.leaderboard;
+    leaderboard.participant = ctx.accounts.participant.key();
+    leaderboard.bump = ctx.bumps.leaderboard;
+
+    msg!(
+        "Leaderboard created: participant={}",
+        leaderboard.participant
+    );
+    Ok(())
+}
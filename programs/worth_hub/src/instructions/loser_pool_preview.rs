@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use crate::errors::WorthHubError;
+use crate::instructions::settle::{compute_settle_plan, forfeit, SettleEntry, SettleParams};
+use crate::state::{Commitment, Topic, TopicStatus};
+
+#[derive(Accounts)]
+pub struct LoserPoolPreview<'info> {
+    #[account(
+        constraint = topic.status != TopicStatus::Settled @ WorthHubError::AlreadySettled,
+    )]
+    pub topic: Account<'info, Topic>,
+
+    // Remaining accounts: every commitment on the topic, each exactly once
+}
+
+/// Return the loser pool settle would distribute, over the full commitment
+/// set. Once the topic is finalized this is settle's own figure, wrong-
+/// direction penalties included; before that it is what the commitments
+/// still unrevealed would forfeit if the reveal phase ended now.
+/// Refunded and abstained commitments are skipped, as in settle.
+pub fn handle_loser_pool_preview<'info>(
+    ctx: Context<'_, '_, 'info, 'info, LoserPoolPreview<'info>>,
+) -> Result<u64> {
+    let topic = &ctx.accounts.topic;
+    let topic_key = topic.key();
+    let remaining = ctx.remaining_accounts;
+    require!(
+        remaining.len() == topic.commitment_count as usize,
+        WorthHubError::IncompleteCommitmentSet
+    );
+
+    let mut entries: Vec<SettleEntry> = Vec::with_capacity(remaining.len());
+    for (i, info) in remaining.iter().enumerate() {
+        require_keys_eq!(
+            *info.owner,
+            crate::ID,
+            WorthHubError::InvalidCommitmentAccount
+        );
+        require!(
+            remaining[..i].iter().all(|prev| prev.key() != info.key()),
+            WorthHubError::IncompleteCommitmentSet
+        );
+        let commitment = Commitment::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require_keys_eq!(
+            commitment.topic,
+            topic_key,
+            WorthHubError::InvalidCommitmentAccount
+        );
+        commitment.verify_address(info.key)?;
+        if commitment.settled || commitment.abstained {
+            continue;
+        }
+        entries.push(SettleEntry {
+            stake: commitment.stake_amount,
+            weight: commitment.weight,
+            prediction: commitment.prediction_value,
+            submit_order: commitment.submit_order,
+            revealed: commitment.revealed,
+            interval: (commitment.interval_low, commitment.interval_high),
+            extra_predictions: commitment.extra_predictions,
+        });
+    }
+
+    let loser_pool = if topic.status == TopicStatus::Finalized {
+        u64::try_from(compute_settle_plan(&entries, &SettleParams::from_topic(topic))?.loser_pool)
+            .map_err(|_| WorthHubError::ArithmeticOverflow)?
+    } else if !topic.options.reward_enabled {
+        0
+    } else {
+        entries
+            .iter()
+            .filter(|e| !e.revealed)
+            .try_fold(0u64, |pool, e| pool.checked_add(forfeit(e, &topic.options)))
+            .ok_or(WorthHubError::ArithmeticOverflow)?
+    };
+
+    msg!(
+        "Loser pool preview: id={}, status={:?}, loser_pool={}",
+        topic.topic_id,
+        topic.status,
+        loser_pool
+    );
+    Ok(loser_pool)
+}
@@ -1,11 +1,63 @@
 pub mod create_topic;
+pub mod create_recurring_topics;
 pub mod commit;
+pub mod cancel_commitment;
 pub mod reveal;
+pub mod reveal_many;
+pub mod open_reveal;
 pub mod finalize;
+pub mod finalize_and_settle;
 pub mod settle;
+pub mod settle_dry_run;
+pub mod projected_payout;
+pub mod snapshot_consensus;
+pub mod refund_participant;
+pub mod void_topic;
+pub mod reopen_topic;
+pub mod set_min_stake;
+pub mod set_authority;
+pub mod authority_replace_oracle;
+pub mod verify_reveal;
+pub mod verify_range_proof;
+pub mod deadlines;
+pub mod topic_snapshot;
+pub mod ping_reveal_window;
+pub mod get_decay_table;
+pub mod participant_status;
+pub mod skip_reveal_turn;
+pub mod init_leaderboard;
+pub mod add_allowed_values;
+pub mod loser_pool_preview;
+pub mod self_refund;
 
 pub use create_topic::*;
+pub use create_recurring_topics::*;
 pub use commit::*;
+pub use cancel_commitment::*;
 pub use reveal::*;
+pub use reveal_many::*;
+pub use open_reveal::*;
 pub use finalize::*;
+pub use finalize_and_settle::*;
 pub use settle::*;
+pub use settle_dry_run::*;
+pub use projected_payout::*;
+pub use snapshot_consensus::*;
+pub use refund_participant::*;
+pub use void_topic::*;
+pub use reopen_topic::*;
+pub use set_min_stake::*;
+pub use set_authority::*;
+pub use authority_replace_oracle::*;
+pub use verify_reveal::*;
+pub use verify_range_proof::*;
+pub use deadlines::*;
+pub use topic_snapshot::*;
+pub use ping_reveal_window::*;
+pub use get_decay_table::*;
+pub use participant_status::*;
+pub use skip_reveal_turn::*;
+pub use init_leaderboard::*;
+pub use add_allowed_values::*;
+pub use loser_pool_preview::*;
+pub use self_refund::*;
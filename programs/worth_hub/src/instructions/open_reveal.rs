@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::errors::WorthHubError;
+use crate::state::{Topic, TopicStatus};
+
+#[derive(Accounts)]
+pub struct OpenReveal<'info> {
+    pub oracle_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = topic.oracle_authority == oracle_authority.key()
+            @ WorthHubError::UnauthorizedOracle,
+        constraint = topic.status == TopicStatus::Open @ WorthHubError::TopicNotOpen,
+    )]
+    pub topic: Account<'info, Topic>,
+}
+
+/// Move the topic into `Revealing` once the oracle has confirmed the
+/// underlying event. Required before reveals on `manual_reveal_gate` topics.
+pub fn handle_open_reveal(ctx: Context<OpenReveal>) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        ctx.accounts
+            .topic
+            .is_commit_phase_over(clock.unix_timestamp),
+        WorthHubError::CommitPhaseNotEnded
+    );
+
+    let topic = &mut ctx.accounts.topic;
+    topic.status = TopicStatus::Revealing;
+    topic.reveal_opened_at = clock.unix_timestamp;
+
+    msg!("Reveal phase opened: id={}", topic.topic_id);
+    Ok(())
+}
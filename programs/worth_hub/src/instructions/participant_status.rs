@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::state::{Commitment, Topic};
+
+#[derive(Accounts)]
+#[instruction(participant: Pubkey, nonce: u32)]
+pub struct ParticipantStatus<'info> {
+    pub topic: Account<'info, Topic>,
+
+    /// CHECK: Pinned to the participant's commitment PDA by seeds; may be
+    /// uninitialized, which `handle_participant_status` reports as not found
+    #[account(
+        seeds = [
+            b"commitment",
+            topic.key().as_ref(),
+            participant.as_ref(),
+            nonce.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub commitment: UncheckedAccount<'info>,
+}
+
+/// Where a participant's commitment stands; all false and zero when it
+/// does not exist
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommitmentStatus {
+    pub exists: bool,
+    pub revealed: bool,
+    pub abstained: bool,
+    pub settled: bool,
+    pub stake_amount: u64,
+}
+
+/// Report the flags and stake of the participant's commitment under
+/// `nonce`, without the caller having to fetch a possibly missing account
+pub fn handle_participant_status(
+    ctx: Context<ParticipantStatus>,
+    _participant: Pubkey,
+    _nonce: u32,
+) -> Result<CommitmentStatus> {
+    let info = ctx.accounts.commitment.to_account_info();
+    if info.data_is_empty() {
+        return Ok(CommitmentStatus::default());
+    }
+
+    let commitment = Commitment::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    Ok(CommitmentStatus {
+        exists: true,
+        revealed: commitment.revealed,
+        abstained: commitment.abstained,
+        settled: commitment.settled,
+        stake_amount: commitment.stake_amount,
+    })
+}
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+use crate::events::RevealWindowOpen;
+use crate::instructions::deadlines::ReadTopic;
+use crate::instructions::reveal::check_reveal_window;
+
+/// Emit `RevealWindowOpen` while reveals are being accepted, so notification
+/// services can remind participants before the deadline. Permissionless and
+/// mutates nothing; outside the window it fails with the error a reveal would.
+pub fn handle_ping_reveal_window(ctx: Context<ReadTopic>) -> Result<()> {
+    let topic = &ctx.accounts.topic;
+    check_reveal_window(topic)?;
+
+    emit!(RevealWindowOpen {
+        topic_id: topic.topic_id,
+        reveal_deadline: topic.reveal_deadline,
+        reveal_count: topic.reveal_count,
+        commitment_count: topic.commitment_count,
+    });
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use crate::errors::WorthHubError;
+use crate::instructions::settle::{prepare_settle, SettleRun};
+use crate::state::{Topic, TopicStatus};
+
+#[derive(Accounts)]
+pub struct ProjectedPayout<'info> {
+    #[account(
+        constraint = topic.status == TopicStatus::Finalized @ WorthHubError::TopicNotFinalized,
+    )]
+    pub topic: Account<'info, Topic>,
+
+    /// The vault PDA holding staked SOL
+    /// CHECK: Validated by seeds and against `topic.vault`
+    #[account(
+        seeds = [b"vault", topic.key().as_ref()],
+        bump = topic.vault_bump,
+        address = topic.vault @ WorthHubError::VaultMismatch,
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// The commitment whose payout to project; must also appear in the
+    /// remaining accounts
+    /// CHECK: Only its address is compared against the remaining accounts
+    pub commitment: UncheckedAccount<'info>,
+
+    // Remaining accounts: the same (commitment_account, participant_account)
+    // pairs settle would receive
+}
+
+/// Run the real settle math against the finalized truth and return what
+/// `settle` would pay the target commitment with the same remaining
+/// accounts: 0 for non-revealers and already-settled commitments.
+pub fn handle_projected_payout<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ProjectedPayout<'info>>,
+) -> Result<u64> {
+    let remaining = &ctx.remaining_accounts;
+    let target = ctx.accounts.commitment.key();
+    let pair = (0..remaining.len() / 2)
+        .find(|&i| remaining[i * 2].key() == target)
+        .ok_or(WorthHubError::CommitmentNotFound)?;
+
+    let rent_exempt_min = Rent::get()?.minimum_balance(0);
+    let vault_balance = ctx.accounts.vault.lamports();
+    let SettleRun { transfers, .. } = prepare_settle(
+        &ctx.accounts.topic,
+        ctx.accounts.topic.key(),
+        remaining,
+        vault_balance,
+        rent_exempt_min,
+    )?;
+
+    let payout = transfers
+        .iter()
+        .find(|&&(i, _)| i == pair)
+        .map_or(0, |&(_, payout)| payout);
+
+    msg!(
+        "Projected payout: commitment={}, lamports={}",
+        target,
+        payout
+    );
+    Ok(payout)
+}
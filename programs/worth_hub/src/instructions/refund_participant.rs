@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::errors::WorthHubError;
+use crate::state::{Commitment, Topic, TopicStatus};
+
+#[derive(Accounts)]
+pub struct RefundParticipant<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = topic.authority == authority.key() @ WorthHubError::UnauthorizedAuthority,
+        constraint = topic.status == TopicStatus::Finalized @ WorthHubError::TopicNotFinalized,
+    )]
+    pub topic: Account<'info, Topic>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"commitment",
+            topic.key().as_ref(),
+            commitment.participant.as_ref(),
+            commitment.nonce.to_le_bytes().as_ref(),
+        ],
+        bump = commitment.bump,
+        constraint = !Commitment::is_settled(&commitment.to_account_info().try_borrow_data()?)?
+            @ WorthHubError::CommitmentAlreadySettled,
+        constraint = commitment.revealed || commitment.abstained @ WorthHubError::NotRevealed,
+    )]
+    pub commitment: Account<'info, Commitment>,
+
+    /// CHECK: Must be the participant recorded on the commitment
+    #[account(mut, address = commitment.participant)]
+    pub participant: UncheckedAccount<'info>,
+
+    /// CHECK: Must be the stake payer recorded on the commitment
+    #[account(mut, address = commitment.stake_payer @ WorthHubError::InvalidRefundRecipient)]
+    pub stake_payer: UncheckedAccount<'info>,
+
+    /// The vault PDA holding staked SOL
+    /// CHECK: Validated by seeds and against `topic.vault`
+    #[account(
+        mut,
+        seeds = [b"vault", topic.key().as_ref()],
+        bump = topic.vault_bump,
+        address = topic.vault @ WorthHubError::VaultMismatch,
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Return a single revealed or abstained participant's stake (no bonus),
+/// plus their reveal bond if they revealed, once `refund_timeout` has
+/// passed since finalization. A non-revealer's stake is forfeit and only
+/// leaves through settle. Settle skips commitments refunded here, so the
+/// participant can never be paid twice.
+pub fn handle_refund_participant(ctx: Context<RefundParticipant>) -> Result<()> {
+    let topic = &ctx.accounts.topic;
+
+    let clock = Clock::get()?;
+    let refund_at = topic
+        .finalized_at
+        .checked_add(topic.options.refund_timeout)
+        .ok_or(WorthHubError::ArithmeticOverflow)?;
+    require!(
+        clock.unix_timestamp >= refund_at,
+        WorthHubError::RefundTimeoutNotReached
+    );
+
+    let refund = refund_stake(
+        &mut ctx.accounts.topic,
+        &mut ctx.accounts.commitment,
+        &ctx.accounts.participant.to_account_info(),
+        &ctx.accounts.stake_payer.to_account_info(),
+        &ctx.accounts.vault.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    msg!(
+        "Participant refunded: participant={}, refund={} lamports",
+        ctx.accounts.commitment.participant,
+        refund
+    );
+    Ok(())
+}
+
+/// Pay `commitment`'s stake (no bonus), plus its reveal bond if it
+/// revealed, out of the vault down to its rent-exempt minimum, and mark it
+/// settled so settle skips it. The stake goes to the commitment's stake
+/// payer and the bond to the participant, who posted it at reveal. Returns
+/// the lamports paid.
+pub(crate) fn refund_stake<'info>(
+    topic: &mut Account<'info, Topic>,
+    commitment: &mut Account<'info, Commitment>,
+    participant: &AccountInfo<'info>,
+    stake_payer: &AccountInfo<'info>,
+    vault: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<u64> {
+    // Keep the vault rent-exempt, same as settle
+    let rent_exempt_min = Rent::get()?.minimum_balance(0);
+    let bond = if commitment.revealed {
+        topic.options.reveal_bond
+    } else {
+        0
+    };
+    let owed = commitment
+        .stake_amount
+        .checked_add(bond)
+        .ok_or(WorthHubError::ArithmeticOverflow)?;
+    let refund = std::cmp::min(owed, vault.lamports().saturating_sub(rent_exempt_min));
+
+    // A short vault shortchanges the bond before the stake
+    let stake_refund = std::cmp::min(refund, commitment.stake_amount);
+    let topic_key = topic.key();
+    let vault_signer_seeds: &[&[u8]] = &[b"vault", topic_key.as_ref(), &[topic.vault_bump]];
+    for (to, amount) in [
+        (stake_payer, stake_refund),
+        (participant, refund - stake_refund),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: vault.clone(),
+                    to: to.clone(),
+                },
+                &[vault_signer_seeds],
+            ),
+            amount,
+        )?;
+    }
+
+    commitment.settled = true;
+
+    // The whole stake leaves escrow, even if the vault could not cover it
+    topic.escrowed_stake = topic.escrowed_stake.saturating_sub(commitment.stake_amount);
+    topic.settle_fund = topic.settle_fund.saturating_sub(bond);
+    // A snapshot taken before this refund still counts its stake; drop it
+    // so settle recomputes, or a new snapshot is taken
+    topic.consensus_snapshot_at = 0;
+    Ok(refund)
+}
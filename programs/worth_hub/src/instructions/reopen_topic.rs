@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::errors::WorthHubError;
+use crate::instructions::create_topic::{validate_deadlines, validate_reveal_window};
+use crate::state::{Topic, TopicStatus};
+
+#[derive(Accounts)]
+pub struct ReopenTopic<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = topic.authority == authority.key() @ WorthHubError::UnauthorizedAuthority,
+        constraint = (topic.status == TopicStatus::Open || topic.status == TopicStatus::Revealing)
+            @ WorthHubError::AlreadyFinalized,
+        constraint = topic.commitment_count == 0 @ WorthHubError::TopicHasCommitments,
+    )]
+    pub topic: Account<'info, Topic>,
+}
+
+/// Give a topic nobody committed to a fresh commit phase instead of
+/// abandoning its PDA
+pub fn handle_reopen_topic(
+    ctx: Context<ReopenTopic>,
+    commit_deadline: i64,
+    reveal_deadline: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    validate_deadlines(commit_deadline, reveal_deadline, clock.unix_timestamp)?;
+
+    let topic = &mut ctx.accounts.topic;
+    validate_reveal_window(
+        commit_deadline,
+        reveal_deadline,
+        topic.min_stake,
+        &topic.options,
+    )?;
+    topic.commit_deadline = commit_deadline;
+    topic.reveal_deadline = reveal_deadline;
+    topic.status = TopicStatus::Open;
+    topic.reveal_opened_at = 0;
+
+    msg!(
+        "Topic reopened: id={}, commit_deadline={}, reveal_deadline={}",
+        topic.topic_id,
+        commit_deadline,
+        reveal_deadline
+    );
+    Ok(())
+}
@@ -1,27 +1,45 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::keccak;
+use anchor_lang::system_program;
 use crate::errors::WorthHubError;
-use crate::state::{Commitment, Topic, TopicStatus};
+use crate::state::{Commitment, Phase, Topic, TopicConfig, TopicStatus};
 
 #[derive(Accounts)]
 pub struct RevealPrediction<'info> {
+    /// Pays the topic's reveal bond on a reveal, if it has one
+    #[account(mut)]
     pub participant: Signer<'info>,
 
+    /// Status is checked in `handle_reveal` so a finalized topic gets a
+    /// dedicated error
+    #[account(mut)]
+    pub topic: Account<'info, Topic>,
+
+    /// CHECK: Deserialized in `handle_reveal` so a participant who never
+    /// committed gets `CommitmentNotFound` instead of an opaque Anchor error.
+    /// The address is re-derived from the stored topic, participant and
+    /// nonce (see `Commitment::verify_address`).
     #[account(
         mut,
-        constraint = (topic.status == TopicStatus::Open || topic.status == TopicStatus::Revealing)
-            @ WorthHubError::InvalidTopicState,
+        constraint = !commitment.data_is_empty() @ WorthHubError::CommitmentNotFound,
+        owner = crate::ID @ WorthHubError::InvalidCommitmentAccount,
     )]
-    pub topic: Account<'info, Topic>,
+    pub commitment: UncheckedAccount<'info>,
 
+    /// The vault PDA, which holds the settle fund alongside the stakes
+    /// CHECK: Validated by seeds and against `topic.vault`
     #[account(
         mut,
-        seeds = [b"commitment", topic.key().as_ref(), participant.key().as_ref()],
-        bump = commitment.bump,
-        constraint = commitment.participant == participant.key(),
-        constraint = !commitment.revealed @ WorthHubError::AlreadyRevealed,
+        seeds = [b"vault", topic.key().as_ref()],
+        bump = topic.vault_bump,
+        address = topic.vault @ WorthHubError::VaultMismatch,
     )]
-    pub commitment: Account<'info, Commitment>,
+    pub vault: UncheckedAccount<'info>,
+
+    /// The topic's config PDA; required once `topic.config` is set
+    #[account(seeds = [b"topic_config", topic.key().as_ref()], bump = topic_config.bump)]
+    pub topic_config: Option<Account<'info, TopicConfig>>,
+
+    pub system_program: Program<'info, System>,
 }
 
 pub fn handle_reveal(
@@ -29,42 +47,248 @@ pub fn handle_reveal(
     prediction_value: i64,
     salt: [u8; 32],
 ) -> Result<()> {
-    let topic = &ctx.accounts.topic;
+    let participant = ctx.accounts.participant.key();
+    let hash = Commitment::compute_hash(prediction_value, &salt, &participant);
+    apply_reveal(ctx, prediction_value, prediction_value, hash, salt, &[])
+}
+
+/// Reveal an interval `[low, high]` committed with `compute_interval_hash`.
+/// The prediction value recorded for consensus is the interval midpoint.
+pub fn handle_reveal_interval(
+    ctx: Context<RevealPrediction>,
+    low: i64,
+    high: i64,
+    salt: [u8; 32],
+) -> Result<()> {
+    require!(low <= high, WorthHubError::InvalidInterval);
+    let participant = ctx.accounts.participant.key();
+    let hash = Commitment::compute_interval_hash(low, high, &salt, &participant);
+    apply_reveal(ctx, low, high, hash, salt, &[])
+}
+
+/// Reveal one prediction per truth dimension of a multi-dimension topic,
+/// committed with `compute_vector_hash`
+pub fn handle_reveal_vector(
+    ctx: Context<RevealPrediction>,
+    predictions: Vec<i64>,
+    salt: [u8; 32],
+) -> Result<()> {
+    let (&first, extra) = predictions
+        .split_first()
+        .ok_or(WorthHubError::InvalidDimensionCount)?;
+    let participant = ctx.accounts.participant.key();
+    let hash = Commitment::compute_vector_hash(&predictions, &salt, &participant);
+    apply_reveal(ctx, first, first, hash, salt, extra)
+}
 
-    // Check we're in the reveal window:
-    // After commit deadline, before reveal deadline
-    let clock = Clock::get()?;
+fn apply_reveal(
+    ctx: Context<RevealPrediction>,
+    low: i64,
+    high: i64,
+    computed_hash: [u8; 32],
+    salt: [u8; 32],
+    extra_predictions: &[i64],
+) -> Result<()> {
+    check_reveal_window(&ctx.accounts.topic)?;
+    let config = topic_config(&ctx.accounts.topic, ctx.accounts.topic_config.as_deref())?;
+    let participant = ctx.accounts.participant.key();
+    let commitment_info = ctx.accounts.commitment.to_account_info();
+    reveal_commitment(
+        &mut ctx.accounts.topic,
+        config,
+        &participant,
+        &commitment_info,
+        low,
+        high,
+        computed_hash,
+        salt,
+        extra_predictions,
+    )?;
+    collect_reveal_bond(
+        &mut ctx.accounts.topic,
+        &ctx.accounts.participant.to_account_info(),
+        &ctx.accounts.vault.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        1,
+    )
+}
+
+/// Move the topic's reveal bond for `count` reveals from the participant
+/// into the vault's settle fund
+pub(crate) fn collect_reveal_bond<'info>(
+    topic: &mut Account<'info, Topic>,
+    participant: &AccountInfo<'info>,
+    vault: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    count: u64,
+) -> Result<()> {
+    let bond = topic
+        .options
+        .reveal_bond
+        .checked_mul(count)
+        .ok_or(WorthHubError::ArithmeticOverflow)?;
+    if bond == 0 {
+        return Ok(());
+    }
+
+    system_program::transfer(
+        CpiContext::new(
+            system_program.clone(),
+            system_program::Transfer {
+                from: participant.clone(),
+                to: vault.clone(),
+            },
+        ),
+        bond,
+    )?;
+    topic.settle_fund = topic
+        .settle_fund
+        .checked_add(bond)
+        .ok_or(WorthHubError::ArithmeticOverflow)?;
+    Ok(())
+}
+
+/// Reject reveals outside the reveal window, or before the oracle opens a
+/// gated topic
+pub(crate) fn check_reveal_window(topic: &Topic) -> Result<()> {
+    // Reveal window: [commit_deadline, reveal_deadline), whether or not the
+    // status has caught up
+    let now = Clock::get()?.unix_timestamp;
+    match topic.effective_phase(now) {
+        Phase::Reveal => {}
+        Phase::Commit => return err!(WorthHubError::CommitPhaseNotEnded),
+        Phase::AwaitingFinalize => return err!(WorthHubError::RevealPhaseEnded),
+        Phase::Finalized | Phase::Settled => return err!(WorthHubError::RevealAfterFinalize),
+    }
+
+    // Gated topics wait for the oracle's open_reveal instead of the clock alone
+    if topic.options.manual_reveal_gate {
+        require!(
+            topic.status == TopicStatus::Revealing,
+            WorthHubError::RevealNotOpened
+        );
+    }
+    Ok(())
+}
+
+/// The config reveals must check: None for a topic without one, else the
+/// passed account, which must be there
+pub(crate) fn topic_config<'a>(
+    topic: &Topic,
+    config: Option<&'a TopicConfig>,
+) -> Result<Option<&'a TopicConfig>> {
+    if topic.config == Pubkey::default() {
+        return Ok(None);
+    }
+    require!(config.is_some(), WorthHubError::InvalidTopicConfig);
+    Ok(config)
+}
+
+/// Enforce `options.ordered_reveal`: a commitment may reveal once every
+/// earlier submit_order has revealed or been skipped, and revealing the
+/// pending one hands the turn to the next
+pub(crate) fn take_reveal_turn(topic: &mut Topic, submit_order: u32) -> Result<()> {
+    if !topic.options.ordered_reveal {
+        return Ok(());
+    }
     require!(
-        clock.unix_timestamp >= topic.commit_deadline,
-        WorthHubError::CommitPhaseNotEnded
+        submit_order <= topic.next_expected_reveal_order,
+        WorthHubError::RevealOutOfOrder
     );
+    if submit_order == topic.next_expected_reveal_order {
+        topic.next_expected_reveal_order += 1;
+        topic.reveal_turn_started_at = Clock::get()?.unix_timestamp;
+    }
+    Ok(())
+}
+
+/// Reject an all-zero salt, which defeats the hash's hiding, and on
+/// `bind_salt` topics a salt without the commitment's required prefix
+pub(crate) fn check_salt(
+    topic: &Account<Topic>,
+    commitment: &Commitment,
+    salt: &[u8; 32],
+) -> Result<()> {
+    require!(salt.iter().any(|&b| b != 0), WorthHubError::InvalidSalt);
+    if topic.options.bind_salt {
+        let prefix =
+            Commitment::salt_prefix(&topic.key(), &commitment.participant, commitment.nonce);
+        require!(salt[..8] == prefix, WorthHubError::InvalidSalt);
+    }
+    Ok(())
+}
+
+/// Shared reveal path, run after `check_reveal_window`. A point prediction
+/// is the degenerate interval `[value, value]`; `extra_predictions` holds
+/// one value per truth dimension after the first.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn reveal_commitment(
+    topic: &mut Account<Topic>,
+    config: Option<&TopicConfig>,
+    participant: &Pubkey,
+    commitment_info: &AccountInfo,
+    low: i64,
+    high: i64,
+    computed_hash: [u8; 32],
+    salt: [u8; 32],
+    extra_predictions: &[i64],
+) -> Result<()> {
+    let mut commitment = Commitment::try_deserialize(&mut &commitment_info.try_borrow_data()?[..])?;
     require!(
-        clock.unix_timestamp < topic.reveal_deadline,
-        WorthHubError::RevealPhaseEnded
+        commitment.topic == topic.key() && commitment.participant == *participant,
+        WorthHubError::InvalidCommitmentAccount
     );
+    commitment.verify_address(commitment_info.key)?;
+    require!(!commitment.revealed, WorthHubError::AlreadyRevealed);
+    require!(!commitment.abstained, WorthHubError::AlreadyAbstained);
 
-    // Verify hash: keccak256(prediction_value || salt || participant_address)
-    let participant_key = ctx.accounts.participant.key();
-    let mut hash_input = Vec::with_capacity(8 + 32 + 32);
-    hash_input.extend_from_slice(&prediction_value.to_le_bytes());
-    hash_input.extend_from_slice(&salt);
-    hash_input.extend_from_slice(participant_key.as_ref());
-
-    let computed_hash = keccak::hash(&hash_input);
     require!(
-        computed_hash.0 == ctx.accounts.commitment.commitment_hash,
+        computed_hash == commitment.commitment_hash,
         WorthHubError::HashMismatch
     );
+    check_salt(topic, &commitment, &salt)?;
+
+    require!(
+        extra_predictions.len() + 1 == topic.options.dimensions as usize,
+        WorthHubError::InvalidDimensionCount
+    );
+    require!(
+        Topic::is_value_in_range(low)
+            && Topic::is_value_in_range(high)
+            && extra_predictions
+                .iter()
+                .all(|&v| Topic::is_value_in_range(v)),
+        WorthHubError::ValueOutOfRange
+    );
+
+    // Discrete-outcome topics only accept predictions on the allowed grid,
+    // and bounded topics only predictions within their bounds; both apply
+    // to the first dimension only
+    require!(
+        [low, high]
+            .iter()
+            .all(|&v| topic.options.within_bounds(v) && config.is_none_or(|c| c.allows(v))),
+        WorthHubError::InvalidPredictionValue
+    );
+    take_reveal_turn(topic, commitment.submit_order)?;
 
     // Update commitment with revealed values
-    let commitment = &mut ctx.accounts.commitment;
+    let prediction_value = Commitment::interval_midpoint(low, high);
     commitment.prediction_value = prediction_value;
+    commitment.interval_low = low;
+    commitment.interval_high = high;
+    commitment.extra_predictions[..extra_predictions.len()].copy_from_slice(extra_predictions);
     commitment.salt = salt;
     commitment.revealed = true;
+    commitment.try_serialize(&mut &mut commitment_info.try_borrow_mut_data()?[..])?;
 
-    // Update topic state
-    let topic = &mut ctx.accounts.topic;
+    // Update topic state. Each commitment can only be revealed once, so the
+    // reveal count can never overtake the commitment count.
     topic.reveal_count += 1;
+    require!(
+        topic.reveal_count <= topic.commitment_count,
+        WorthHubError::InvariantViolated
+    );
 
     // Transition to Revealing status if still Open
     if topic.status == TopicStatus::Open {
@@ -78,3 +302,51 @@ pub fn handle_reveal(
     );
     Ok(())
 }
+
+/// Prove knowledge of a point commitment's prediction and salt but opt out
+/// of scoring. The stake is returned in full at settle and takes no part in
+/// consensus or the loser pool. Counts towards `reveal_count`, since the
+/// participant did not forfeit.
+pub fn handle_abstain(
+    ctx: Context<RevealPrediction>,
+    prediction_value: i64,
+    salt: [u8; 32],
+) -> Result<()> {
+    check_reveal_window(&ctx.accounts.topic)?;
+
+    let participant = ctx.accounts.participant.key();
+    let commitment_info = ctx.accounts.commitment.to_account_info();
+    let mut commitment = Commitment::try_deserialize(&mut &commitment_info.try_borrow_data()?[..])?;
+    require!(
+        commitment.topic == ctx.accounts.topic.key() && commitment.participant == participant,
+        WorthHubError::InvalidCommitmentAccount
+    );
+    commitment.verify_address(commitment_info.key)?;
+    require!(!commitment.revealed, WorthHubError::AlreadyRevealed);
+    require!(!commitment.abstained, WorthHubError::AlreadyAbstained);
+    require!(
+        Commitment::compute_hash(prediction_value, &salt, &participant)
+            == commitment.commitment_hash,
+        WorthHubError::HashMismatch
+    );
+    check_salt(&ctx.accounts.topic, &commitment, &salt)?;
+
+    take_reveal_turn(&mut ctx.accounts.topic, commitment.submit_order)?;
+
+    commitment.abstained = true;
+    commitment.salt = salt;
+    commitment.try_serialize(&mut &mut commitment_info.try_borrow_mut_data()?[..])?;
+
+    let topic = &mut ctx.accounts.topic;
+    topic.reveal_count += 1;
+    require!(
+        topic.reveal_count <= topic.commitment_count,
+        WorthHubError::InvariantViolated
+    );
+    if topic.status == TopicStatus::Open {
+        topic.status = TopicStatus::Revealing;
+    }
+
+    msg!("Commitment abstained: participant={}", participant);
+    Ok(())
+}
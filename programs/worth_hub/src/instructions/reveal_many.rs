@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use crate::errors::WorthHubError;
+use crate::instructions::reveal::{
+    check_reveal_window, collect_reveal_bond, reveal_commitment, topic_config,
+};
+use crate::state::{Commitment, Topic, TopicConfig};
+
+/// Most commitments `reveal_many` accepts, keeping the transaction within
+/// compute limits
+pub const MAX_REVEAL_BATCH: usize = 8;
+
+/// One commitment to reveal in a batch
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct RevealEntry {
+    /// The commitment's commit nonce (its index among the participant's commitments)
+    pub commitment_index: u32,
+    pub prediction_value: i64,
+    pub salt: [u8; 32],
+}
+
+#[derive(Accounts)]
+pub struct RevealMany<'info> {
+    /// Pays the topic's reveal bond for every entry, if it has one
+    #[account(mut)]
+    pub participant: Signer<'info>,
+
+    #[account(mut)]
+    pub topic: Account<'info, Topic>,
+
+    /// The vault PDA, which holds the settle fund alongside the stakes
+    /// CHECK: Validated by seeds and against `topic.vault`
+    #[account(
+        mut,
+        seeds = [b"vault", topic.key().as_ref()],
+        bump = topic.vault_bump,
+        address = topic.vault @ WorthHubError::VaultMismatch,
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// The topic's config PDA; required once `topic.config` is set
+    #[account(seeds = [b"topic_config", topic.key().as_ref()], bump = topic_config.bump)]
+    pub topic_config: Option<Account<'info, TopicConfig>>,
+
+    pub system_program: Program<'info, System>,
+
+    // Remaining accounts: one commitment account per entry, in order
+}
+
+/// Reveal several of the signer's commitments in one transaction. Each
+/// entry is checked exactly like a single `reveal`, and the whole batch
+/// fails if any entry does.
+pub fn handle_reveal_many<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RevealMany<'info>>,
+    reveals: Vec<RevealEntry>,
+) -> Result<()> {
+    require!(
+        !reveals.is_empty() && reveals.len() <= MAX_REVEAL_BATCH,
+        WorthHubError::InvalidRevealBatch
+    );
+    require!(
+        ctx.remaining_accounts.len() == reveals.len(),
+        WorthHubError::InvalidRevealBatch
+    );
+    check_reveal_window(&ctx.accounts.topic)?;
+    let config = topic_config(&ctx.accounts.topic, ctx.accounts.topic_config.as_deref())?;
+
+    let participant = ctx.accounts.participant.key();
+    let topic_key = ctx.accounts.topic.key();
+    for (entry, commitment_info) in reveals.iter().zip(ctx.remaining_accounts) {
+        let (expected, _) = Pubkey::find_program_address(
+            &[
+                b"commitment",
+                topic_key.as_ref(),
+                participant.as_ref(),
+                entry.commitment_index.to_le_bytes().as_ref(),
+            ],
+            &crate::ID,
+        );
+        require_keys_eq!(
+            commitment_info.key(),
+            expected,
+            WorthHubError::InvalidCommitmentAccount
+        );
+        require!(
+            !commitment_info.data_is_empty(),
+            WorthHubError::CommitmentNotFound
+        );
+
+        let hash = Commitment::compute_hash(entry.prediction_value, &entry.salt, &participant);
+        reveal_commitment(
+            &mut ctx.accounts.topic,
+            config,
+            &participant,
+            commitment_info,
+            entry.prediction_value,
+            entry.prediction_value,
+            hash,
+            entry.salt,
+            &[],
+        )?;
+    }
+    collect_reveal_bond(
+        &mut ctx.accounts.topic,
+        &ctx.accounts.participant.to_account_info(),
+        &ctx.accounts.vault.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        reveals.len() as u64,
+    )?;
+
+    msg!(
+        "Batch revealed: participant={}, count={}",
+        participant,
+        reveals.len()
+    );
+    Ok(())
+}
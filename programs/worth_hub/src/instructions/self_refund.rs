@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use crate::errors::WorthHubError;
+use crate::instructions::refund_participant::refund_stake;
+use crate::state::{Commitment, Topic, TopicStatus};
+
+#[derive(Accounts)]
+pub struct SelfRefund<'info> {
+    #[account(mut)]
+    pub participant: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = topic.status == TopicStatus::Finalized @ WorthHubError::TopicNotFinalized,
+    )]
+    pub topic: Account<'info, Topic>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"commitment",
+            topic.key().as_ref(),
+            participant.key().as_ref(),
+            commitment.nonce.to_le_bytes().as_ref(),
+        ],
+        bump = commitment.bump,
+        constraint = !Commitment::is_settled(&commitment.to_account_info().try_borrow_data()?)?
+            @ WorthHubError::CommitmentAlreadySettled,
+        constraint = commitment.revealed || commitment.abstained @ WorthHubError::NotRevealed,
+    )]
+    pub commitment: Account<'info, Commitment>,
+
+    /// CHECK: Must be the stake payer recorded on the commitment
+    #[account(mut, address = commitment.stake_payer @ WorthHubError::InvalidRefundRecipient)]
+    pub stake_payer: UncheckedAccount<'info>,
+
+    /// The vault PDA holding staked SOL
+    /// CHECK: Validated by seeds and against `topic.vault`
+    #[account(
+        mut,
+        seeds = [b"vault", topic.key().as_ref()],
+        bump = topic.vault_bump,
+        address = topic.vault @ WorthHubError::VaultMismatch,
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Escape hatch for a topic finalized but never settled: once
+/// `self_refund_timeout` has passed since finalization, a participant who
+/// revealed or abstained takes their own stake back (no bonus), exactly as
+/// `refund_participant` would pay it. Non-revealers forfeited theirs and
+/// cannot. Settle skips the refunded commitment afterwards.
+pub fn handle_self_refund(ctx: Context<SelfRefund>) -> Result<()> {
+    let topic = &ctx.accounts.topic;
+    let refund_at = topic
+        .finalized_at
+        .checked_add(topic.options.self_refund_timeout)
+        .ok_or(WorthHubError::ArithmeticOverflow)?;
+    require!(
+        Clock::get()?.unix_timestamp >= refund_at,
+        WorthHubError::RefundTimeoutNotReached
+    );
+
+    let refund = refund_stake(
+        &mut ctx.accounts.topic,
+        &mut ctx.accounts.commitment,
+        &ctx.accounts.participant.to_account_info(),
+        &ctx.accounts.stake_payer.to_account_info(),
+        &ctx.accounts.vault.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    msg!(
+        "Participant self-refunded: participant={}, refund={} lamports",
+        ctx.accounts.participant.key(),
+        refund
+    );
+    Ok(())
+}
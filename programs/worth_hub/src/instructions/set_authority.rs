@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::errors::WorthHubError;
+use crate::events::AuthorityTransferred;
+use crate::state::{Topic, TopicStatus};
+
+#[derive(Accounts)]
+pub struct SetAuthority<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = topic.authority == authority.key() @ WorthHubError::UnauthorizedAuthority,
+        constraint = topic.status != TopicStatus::Settled @ WorthHubError::AlreadySettled,
+    )]
+    pub topic: Account<'info, Topic>,
+}
+
+/// Hand the topic over to `new_authority`, e.g. to rotate from a hot wallet
+/// to a multisig. The new authority receives the protocol fee at settle and
+/// takes over every authority-gated instruction.
+pub fn handle_set_authority(ctx: Context<SetAuthority>, new_authority: Pubkey) -> Result<()> {
+    let topic = &mut ctx.accounts.topic;
+    let previous_authority = topic.authority;
+    topic.authority = new_authority;
+
+    emit!(AuthorityTransferred {
+        topic_id: topic.topic_id,
+        previous_authority,
+        new_authority,
+    });
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::errors::WorthHubError;
+use crate::instructions::create_topic::{validate_min_stake, validate_reveal_window};
+use crate::state::{Topic, TopicStatus};
+
+#[derive(Accounts)]
+pub struct SetMinStake<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = topic.authority == authority.key() @ WorthHubError::UnauthorizedAuthority,
+        constraint = topic.status == TopicStatus::Open @ WorthHubError::TopicNotOpen,
+        constraint = topic.commitment_count == 0 @ WorthHubError::TopicHasCommitments,
+    )]
+    pub topic: Account<'info, Topic>,
+}
+
+/// Correct a mis-set minimum stake before anyone has committed at the old one
+pub fn handle_set_min_stake(ctx: Context<SetMinStake>, min_stake: u64) -> Result<()> {
+    validate_min_stake(min_stake)?;
+
+    let topic = &mut ctx.accounts.topic;
+    validate_reveal_window(
+        topic.commit_deadline,
+        topic.reveal_deadline,
+        min_stake,
+        &topic.options,
+    )?;
+    let previous = topic.min_stake;
+    topic.min_stake = min_stake;
+
+    msg!(
+        "Minimum stake updated: id={}, {} -> {}",
+        topic.topic_id,
+        previous,
+        min_stake
+    );
+    Ok(())
+}
@@ -1,423 +1,4120 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
 use crate::errors::WorthHubError;
-use crate::state::{Commitment, Topic, TopicStatus};
+use crate::events::{TopicSettled, VaultUnderfunded};
+use crate::state::{
+    Commitment, Leaderboard, RewardMode, SettlementOutcome, SettlementRecord, TieBreak, Topic,
+    TopicOptions, TopicStatus, TruthHistory, TruthRecord, UnanimousPolicy,
+};
 
 /// Fixed-point precision: 1e6
-const PRECISION: u128 = 1_000_000;
+pub(crate) const PRECISION: u128 = 1_000_000;
 
 /// Maximum percentage deviation (100x = 10000%) to prevent overflow
-const MAX_PCT: i128 = 100_000_000; // PRECISION * 100
+pub(crate) const MAX_PCT: i128 = 100_000_000; // PRECISION * 100
+
+/// Working precision of the N >= 64 logarithm: 1e12
+const LN_PRECISION: u128 = 1_000_000_000_000;
+
+/// ln(2) * LN_PRECISION, rounded down
+const LN_2: u128 = 693_147_180_559;
+
+/// ln(PRECISION) * LN_PRECISION, rounded down
+const LN_PRECISION_SCALE: u128 = 13_815_510_557_964;
+
+/// e * PRECISION, rounded
+const E_FIXED: u128 = 2_718_282;
+
+/// Largest score multiplier UnderdogBoost grants, so a dust stake cannot
+/// claim the whole pool
+const MAX_UNDERDOG_BOOST: u128 = 16;
+
+/// Largest score multiplier DiversityBonus grants
+const MAX_DIVERSITY_BOOST: u128 = 4;
+
+/// Share of a non-revealer's stake forfeited under `forfeit_by_order`, by
+/// submit order; every later order forfeits the last entry, the full stake
+pub(crate) const FORFEIT_BY_ORDER_BPS: [u16; 6] = [5_000, 6_000, 7_000, 8_000, 9_000, 10_000];
 
 /// Precomputed ln(N + e) * PRECISION values for N = 0..63
-/// ln(0 + e) = 1.0, ln(1 + e) ≈ 1.313, ln(2 + e) ≈ 1.547, ...
-/// These are scaled by PRECISION (1e6)
-const LN_TABLE: [u128; 64] = [
+/// ln(0 + e) = 1.0, ln(1 + e) ≈ 1.313, ln(2 + e) ≈ 1.551, ...
+/// These are scaled by PRECISION (1e6) and strictly increasing, so the
+/// time-decay factor PRECISION² / ln(N + e) strictly decreases with N.
+pub(crate) const LN_TABLE: [u128; 64] = [
     1_000_000,  // ln(e) = 1.0
     1_313_262,  // ln(1 + e)
-    1_547_563,  // ln(2 + e)
-    1_734_601,  // ln(3 + e)
-    1_890_066,  // ln(4 + e)
-    2_022_971,  // ln(5 + e)
-    2_138_990,  // ln(6 + e)
-    2_241_671,  // ln(7 + e)
-    2_333_586,  // ln(8 + e)
-    2_416_540,  // ln(9 + e)
-    2_491_930,  // ln(10 + e)
-    2_560_867,  // ln(11 + e)
-    2_624_230,  // ln(12 + e)
-    2_682_718,  // ln(13 + e)
-    2_736_892,  // ln(14 + e)
-    2_787_200,  // ln(15 + e)
-    2_834_006,  // ln(16 + e)
-    2_877_612,  // ln(17 + e)
-    2_918_272,  // ln(18 + e)
-    2_956_202,  // ln(19 + e)
-    2_991_583,  // ln(20 + e)
-    3_024_572,  // ln(21 + e)
-    3_055_305,  // ln(22 + e)
-    3_083_901,  // ln(23 + e)
-    3_110_467,  // ln(24 + e)
-    3_135_098,  // ln(25 + e)
-    3_157_880,  // ln(26 + e)
-    3_178_889,  // ln(27 + e)
-    3_198_196,  // ln(28 + e)
-    3_215_862,  // ln(29 + e)
-    3_231_943,  // ln(30 + e)
-    3_246_491,  // ln(31 + e)
-    3_259_550,  // ln(32 + e)
-    3_271_162,  // ln(33 + e)
-    3_281_365,  // ln(34 + e)
-    3_290_193,  // ln(35 + e)
-    3_297_677,  // ln(36 + e)
-    3_303_847,  // ln(37 + e)
-    3_308_728,  // ln(38 + e)
-    3_312_345,  // ln(39 + e)
-    3_314_718,  // ln(40 + e)
-    3_315_869,  // ln(41 + e)
-    3_315_816,  // ln(42 + e)
-    3_314_576,  // ln(43 + e)
-    3_312_165,  // ln(44 + e)
-    3_308_598,  // ln(45 + e)
-    3_303_889,  // ln(46 + e)
-    3_298_050,  // ln(47 + e)
-    3_291_094,  // ln(48 + e)
-    3_283_031,  // ln(49 + e)
-    3_273_873,  // ln(50 + e)
-    3_263_628,  // ln(51 + e)
-    3_252_306,  // ln(52 + e)
-    3_239_916,  // ln(53 + e)
-    3_226_465,  // ln(54 + e)
-    3_211_962,  // ln(55 + e)
-    3_196_413,  // ln(56 + e)
-    3_179_826,  // ln(57 + e)
-    3_162_207,  // ln(58 + e)
-    3_143_562,  // ln(59 + e)
-    3_123_897,  // ln(60 + e)
-    3_103_218,  // ln(61 + e)
-    3_081_530,  // ln(62 + e)
-    3_058_839,  // ln(63 + e)
+    1_551_445,  // ln(2 + e)
+    1_743_668,  // ln(3 + e)
+    1_904_832,  // ln(4 + e)
+    2_043_592,  // ln(5 + e)
+    2_165_422,  // ln(6 + e)
+    2_274_009,  // ln(7 + e)
+    2_371_951,  // ln(8 + e)
+    2_461_150,  // ln(9 + e)
+    2_543_040,  // ln(10 + e)
+    2_618_729,  // ln(11 + e)
+    2_689_090,  // ln(12 + e)
+    2_754_824,  // ln(13 + e)
+    2_816_503,  // ln(14 + e)
+    2_874_597,  // ln(15 + e)
+    2_929_501,  // ln(16 + e)
+    2_981_546,  // ln(17 + e)
+    3_031_016,  // ln(18 + e)
+    3_078_154,  // ln(19 + e)
+    3_123_170,  // ln(20 + e)
+    3_166_246,  // ln(21 + e)
+    3_207_543,  // ln(22 + e)
+    3_247_202,  // ln(23 + e)
+    3_285_348,  // ln(24 + e)
+    3_322_092,  // ln(25 + e)
+    3_357_534,  // ln(26 + e)
+    3_391_762,  // ln(27 + e)
+    3_424_858,  // ln(28 + e)
+    3_456_893,  // ln(29 + e)
+    3_487_934,  // ln(30 + e)
+    3_518_040,  // ln(31 + e)
+    3_547_266,  // ln(32 + e)
+    3_575_663,  // ln(33 + e)
+    3_603_275,  // ln(34 + e)
+    3_630_145,  // ln(35 + e)
+    3_656_312,  // ln(36 + e)
+    3_681_812,  // ln(37 + e)
+    3_706_677,  // ln(38 + e)
+    3_730_939,  // ln(39 + e)
+    3_754_627,  // ln(40 + e)
+    3_777_766,  // ln(41 + e)
+    3_800_382,  // ln(42 + e)
+    3_822_498,  // ln(43 + e)
+    3_844_136,  // ln(44 + e)
+    3_865_315,  // ln(45 + e)
+    3_886_054,  // ln(46 + e)
+    3_906_373,  // ln(47 + e)
+    3_926_286,  // ln(48 + e)
+    3_945_811,  // ln(49 + e)
+    3_964_962,  // ln(50 + e)
+    3_983_753,  // ln(51 + e)
+    4_002_198,  // ln(52 + e)
+    4_020_308,  // ln(53 + e)
+    4_038_097,  // ln(54 + e)
+    4_055_574,  // ln(55 + e)
+    4_072_751,  // ln(56 + e)
+    4_089_638,  // ln(57 + e)
+    4_106_245,  // ln(58 + e)
+    4_122_580,  // ln(59 + e)
+    4_138_653,  // ln(60 + e)
+    4_154_472,  // ln(61 + e)
+    4_170_044,  // ln(62 + e)
+    4_185_377,  // ln(63 + e)
 ];
 
-/// Get ln(N + e) * PRECISION, with fallback approximation for N >= 64
+/// Get ln(N + e) * PRECISION: from LN_TABLE for N < 64, computed otherwise
+///
+/// With x = (N + e) * PRECISION = 2^k * m and m in [1, 2),
+/// ln(N + e) = k * ln(2) + ln(m) - ln(PRECISION). ln(m) comes from the
+/// series 2 * (t + t^3/3 + t^5/5 + ...) with t = (m - 1) / (m + 1) < 1/3,
+/// summed at LN_PRECISION until the terms vanish. Every step rounds down
+/// and grows with x, so the result never decreases, and it stays within
+/// 1e-6 of the true ln(N + e).
 fn ln_approx(n: u32) -> u128 {
     if (n as usize) < LN_TABLE.len() {
-        LN_TABLE[n as usize]
-    } else {
-        // For N >= 64, use approximation: ln(N + e) ≈ ln(N) ≈ ln(64) + (N-64)/64
-        // ln(64) * 1e6 ≈ 4_158_883
-        let base: u128 = 4_158_883;
-        let extra = ((n as u128) - 64) * PRECISION / 64;
-        base + extra / 10 // dampen the growth
+        return LN_TABLE[n as usize];
+    }
+    let x = n as u128 * PRECISION + E_FIXED;
+    let k = x.ilog2();
+    let base = 1u128 << k;
+    let t = (x - base) * LN_PRECISION / (x + base);
+    let t_sq = t * t / LN_PRECISION;
+    let mut ln_m = 0;
+    let mut power = t;
+    let mut denom = 1;
+    while power > 0 {
+        ln_m += 2 * power / denom;
+        power = power * t_sq / LN_PRECISION;
+        denom += 2;
     }
+    (LN_2 * k as u128 + ln_m - LN_PRECISION_SCALE) / (LN_PRECISION / PRECISION)
 }
 
 #[derive(Accounts)]
 pub struct SettleTopic<'info> {
+    /// Settle signer; receives the keeper reward
     #[account(mut)]
     pub authority: Signer<'info>,
 
     #[account(
         mut,
-        constraint = topic.status == TopicStatus::Finalized @ WorthHubError::InvalidTopicState,
-        constraint = (topic.authority == authority.key() || topic.oracle_authority == authority.key())
-            @ WorthHubError::UnauthorizedAuthority,
+        constraint = topic.status == TopicStatus::Finalized @ WorthHubError::TopicNotFinalized,
+        constraint = (topic.options.public_settle || topic.is_settler(&authority.key()))
+            @ WorthHubError::UnauthorizedSettler,
     )]
     pub topic: Account<'info, Topic>,
 
+    /// The topic creator, who receives the protocol fee whoever settles
+    /// CHECK: Only receives lamports; address checked against the topic
+    #[account(mut, address = topic.authority @ WorthHubError::TopicAuthorityMismatch)]
+    pub topic_authority: UncheckedAccount<'info>,
+
     /// The vault PDA holding staked SOL
-    /// CHECK: Validated by seeds
+    /// CHECK: Validated by seeds and against `topic.vault`
     #[account(
         mut,
         seeds = [b"vault", topic.key().as_ref()],
         bump = topic.vault_bump,
+        address = topic.vault @ WorthHubError::VaultMismatch,
     )]
     pub vault: UncheckedAccount<'info>,
 
+    /// Recent settled truths for the topic's symbol; the first settle of a
+    /// symbol creates it at the settle signer's expense
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = TruthHistory::MAX_SIZE,
+        seeds = [b"truth_history", topic.symbol.as_bytes()],
+        bump,
+    )]
+    pub truth_history: Account<'info, TruthHistory>,
+
+    /// Per-participant outcomes of this settle, created header-only and
+    /// grown to the participant count at the settle signer's expense
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = SettlementRecord::space(0),
+        seeds = [b"settlement", topic.key().as_ref()],
+        bump,
+    )]
+    pub settlement_record: Account<'info, SettlementRecord>,
+
     pub system_program: Program<'info, System>,
 
     // Remaining accounts: pairs of (commitment_account, participant_account)
-    // passed via ctx.remaining_accounts
+    // passed via ctx.remaining_accounts, optionally followed by leaderboard
+    // entries of settled participants (writable) to credit
 }
 
-/// Consensus-Deviation-Weighted Reward Formula
-///
-/// Instead of rewarding pure accuracy, this formula rewards predictions that
-/// deviate from the consensus in the correct direction. Bold, contrarian
-/// predictions that turn out to be right earn significantly more.
-///
-/// Algorithm:
-///   1. Compute stake-weighted consensus: μ = Σ(pred_i × stake_i) / Σ(stake_i)
-///   2. For each participant:
-///      - edge_pct  = (pred_i − μ) × PRECISION / |μ|    (% deviation from consensus)
-///      - truth_pct = (truth − μ) × PRECISION / |μ|     (% truth deviation from consensus)
-///      - alignment = edge_pct × truth_pct               (positive ⟹ correct direction)
-///   3. Score = max(0, alignment) × accuracy × time_decay
-///      where accuracy  = PRECISION² / (|truth − pred| + 1)
-///            time_decay = PRECISION² / ln(N + e)
-///   4. Payout = stake + loser_pool × score / Σ(scores)
-///
-/// Key properties:
-///   - Consensus predictors (edge ≈ 0) get near-zero bonus
-///   - Wrong-direction predictions (alignment < 0) get zero bonus
-///   - Bold + accurate predictions get the largest share
-pub fn handle_settle<'info>(ctx: Context<'_, '_, 'info, 'info, SettleTopic<'info>>) -> Result<()> {
-    let topic = &ctx.accounts.topic;
-    let truth = topic.truth_value;
-    let topic_key = topic.key();
+/// One commitment's inputs to the settle math
+#[derive(Clone, Debug)]
+pub(crate) struct SettleEntry {
+    pub stake: u64,
+    pub weight: u64,
+    pub prediction: i64,
+    pub submit_order: u32,
+    pub revealed: bool,
+    /// Revealed `[low, high]`, only scored by IntervalScoring
+    pub interval: (i64, i64),
+    /// Predictions for dimensions 2.., zero past the topic's dimension count
+    pub extra_predictions: [i64; 3],
+}
 
-    // Parse remaining accounts as commitment + participant pairs
-    let remaining = &ctx.remaining_accounts;
-    require!(remaining.len() % 2 == 0, WorthHubError::NoRevealedCommitments);
-    require!(!remaining.is_empty(), WorthHubError::NoRevealedCommitments);
+/// Topic-level inputs to the settle math
+pub(crate) struct SettleParams<'a> {
+    pub truth: i64,
+    /// Oracle confidence band `[low, high]`; predictions inside it get full accuracy
+    pub truth_band: Option<(i64, i64)>,
+    /// Truths for dimensions 2.., one per extra dimension of the topic
+    pub extra_truths: &'a [i64],
+    /// Consensus stored by `snapshot_consensus`, used instead of recomputing
+    /// it from the entries passed to settle
+    pub consensus: Option<i128>,
+    pub seed: [u8; 32],
+    pub options: &'a TopicOptions,
+}
 
-    let pair_count = remaining.len() / 2;
+impl<'a> SettleParams<'a> {
+    pub fn from_topic(topic: &'a Topic) -> Self {
+        Self {
+            truth: topic.truth_value,
+            truth_band: Some((topic.truth_low, topic.truth_high)),
+            extra_truths: &topic.extra_truths[..topic.options.dimensions as usize - 1],
+            consensus: (topic.consensus_snapshot_at != 0).then_some(topic.consensus),
+            seed: topic.finalize_seed,
+            options: &topic.options,
+        }
+    }
+}
+
+/// Outcome of the settle math, before any lamports move
+#[derive(Debug)]
+pub(crate) struct SettlePlan {
+    pub consensus: i128,
+    pub loser_pool: u128,
+    /// Unscaled payout per entry, in entry order
+    pub payouts: Vec<u64>,
+    /// Score per entry, summed across dimensions, in entry order
+    pub scores: Vec<u128>,
+    /// Unscaled reward for whoever signs settle, carved from the loser pool
+    pub keeper_reward: u64,
+    /// Stake-weighted variance of the revealed predictions around the
+    /// consensus; see `prediction_variance`
+    pub prediction_variance: u64,
+}
 
-    // ── Phase 1: Deserialize all commitments and compute consensus ──────
+/// floor(a × b / c), falling back to reduced precision instead of failing
+/// when a × b overflows u128. Callers guarantee b <= c, so the result never
+/// exceeds a.
+fn mul_div_floor(a: u128, mut b: u128, mut c: u128) -> u128 {
+    loop {
+        if let Some(product) = a.checked_mul(b) {
+            return product / c;
+        }
+        b >>= 1;
+        c >>= 1;
+    }
+}
 
-    struct ParticipantData {
-        commitment_index: usize,
-        participant_index: usize,
-        stake: u64,
-        prediction: i64,
-        submit_order: u32,
-        revealed: bool,
+/// ratio^(weight_bps / 10_000) for a PRECISION-scaled ratio in [0, PRECISION].
+/// The fractional part of the exponent is applied bit by bit through
+/// repeated square roots, so the result is exact to ~14 binary digits.
+fn pow_ratio(ratio: u128, weight_bps: u16) -> u128 {
+    let one = TopicOptions::NEUTRAL_WEIGHT_BPS as u32;
+    let ratio = ratio.min(PRECISION);
+    let mut result = PRECISION;
+    for _ in 0..(weight_bps as u32 / one) {
+        result = result * ratio / PRECISION;
+    }
+    let mut frac = weight_bps as u32 % one;
+    let mut root = ratio;
+    for _ in 0..14 {
+        if frac == 0 {
+            break;
+        }
+        root = (root * PRECISION).isqrt();
+        frac *= 2;
+        if frac >= one {
+            frac -= one;
+            result = result * root / PRECISION;
+        }
     }
+    result
+}
 
-    let mut participants: Vec<ParticipantData> = Vec::with_capacity(pair_count);
-    let mut consensus_num: i128 = 0; // Σ(prediction × stake)
-    let mut total_revealed_stake: u64 = 0;
-    let mut total_unrevealed_stake: u64 = 0;
+/// UnderdogBoost multiplier × PRECISION: total_revealed_stake / stake,
+/// clamped to [1, MAX_UNDERDOG_BOOST]
+fn underdog_boost(stake: u64, total_revealed_stake: u128) -> u128 {
+    let stake = (stake as u128).max(1);
+    (total_revealed_stake.saturating_mul(PRECISION) / stake)
+        .clamp(PRECISION, MAX_UNDERDOG_BOOST * PRECISION)
+}
 
-    for i in 0..pair_count {
-        let commitment_info = &remaining[i * 2];
-        let data = commitment_info.try_borrow_data()?;
+/// Distance from each revealed prediction to the nearest other revealed
+/// one; None for unrevealed entries and for a lone revealer. Sorting makes
+/// this O(n log n), so no cap on the participant count is needed.
+fn nearest_neighbor_distances(entries: &[SettleEntry]) -> Vec<Option<u128>> {
+    let mut revealed: Vec<usize> = (0..entries.len())
+        .filter(|&i| entries[i].revealed)
+        .collect();
+    revealed.sort_by_key(|&i| entries[i].prediction);
+
+    let mut distances: Vec<Option<u128>> = vec![None; entries.len()];
+    let gap = |a: usize, b: usize| {
+        (entries[a].prediction as i128 - entries[b].prediction as i128).unsigned_abs()
+    };
+    for (k, &i) in revealed.iter().enumerate() {
+        let prev = k.checked_sub(1).map(|p| gap(i, revealed[p]));
+        let next = revealed.get(k + 1).map(|&n| gap(i, n));
+        distances[i] = match (prev, next) {
+            (Some(p), Some(n)) => Some(p.min(n)),
+            (p, n) => p.or(n),
+        };
+    }
+    distances
+}
+
+/// DiversityBonus multiplier × PRECISION: 1 + nearest-neighbor distance
+/// relative to |consensus|, clamped to [1, MAX_DIVERSITY_BOOST]
+fn diversity_boost(nearest: Option<u128>, abs_consensus: u128) -> u128 {
+    let distance = nearest.unwrap_or(0);
+    PRECISION
+        .saturating_add(distance.saturating_mul(PRECISION) / abs_consensus.max(1))
+        .min(MAX_DIVERSITY_BOOST * PRECISION)
+}
+
+/// Time-decay factor PRECISION² / ln(N + e), or a flat PRECISION for topics
+/// that disable time decay
+fn time_decay(submit_order: u32, options: &TopicOptions) -> u128 {
+    if options.time_decay_enabled {
+        PRECISION * PRECISION / ln_approx(submit_order)
+    } else {
+        PRECISION
+    }
+}
+
+/// Lamports a non-revealer forfeits to the loser pool: the whole stake, or
+/// its submit order's share of it under `forfeit_by_order`
+pub(crate) fn forfeit(e: &SettleEntry, options: &TopicOptions) -> u64 {
+    if !options.forfeit_by_order {
+        return e.stake;
+    }
+    let order = (e.submit_order as usize).min(FORFEIT_BY_ORDER_BPS.len() - 1);
+    mul_div_floor(
+        e.stake as u128,
+        FORFEIT_BY_ORDER_BPS[order] as u128,
+        TopicOptions::MAX_BPS as u128,
+    ) as u64
+}
+
+/// IntervalScoring score: PRECISION² / (width + 1) × time_decay / PRECISION
+/// when `truth` lies in the entry's interval, zero otherwise
+fn interval_score(e: &SettleEntry, truth: i64, options: &TopicOptions) -> u128 {
+    let (low, high) = e.interval;
+    if truth < low || truth > high {
+        return 0;
+    }
+    let width = (high as i128 - low as i128) as u128;
+    let tightness = PRECISION * PRECISION / (width + 1);
+    let t_f = time_decay(e.submit_order, options);
+    // Both factors are at most PRECISION², so the product fits in u128
+    tightness * t_f / PRECISION
+}
+
+/// Weighted consensus of the revealed predictions plus the revealed and
+/// forfeited stake totals
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct ConsensusTotals {
+    pub consensus: i128,
+    pub total_revealed_stake: u64,
+    pub total_unrevealed_stake: u64,
+}
 
-        let commitment: Commitment =
-            Commitment::try_deserialize(&mut &data[..])
-                .map_err(|_| WorthHubError::NoRevealedCommitments)?;
+/// Weighted consensus μ = Σ(pred_i × weight_i) / Σ(weight_i) over revealed
+/// entries (0 when nobody revealed). The single source of the consensus for
+/// settle, its dry runs and previews, and `snapshot_consensus`.
+pub(crate) fn compute_consensus(entries: &[SettleEntry]) -> Result<ConsensusTotals> {
+    let mut consensus_num: i128 = 0; // Σ(prediction × weight)
+    let mut total_revealed_weight: u128 = 0;
+    let mut total_revealed_stake: u64 = 0;
+    let mut total_unrevealed_stake: u64 = 0;
 
-        if commitment.revealed {
+    for e in entries {
+        if e.revealed {
             consensus_num = consensus_num
                 .checked_add(
-                    (commitment.prediction_value as i128)
-                        .checked_mul(commitment.stake_amount as i128)
-                        .ok_or(WorthHubError::ArithmeticOverflow)?,
+                    (e.prediction as i128)
+                        .checked_mul(e.weight as i128)
+                        .ok_or(WorthHubError::ConsensusOverflow)?,
                 )
-                .ok_or(WorthHubError::ArithmeticOverflow)?;
+                .ok_or(WorthHubError::ConsensusOverflow)?;
+            total_revealed_weight = total_revealed_weight
+                .checked_add(e.weight as u128)
+                .ok_or(WorthHubError::ConsensusOverflow)?;
             total_revealed_stake = total_revealed_stake
-                .checked_add(commitment.stake_amount)
-                .ok_or(WorthHubError::ArithmeticOverflow)?;
+                .checked_add(e.stake)
+                .ok_or(WorthHubError::ConsensusOverflow)?;
         } else {
             total_unrevealed_stake = total_unrevealed_stake
-                .checked_add(commitment.stake_amount)
-                .ok_or(WorthHubError::ArithmeticOverflow)?;
+                .checked_add(e.stake)
+                .ok_or(WorthHubError::ConsensusOverflow)?;
         }
-
-        participants.push(ParticipantData {
-            commitment_index: i * 2,
-            participant_index: i * 2 + 1,
-            stake: commitment.stake_amount,
-            prediction: commitment.prediction_value,
-            submit_order: commitment.submit_order,
-            revealed: commitment.revealed,
-        });
     }
 
-    // Stake-weighted consensus of revealed predictions
-    let consensus: i128 = if total_revealed_stake > 0 {
-        consensus_num / (total_revealed_stake as i128)
+    let consensus: i128 = if total_revealed_weight > 0 {
+        consensus_num / (total_revealed_weight as i128)
     } else {
         0
     };
 
-    // ── Phase 2: Compute consensus-deviation-weighted scores ────────────
+    Ok(ConsensusTotals {
+        consensus,
+        total_revealed_stake,
+        total_unrevealed_stake,
+    })
+}
+
+/// Stake-weighted variance Σ stake_i × (pred_i − μ)² / Σ stake_i over the
+/// revealed entries, around the consensus μ. Predictions carry PRECISION, so
+/// the raw squares carry PRECISION²; the result is divided by PRECISION once
+/// and is the variance in the predictions' own fixed point (1.0 = 1e6).
+/// Saturates at u64::MAX; 0 when nobody revealed.
+pub(crate) fn prediction_variance(entries: &[SettleEntry], consensus: i128) -> u64 {
+    let total_stake: u128 = entries
+        .iter()
+        .filter(|e| e.revealed)
+        .map(|e| e.stake as u128)
+        .sum();
+    if total_stake == 0 {
+        return 0;
+    }
+    let mut variance: u128 = 0;
+    for e in entries.iter().filter(|e| e.revealed) {
+        let deviation = (e.prediction as i128 - consensus).unsigned_abs();
+        let square = deviation.saturating_mul(deviation) / PRECISION;
+        variance = variance.saturating_add(mul_div_floor(square, e.stake as u128, total_stake));
+    }
+    variance.min(u64::MAX as u128) as u64
+}
+
+/// Distance from `prediction` to the band `[low, high]`, zero inside it
+fn band_distance(prediction: i64, (low, high): (i64, i64)) -> u128 {
+    if prediction < low {
+        (low as i128 - prediction as i128) as u128
+    } else if prediction > high {
+        (prediction as i128 - high as i128) as u128
+    } else {
+        0
+    }
+}
+
+/// Split `pool` by score with no share above `cap`. Each round caps every
+/// entry whose proportional share of what is left would reach the cap, then
+/// reruns over the rest, so the excess flows to the next-highest scorers.
+/// Rounding dust goes to `winner`, or the top uncapped scorer once the winner
+/// is capped. Whatever nobody can take (everyone capped) is left undistributed.
+fn capped_shares(pool: u128, scores: &[u128], cap: u128, winner: usize) -> Vec<u128> {
+    let mut shares: Vec<u128> = vec![0; scores.len()];
+    let mut capped: Vec<bool> = scores.iter().map(|&s| s == 0).collect();
+    let mut remaining = pool;
+
+    loop {
+        let open_score: u128 = scores
+            .iter()
+            .zip(&capped)
+            .filter(|(_, &c)| !c)
+            .map(|(&s, _)| s)
+            .sum();
+        if open_score == 0 || remaining == 0 {
+            return shares;
+        }
+
+        let mut newly_capped = false;
+        for i in 0..scores.len() {
+            if !capped[i] && mul_div_floor(remaining, scores[i], open_score) >= cap {
+                shares[i] = cap;
+                capped[i] = true;
+                newly_capped = true;
+            }
+        }
+        if newly_capped {
+            let taken = shares
+                .iter()
+                .zip(&capped)
+                .filter(|(_, &c)| c)
+                .map(|(&s, _)| s)
+                .sum::<u128>();
+            remaining = pool.saturating_sub(taken);
+            continue;
+        }
+
+        let mut distributed: u128 = 0;
+        for i in 0..scores.len() {
+            if !capped[i] {
+                shares[i] = mul_div_floor(remaining, scores[i], open_score);
+                distributed += shares[i];
+            }
+        }
+        let dust_to = if capped[winner] {
+            (0..scores.len())
+                .filter(|&i| !capped[i])
+                .max_by_key(|&i| scores[i])
+        } else {
+            Some(winner)
+        };
+        if let Some(i) = dust_to {
+            shares[i] = (shares[i] + remaining - distributed).min(cap);
+        }
+        return shares;
+    }
+}
+
+/// Index of the top-scoring revealed entry, breaking ties per `tiebreak`.
+/// Returns None when no entry has a positive score.
+fn pick_winner(
+    entries: &[SettleEntry],
+    scores: &[u128],
+    tiebreak: TieBreak,
+    seed: &[u8; 32],
+) -> Option<usize> {
+    let top = scores.iter().copied().max().filter(|&s| s > 0)?;
+    let mut tied: Vec<usize> = (0..entries.len())
+        .filter(|&i| entries[i].revealed && scores[i] == top)
+        .collect();
+    tied.sort_by_key(|&i| entries[i].submit_order);
+
+    match tiebreak {
+        TieBreak::EarliestOrder => tied.first().copied(),
+        TieBreak::LatestOrder => tied.last().copied(),
+        TieBreak::Random => {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(&seed[..8]);
+            let pick = u64::from_le_bytes(word) % tied.len() as u64;
+            Some(tied[pick as usize])
+        }
+    }
+}
+
+/// Index of the revealed entry closest to the truth band, the earliest
+/// submit_order winning ties. None when nobody revealed.
+fn closest_entry(entries: &[SettleEntry], band: (i64, i64)) -> Option<usize> {
+    (0..entries.len())
+        .filter(|&i| entries[i].revealed)
+        .min_by_key(|&i| {
+            (
+                band_distance(entries[i].prediction, band),
+                entries[i].submit_order,
+            )
+        })
+}
 
+/// Scores one dimension of the entries against its truth and consensus
+/// (steps 2–3 of `compute_settle_plan`), returning each entry's score and
+/// the stake it forfeits for predicting against the truth
+fn score_dimension(
+    entries: &[SettleEntry],
+    truth: i64,
+    band: (i64, i64),
+    consensus: i128,
+    total_revealed_stake: u128,
+    options: &TopicOptions,
+) -> Result<(Vec<u128>, Vec<u64>)> {
     let truth_i128 = truth as i128;
     let truth_edge: i128 = truth_i128 - consensus;
 
-    // Use |consensus| for percentage normalization (min 1 to avoid division by zero)
-    let abs_consensus: i128 = consensus.unsigned_abs().max(1) as i128;
+    // Use |consensus| for percentage normalization, raised to the topic's
+    // consensus floor (min 1 to avoid division by zero)
+    let abs_consensus: i128 = consensus
+        .unsigned_abs()
+        .max(options.consensus_floor as u128)
+        .max(1) as i128;
 
     // truth_edge as percentage of consensus (capped to prevent overflow)
     let truth_edge_pct: i128 = (truth_edge
         .checked_mul(PRECISION as i128)
-        .ok_or(WorthHubError::ArithmeticOverflow)?
+        .ok_or(WorthHubError::EdgePctOverflow)?
         / abs_consensus)
-        .max(-MAX_PCT)
-        .min(MAX_PCT);
-
-    struct ScoredParticipant {
-        commitment_index: usize,
-        participant_index: usize,
-        stake: u64,
-        score: u128,
-        revealed: bool,
-    }
+        .clamp(-MAX_PCT, MAX_PCT);
 
-    let mut scored: Vec<ScoredParticipant> = Vec::with_capacity(pair_count);
-    let mut total_score: u128 = 0;
+    // (alignment, accuracy) for each revealed entry aligned with the truth,
+    // and the stake each revealer against it forfeits to the loser pool
+    let mut factors: Vec<Option<(u128, u128)>> = Vec::with_capacity(entries.len());
+    let mut penalties: Vec<u64> = vec![0; entries.len()];
+    for (e, penalty) in entries.iter().zip(penalties.iter_mut()) {
+        if !e.revealed
+            || matches!(
+                options.reward_mode,
+                RewardMode::IntervalScoring | RewardMode::ClosestWins
+            )
+        {
+            factors.push(None);
+            continue;
+        }
 
-    for p in &participants {
-        if p.revealed {
-            // Percentage deviation from consensus (capped)
-            let edge_i: i128 = (p.prediction as i128) - consensus;
-            let edge_pct: i128 = (edge_i
-                .checked_mul(PRECISION as i128)
-                .ok_or(WorthHubError::ArithmeticOverflow)?
-                / abs_consensus)
-                .max(-MAX_PCT)
-                .min(MAX_PCT);
-
-            // Alignment = edge_pct × truth_edge_pct
-            // Positive when prediction deviates from consensus in the SAME direction as truth
-            let alignment_i: i128 = edge_pct
-                .checked_mul(truth_edge_pct)
-                .ok_or(WorthHubError::ArithmeticOverflow)?;
-
-            let score: u128 = if alignment_i > 0 {
-                let alignment: u128 = alignment_i as u128;
-
-                // Accuracy weight: PRECISION² / (|truth − prediction| + 1)
-                let error = (truth_i128 - p.prediction as i128).unsigned_abs();
-                let w_e: u128 = PRECISION * PRECISION / (error + 1);
-
-                // Time decay: PRECISION² / ln(N + e)
-                let ln_val = ln_approx(p.submit_order);
-                let t_f: u128 = PRECISION * PRECISION / ln_val;
-
-                // score = alignment × w_e / PRECISION × t_f / PRECISION
-                let step1 = alignment
-                    .checked_mul(w_e)
-                    .ok_or(WorthHubError::ArithmeticOverflow)?
-                    / PRECISION;
-                step1
-                    .checked_mul(t_f)
-                    .ok_or(WorthHubError::ArithmeticOverflow)?
-                    / PRECISION
-            } else {
-                // Wrong direction or exactly on consensus → no bonus
-                0
-            };
+        // Percentage deviation from consensus (capped)
+        let edge_i: i128 = (e.prediction as i128) - consensus;
+        let edge_pct: i128 = (edge_i
+            .checked_mul(PRECISION as i128)
+            .ok_or(WorthHubError::EdgePctOverflow)?
+            / abs_consensus)
+            .clamp(-MAX_PCT, MAX_PCT);
 
-            total_score = total_score
-                .checked_add(score)
-                .ok_or(WorthHubError::ArithmeticOverflow)?;
+        // Alignment = edge_pct × truth_edge_pct
+        // Positive when prediction deviates from consensus in the SAME direction as truth
+        let alignment_i: i128 = edge_pct
+            .checked_mul(truth_edge_pct)
+            .ok_or(WorthHubError::EdgePctOverflow)?;
 
-            scored.push(ScoredParticipant {
-                commitment_index: p.commitment_index,
-                participant_index: p.participant_index,
-                stake: p.stake,
-                score,
-                revealed: true,
-            });
+        if alignment_i > 0 {
+            // Accuracy weight: PRECISION² / (distance to truth band beyond
+            // the topic's tolerance + 1)
+            let error = band_distance(e.prediction, band)
+                .saturating_sub(options.accuracy_tolerance as u128);
+            let w_e: u128 = PRECISION * PRECISION / (error + 1);
+            factors.push(Some((alignment_i as u128, w_e)));
         } else {
-            scored.push(ScoredParticipant {
-                commitment_index: p.commitment_index,
-                participant_index: p.participant_index,
-                stake: p.stake,
-                score: 0,
-                revealed: false,
-            });
+            // Wrong direction or exactly on consensus → no bonus; only the
+            // wrong direction is penalized
+            if alignment_i < 0 {
+                *penalty = mul_div_floor(
+                    e.stake as u128,
+                    options.wrong_direction_penalty_bps as u128,
+                    TopicOptions::MAX_BPS as u128,
+                ) as u64;
+            }
+            factors.push(None);
         }
     }
 
-    // ── Phase 3: Distribute rewards ─────────────────────────────────────
+    // Non-neutral weights exponentiate each factor, normalized to the best
+    // participant's so the base stays in (0, 1]
+    let weighted = options.accuracy_weight_bps != TopicOptions::NEUTRAL_WEIGHT_BPS
+        || options.boldness_weight_bps != TopicOptions::NEUTRAL_WEIGHT_BPS;
+    let max_alignment = factors.iter().flatten().map(|f| f.0).max().unwrap_or(1);
+    let max_accuracy = factors.iter().flatten().map(|f| f.1).max().unwrap_or(1);
 
-    // The "loser pool" is the unrevealed stakes (people who didn't reveal forfeit)
-    let loser_pool = total_unrevealed_stake as u128;
+    let nearest = if options.reward_mode == RewardMode::DiversityBonus {
+        nearest_neighbor_distances(entries)
+    } else {
+        Vec::new()
+    };
 
-    // We need to keep the vault rent-exempt. A 0-data account needs ~890_880 lamports.
-    // Reserve this from the pool.
-    let rent = Rent::get()?;
-    let rent_exempt_min = rent.minimum_balance(0);
+    let mut scores: Vec<u128> = Vec::with_capacity(entries.len());
 
-    // Calculate all payouts first
-    let mut payouts: Vec<u64> = Vec::with_capacity(scored.len());
-    let mut total_payout: u64 = 0;
+    for (i, (e, factor)) in entries.iter().zip(&factors).enumerate() {
+        let score: u128 = if e.revealed && options.reward_mode == RewardMode::IntervalScoring {
+            interval_score(e, truth, options)
+        } else if let Some((alignment, w_e)) = *factor {
+            // Time decay: PRECISION² / ln(N + e), unless disabled
+            let t_f = time_decay(e.submit_order, options);
 
-    for sp in &scored {
-        let payout: u64 = if sp.revealed && total_score > 0 {
-            let bonus = loser_pool
-                .checked_mul(sp.score)
-                .ok_or(WorthHubError::ArithmeticOverflow)?
-                / total_score;
-            sp.stake
-                .checked_add(bonus as u64)
-                .ok_or(WorthHubError::ArithmeticOverflow)?
-        } else if sp.revealed {
-            // Revealed but total_score is 0 (e.g. truth == consensus) → return stake
-            sp.stake
+            let step1 = if weighted {
+                // (alignment / max)^boldness × (accuracy / max)^accuracy, in PRECISION
+                let boldness = pow_ratio(
+                    mul_div_floor(alignment, PRECISION, max_alignment),
+                    options.boldness_weight_bps,
+                );
+                let accuracy = pow_ratio(
+                    mul_div_floor(w_e, PRECISION, max_accuracy),
+                    options.accuracy_weight_bps,
+                );
+                boldness * accuracy
+            } else {
+                // alignment × w_e / PRECISION
+                alignment
+                    .checked_mul(w_e)
+                    .ok_or(WorthHubError::ScoreOverflow)?
+                    / PRECISION
+            };
+            // score = step1 × t_f / PRECISION
+            let base = step1.checked_mul(t_f).ok_or(WorthHubError::ScoreOverflow)? / PRECISION;
+
+            match options.reward_mode {
+                RewardMode::UnderdogBoost => {
+                    base.checked_mul(underdog_boost(e.stake, total_revealed_stake))
+                        .ok_or(WorthHubError::ScoreOverflow)?
+                        / PRECISION
+                }
+                RewardMode::DiversityBonus => {
+                    base.checked_mul(diversity_boost(nearest[i], abs_consensus as u128))
+                        .ok_or(WorthHubError::ScoreOverflow)?
+                        / PRECISION
+                }
+                _ => base,
+            }
         } else {
             0
         };
-        total_payout = total_payout
-            .checked_add(payout)
-            .ok_or(WorthHubError::ArithmeticOverflow)?;
-        payouts.push(payout);
+
+        scores.push(score);
     }
 
-    // Distribute rewards via CPI invoke_signed
-    let vault_info = ctx.accounts.vault.to_account_info();
-    let authority_info = ctx.accounts.authority.to_account_info();
-    let system_prog = ctx.accounts.system_program.to_account_info();
-    let topic_key_bytes = topic_key.as_ref();
-    let vault_bump = topic.vault_bump;
-    let bump_slice = &[vault_bump];
-    let vault_signer_seeds: &[&[u8]] = &[b"vault", topic_key_bytes, bump_slice];
+    Ok((scores, penalties))
+}
 
-    // Cap total payout so vault keeps rent-exempt minimum
-    let vault_balance = vault_info.lamports();
-    let max_distributable = vault_balance.saturating_sub(rent_exempt_min);
+/// Whether someone revealed and every revealed prediction equals `consensus`
+fn is_unanimous(entries: &[SettleEntry], consensus: i128) -> bool {
+    let mut revealed = entries.iter().filter(|e| e.revealed).peekable();
+    revealed.peek().is_some() && revealed.all(|e| e.prediction as i128 == consensus)
+}
+
+/// Consensus-Deviation-Weighted Reward Formula
+///
+/// Instead of rewarding pure accuracy, this formula rewards predictions that
+/// deviate from the consensus in the correct direction. Bold, contrarian
+/// predictions that turn out to be right earn significantly more.
+///
+/// Algorithm:
+///   1. Compute weighted consensus: μ = Σ(pred_i × weight_i) / Σ(weight_i)
+///      (weight_i defaults to stake_i; lamports always move by stake_i),
+///      or take `params.consensus` when `snapshot_consensus` stored one
+///   2. For each participant:
+///      - edge_pct  = (pred_i − μ) × PRECISION / |μ|    (% deviation from consensus)
+///      - truth_pct = (truth − μ) × PRECISION / |μ|     (% truth deviation from consensus)
+///      - alignment = edge_pct × truth_pct               (positive ⟹ correct direction)
+///   3. Score = max(0, alignment) × accuracy × time_decay
+///      where accuracy   = PRECISION² / (distance from pred to truth band + 1),
+///      the distance measured beyond `options.accuracy_tolerance`
+///      and   time_decay = PRECISION² / ln(N + e), or PRECISION for every N
+///      when `options.time_decay_enabled` is false
+///   4. Payout = stake + loser_pool × score / Σ(scores)
+///      (WinnerTakeAll: the top scorer takes the whole loser_pool)
+///      (ClosestWins: no scores; the revealed prediction nearest the truth
+///      takes the whole loser_pool, the earliest commit winning ties)
+///      (UnderdogBoost: score × min(Σ revealed stake / stake_i, MAX_UNDERDOG_BOOST))
+///      (DiversityBonus: score × min(1 + nearest_i / |μ|, MAX_DIVERSITY_BOOST))
+///      (nearest_i: distance to the closest other revealed prediction)
+///
+/// Non-revealers forfeit their whole stake to the loser pool, or with
+/// `options.forfeit_by_order` the `FORFEIT_BY_ORDER_BPS` share for their
+/// submit order, keeping the rest.
+///
+/// `options.keeper_reward_bps` of the loser pool is carved out first for the
+/// settle signer. When `options.reveal_rebate_bps` is set, that share of the loser pool is
+/// first split equally among all revealers, whatever their score, and only
+/// the rest is distributed by score.
+///
+/// `options.max_payout_bps` caps any one participant's share of that
+/// score-weighted pool; the excess is redistributed to the next-highest
+/// scorers (see `capped_shares`).
+///
+/// `options.boldness_weight_bps` and `options.accuracy_weight_bps` (10_000 =
+/// neutral) raise alignment and accuracy, each normalized to the best
+/// participant's, to that power before multiplying, biasing the score toward
+/// contrarian boldness or raw accuracy.
+///
+/// IntervalScoring replaces steps 2–3: an interval containing the truth
+/// scores PRECISION² / (high − low + 1) × time_decay, any other scores zero.
+///
+/// With `options.dimensions` > 1, steps 1–3 run once per dimension against
+/// that dimension's truth and consensus and the scores are summed; wrong-
+/// direction penalties are averaged across dimensions. ClosestWins and the
+/// reported consensus use the first dimension only.
+///
+/// When every revealed prediction equals the consensus, every edge is zero
+/// and nobody scores. Under `UnanimousPolicy::Refund` the whole loser pool
+/// then goes back to every committer pro-rata to stake instead of being
+/// left undistributed. ClosestWins, which needs no scores, is unaffected.
+///
+/// With `options.reward_enabled` off, scores are still computed (settle
+/// records them and credits leaderboards with them) but nothing is
+/// redistributed: every entry, revealed or not, is paid its stake back.
+///
+/// In proportional mode the rounding dust left by the floor divisions goes
+/// to the top scorer, so the loser pool is always fully distributed when
+/// anyone scores. Ties for top score are broken by `options.tiebreak`.
+///
+/// Key properties:
+///   - Consensus predictors (edge ≈ 0) get near-zero bonus
+///   - Wrong-direction predictions (alignment < 0) get zero bonus
+///   - Bold + accurate predictions get the largest share
+pub(crate) fn compute_settle_plan(
+    entries: &[SettleEntry],
+    params: &SettleParams,
+) -> Result<SettlePlan> {
+    let truth = params.truth;
+
+    // ── Phase 1: Compute consensus ──────────────────────────────────────
+
+    let ConsensusTotals {
+        consensus,
+        total_revealed_stake,
+        ..
+    } = compute_consensus(entries)?;
+    let consensus = params.consensus.unwrap_or(consensus);
+    let total_revealed_stake = total_revealed_stake as u128;
+
+    // ── Phase 2: Compute consensus-deviation-weighted scores ────────────
+
+    let options = params.options;
+    let (mut scores, mut penalties) = score_dimension(
+        entries,
+        truth,
+        params.truth_band.unwrap_or((truth, truth)),
+        consensus,
+        total_revealed_stake,
+        options,
+    )?;
+
+    // Each extra dimension is scored against its own truth and consensus;
+    // scores are summed and penalties averaged across dimensions
+    for (d, &extra_truth) in params.extra_truths.iter().enumerate() {
+        let dim: Vec<SettleEntry> = entries
+            .iter()
+            .map(|e| SettleEntry {
+                prediction: e.extra_predictions[d],
+                interval: (e.extra_predictions[d], e.extra_predictions[d]),
+                ..*e
+            })
+            .collect();
+        let dim_consensus = compute_consensus(&dim)?.consensus;
+        let (dim_scores, dim_penalties) = score_dimension(
+            &dim,
+            extra_truth,
+            (extra_truth, extra_truth),
+            dim_consensus,
+            total_revealed_stake,
+            options,
+        )?;
+        for (score, dim_score) in scores.iter_mut().zip(dim_scores) {
+            *score = score
+                .checked_add(dim_score)
+                .ok_or(WorthHubError::ScoreOverflow)?;
+        }
+        for (penalty, dim_penalty) in penalties.iter_mut().zip(dim_penalties) {
+            *penalty += dim_penalty;
+        }
+    }
+    let dimensions = params.extra_truths.len() as u64 + 1;
+    for penalty in penalties.iter_mut() {
+        *penalty /= dimensions;
+    }
+
+    let mut total_score: u128 = 0;
+    for &score in &scores {
+        total_score = total_score
+            .checked_add(score)
+            .ok_or(WorthHubError::ScoreOverflow)?;
+    }
+
+    if !options.reward_enabled {
+        return Ok(SettlePlan {
+            consensus,
+            loser_pool: 0,
+            payouts: entries.iter().map(|e| e.stake).collect(),
+            scores,
+            keeper_reward: 0,
+            prediction_variance: prediction_variance(entries, consensus),
+        });
+    }
+
+    // ── Phase 3: Compute payouts ────────────────────────────────────────
 
-    for (i, sp) in scored.iter().enumerate() {
-        let participant_info = &remaining[sp.participant_index];
-        let commitment_info = &remaining[sp.commitment_index];
+    // The "loser pool" is the unrevealed stakes (people who didn't reveal
+    // forfeit) plus any wrong-direction penalties
+    let forfeits: Vec<u64> = entries
+        .iter()
+        .map(|e| if e.revealed { 0 } else { forfeit(e, options) })
+        .collect();
+    let total_forfeit: u128 = forfeits.iter().map(|&f| f as u128).sum();
+    let total_penalty: u128 = penalties.iter().map(|&p| p as u128).sum();
+    let loser_pool = total_forfeit + total_penalty;
+
+    let winner = if params.options.reward_mode == RewardMode::ClosestWins {
+        closest_entry(entries, params.truth_band.unwrap_or((truth, truth)))
+    } else {
+        pick_winner(entries, &scores, params.options.tiebreak, &params.seed)
+    };
 
-        let mut payout = payouts[i];
+    // Bonus per entry out of the loser pool
+    let mut bonuses: Vec<u128> = vec![0; entries.len()];
+    let revealer_count = entries.iter().filter(|e| e.revealed).count() as u128;
 
-        // Scale down if we'd exceed distributable amount
-        if total_payout > max_distributable && total_payout > 0 {
-            payout = (payout as u128 * max_distributable as u128 / total_payout as u128) as u64;
+    // Only the revealed share (by count) of the pool is released when the
+    // topic ties it to the reveal ratio, and none of it when a unanimous
+    // reveal is refunded; the rest goes back to every committer pro-rata to
+    // stake, its rounding dust staying released
+    let refund_unanimous = options.unanimous_policy == UnanimousPolicy::Refund
+        && options.reward_mode != RewardMode::ClosestWins
+        && total_score == 0
+        && is_unanimous(entries, consensus);
+    let mut returns: Vec<u128> = vec![0; entries.len()];
+    let mut released_pool = loser_pool;
+    let retained = if refund_unanimous {
+        loser_pool
+    } else if params.options.pool_release_by_reveal_ratio && !entries.is_empty() {
+        loser_pool - loser_pool * revealer_count / entries.len() as u128
+    } else {
+        0
+    };
+    if retained > 0 {
+        let total_stake: u128 = entries.iter().map(|e| e.stake as u128).sum();
+        for (ret, e) in returns.iter_mut().zip(entries) {
+            *ret = mul_div_floor(e.stake as u128, retained, total_stake.max(1));
+            released_pool -= *ret;
         }
+    }
 
-        if payout > 0 {
-            let current_vault = vault_info.lamports();
-            let actual_payout = std::cmp::min(payout, current_vault.saturating_sub(rent_exempt_min));
-
-            if actual_payout > 0 {
-                system_program::transfer(
-                    CpiContext::new_with_signer(
-                        system_prog.clone(),
-                        system_program::Transfer {
-                            from: vault_info.clone(),
-                            to: participant_info.clone(),
-                        },
-                        &[vault_signer_seeds],
-                    ),
-                    actual_payout,
-                )?;
+    // Keeper reward for the settle signer comes off the top
+    let keeper_reward =
+        released_pool * params.options.keeper_reward_bps as u128 / TopicOptions::MAX_BPS as u128;
+    let distributable_pool = released_pool - keeper_reward;
+
+    // Reveal-effort rebate, split equally; its division dust stays in the
+    // score-weighted pool
+    let mut score_pool = distributable_pool;
+    let rebate_pool = distributable_pool * params.options.reveal_rebate_bps as u128
+        / TopicOptions::MAX_BPS as u128;
+    if let Some(rebate) = rebate_pool.checked_div(revealer_count) {
+        for (bonus, e) in bonuses.iter_mut().zip(entries) {
+            if e.revealed {
+                *bonus = rebate;
             }
         }
+        score_pool -= rebate * revealer_count;
+    }
 
-        // Mark commitment as settled
-        let mut data = commitment_info.try_borrow_mut_data()?;
-        // settled field is at offset: 8(disc) + 32(topic) + 32(participant) + 32(hash)
-        //   + 8(stake) + 4(order) + 8(prediction) + 1(revealed) + 32(salt) = 157
-        // settled is a bool at offset 157
-        if data.len() > 157 {
-            data[157] = 1; // true
+    if let Some(w) = winner {
+        match params.options.reward_mode {
+            RewardMode::Proportional
+            | RewardMode::UnderdogBoost
+            | RewardMode::IntervalScoring
+            | RewardMode::DiversityBonus
+                if params.options.max_payout_bps > 0 =>
+            {
+                let cap = mul_div_floor(
+                    score_pool,
+                    params.options.max_payout_bps as u128,
+                    TopicOptions::MAX_BPS as u128,
+                );
+                let shares = capped_shares(score_pool, &scores, cap, w);
+                let distributed: u128 = shares.iter().sum();
+                require!(
+                    distributed <= score_pool && shares.iter().all(|&s| s <= cap),
+                    WorthHubError::InvariantViolated
+                );
+                for (bonus, share) in bonuses.iter_mut().zip(shares) {
+                    *bonus += share;
+                }
+            }
+            RewardMode::Proportional
+            | RewardMode::UnderdogBoost
+            | RewardMode::IntervalScoring
+            | RewardMode::DiversityBonus => {
+                let mut distributed: u128 = 0;
+                for (bonus, &score) in bonuses.iter_mut().zip(&scores) {
+                    // score <= total_score, so the share never exceeds the pool
+                    let share = mul_div_floor(score_pool, score, total_score);
+                    *bonus += share;
+                    distributed += share;
+                }
+                bonuses[w] += score_pool - distributed;
+            }
+            RewardMode::WinnerTakeAll | RewardMode::ClosestWins => bonuses[w] += score_pool,
         }
     }
 
-    // Transfer remaining vault balance (minus rent) to authority as protocol fee
-    let remaining_vault = vault_info.lamports().saturating_sub(rent_exempt_min);
-    if remaining_vault > 0 {
-        system_program::transfer(
-            CpiContext::new_with_signer(
-                system_prog.clone(),
-                system_program::Transfer {
-                    from: vault_info.clone(),
-                    to: authority_info.clone(),
-                },
-                &[vault_signer_seeds],
-            ),
-            remaining_vault,
-        )?;
+    let mut payouts: Vec<u64> = Vec::with_capacity(entries.len());
+    for ((((e, &bonus), &penalty), &ret), &forfeit) in entries
+        .iter()
+        .zip(&bonuses)
+        .zip(&penalties)
+        .zip(&returns)
+        .zip(&forfeits)
+    {
+        // Revealed participants get their stake back less any wrong-direction
+        // penalty (no one scoring, e.g. truth == consensus, just returns
+        // stakes); non-revealers keep only what they did not forfeit, bar
+        // any retained-pool return
+        let payout: u64 = if e.revealed {
+            (e.stake - penalty)
+                .checked_add(bonus as u64)
+                .ok_or(WorthHubError::PayoutOverflow)?
+        } else {
+            e.stake - forfeit
+        };
+        payouts.push(
+            payout
+                .checked_add(ret as u64)
+                .ok_or(WorthHubError::PayoutOverflow)?,
+        );
     }
 
-    // Mark topic as settled
-    let topic = &mut ctx.accounts.topic;
-    topic.status = TopicStatus::Settled;
-
-    msg!(
-        "Topic settled: id={}, truth={}, consensus={}, participants={}, loser_pool={}",
-        topic.topic_id,
-        truth,
+    Ok(SettlePlan {
         consensus,
-        scored.len(),
-        loser_pool
-    );
+        loser_pool,
+        payouts,
+        scores,
+        keeper_reward: keeper_reward as u64,
+        prediction_variance: prediction_variance(entries, consensus),
+    })
+}
 
-    Ok(())
+/// Scale payouts down proportionally when their sum exceeds what the vault
+/// can pay out while staying rent-exempt. Scaled payouts sum to exactly
+/// `max_distributable`: the lamports lost to truncation go one each to the
+/// largest remainders, ties to the earliest entry.
+pub(crate) fn scale_payouts(payouts: &[u64], max_distributable: u64) -> Result<Vec<u64>> {
+    let mut total_payout: u64 = 0;
+    for &payout in payouts {
+        total_payout = total_payout
+            .checked_add(payout)
+            .ok_or(WorthHubError::PayoutOverflow)?;
+    }
+
+    if total_payout <= max_distributable {
+        return Ok(payouts.to_vec());
+    }
+
+    let (total, max) = (total_payout as u128, max_distributable as u128);
+    let mut scaled: Vec<u64> = Vec::with_capacity(payouts.len());
+    let mut remainders: Vec<(u128, usize)> = Vec::with_capacity(payouts.len());
+    for (i, &payout) in payouts.iter().enumerate() {
+        let product = payout as u128 * max;
+        scaled.push((product / total) as u64);
+        remainders.push((product % total, i));
+    }
+
+    // Fewer than payouts.len() lamports are left, so each gets at most one
+    let residual = max_distributable - scaled.iter().sum::<u64>();
+    remainders.sort_unstable_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    for &(_, i) in remainders.iter().take(residual as usize) {
+        scaled[i] += 1;
+    }
+    Ok(scaled)
+}
+
+/// `scale_payouts`, except that each payout keeps up to its floor in full
+/// when the floors fit in `max_distributable`, and only the part above it is
+/// scaled. Floors that do not fit fall back to plain proportional scaling.
+pub(crate) fn scale_payouts_above_floors(
+    payouts: &[u64],
+    floors: &[u64],
+    max_distributable: u64,
+) -> Result<Vec<u64>> {
+    let kept: Vec<u64> = payouts
+        .iter()
+        .zip(floors)
+        .map(|(&p, &f)| p.min(f))
+        .collect();
+    let mut total_kept: u64 = 0;
+    for &k in &kept {
+        total_kept = total_kept
+            .checked_add(k)
+            .ok_or(WorthHubError::PayoutOverflow)?;
+    }
+    if total_kept > max_distributable {
+        return scale_payouts(payouts, max_distributable);
+    }
+
+    let excess: Vec<u64> = payouts.iter().zip(&kept).map(|(&p, &k)| p - k).collect();
+    let scaled = scale_payouts(&excess, max_distributable - total_kept)?;
+    Ok(kept.iter().zip(scaled).map(|(&k, s)| k + s).collect())
+}
+
+/// Settle's split of the vault balance above the rent-exempt minimum
+pub(crate) struct VaultAllocation {
+    /// Participant payouts, in input order
+    pub payouts: Vec<u64>,
+    /// Reward for the settle signer
+    pub keeper_reward: u64,
+    /// Remainder swept to the topic authority
+    pub protocol_fee: u64,
+}
+
+/// Add `extra` to `payouts` pro-rata, the rounding dust going to the
+/// largest payout. All-zero payouts are left as they are.
+fn spread_pro_rata(payouts: &mut [u64], extra: u64) {
+    let total: u128 = payouts.iter().map(|&p| p as u128).sum();
+    if total == 0 || extra == 0 {
+        return;
+    }
+    let mut given: u64 = 0;
+    for payout in payouts.iter_mut() {
+        let share = mul_div_floor(*payout as u128, extra as u128, total) as u64;
+        *payout += share;
+        given += share;
+    }
+    if let Some(largest) = payouts.iter_mut().max_by_key(|p| **p) {
+        *largest += extra - given;
+    }
+}
+
+/// Partition the vault above `rent_exempt_min` in strict priority order:
+/// participant payouts, scaled down to `max_distributable` if need be
+/// (sparing each payout's entry in `floors`, see `scale_payouts_above_floors`); then
+/// the keeper reward, out of what `max_distributable` has left; then the
+/// protocol fee, which takes everything else. The three parts sum to exactly
+/// the balance above rent, so no transfer can dip into the rent reserve.
+///
+/// Whatever `max_distributable` has left after participants and keeper is
+/// owed to participants when their payouts were scaled down (the rounding
+/// dust), and with `leftover_to_participants` otherwise too; it is then
+/// spread over the payouts pro-rata instead of joining the protocol fee.
+/// The fee only ever keeps lamports beyond `max_distributable` in that case.
+pub(crate) fn allocate_vault(
+    payouts: &[u64],
+    floors: &[u64],
+    keeper_reward: u64,
+    vault_balance: u64,
+    rent_exempt_min: u64,
+    max_distributable: u64,
+    leftover_to_participants: bool,
+) -> Result<VaultAllocation> {
+    let available = vault_balance.saturating_sub(rent_exempt_min);
+    let max_distributable = max_distributable.min(available);
+
+    let mut scaled = scale_payouts_above_floors(payouts, floors, max_distributable)?;
+    let was_scaled = scaled.as_slice() != payouts;
+    let keeper_budget = max_distributable
+        .checked_sub(scaled.iter().sum())
+        .ok_or(WorthHubError::InvariantViolated)?;
+    // Rounding dust left by scaled-down participants is not the keeper's
+    let keeper_reward = if was_scaled {
+        0
+    } else {
+        keeper_reward.min(keeper_budget)
+    };
+    if was_scaled || leftover_to_participants {
+        spread_pro_rata(&mut scaled, keeper_budget - keeper_reward);
+    }
+    let payouts = scaled;
+    let paid: u64 = payouts.iter().sum();
+    let protocol_fee = available
+        .checked_sub(paid)
+        .and_then(|rest| rest.checked_sub(keeper_reward))
+        .ok_or(WorthHubError::InvariantViolated)?;
+
+    require!(
+        paid.checked_add(keeper_reward)
+            .and_then(|t| t.checked_add(protocol_fee))
+            == Some(available),
+        WorthHubError::InvariantViolated
+    );
+    Ok(VaultAllocation {
+        payouts,
+        keeper_reward,
+        protocol_fee,
+    })
+}
+
+/// Move `rebate` lamports per revealed entry out of the protocol fee and
+/// into its payout, whose leading part lines up with `entries`. A fee too
+/// small to cover every revealer is split equally, its division dust
+/// staying with the fee.
+pub(crate) fn pay_reveal_rebates(
+    allocation: &mut VaultAllocation,
+    entries: &[SettleEntry],
+    rebate: u64,
+) {
+    let revealers = entries.iter().filter(|e| e.revealed).count() as u64;
+    if rebate == 0 || revealers == 0 {
+        return;
+    }
+    let each = rebate.min(allocation.protocol_fee / revealers);
+    for (payout, e) in allocation.payouts.iter_mut().zip(entries) {
+        if e.revealed {
+            *payout += each;
+        }
+    }
+    allocation.protocol_fee -= each * revealers;
+}
+
+/// Pro-rata refunds of `stakes` out of `max_distributable`, used when the
+/// vault cannot cover the stakes it should hold. Never sums to more than
+/// `max_distributable`.
+pub(crate) fn pro_rata_refunds(stakes: &[u64], max_distributable: u64) -> Vec<u64> {
+    let total_stake: u128 = stakes.iter().map(|&s| s as u128).sum();
+    stakes
+        .iter()
+        .map(|&s| mul_div_floor(s as u128, max_distributable as u128, total_stake.max(1)) as u64)
+        .collect()
+}
+
+/// Pay the settle fund out alongside `transfers`, whose leading entries
+/// line up with `entries`: each revealer gets their reveal bond back less
+/// the keeper's share, and the keeper everything else in the fund. Returns
+/// the keeper's part.
+fn add_settle_fund(
+    topic: &Topic,
+    entries: &[SettleEntry],
+    transfers: &mut [(usize, u64)],
+) -> Result<u64> {
+    let bond = topic.options.reveal_bond;
+    let keeper_cut = mul_div_floor(
+        bond as u128,
+        topic.options.reveal_bond_keeper_bps as u128,
+        TopicOptions::MAX_BPS as u128,
+    ) as u64;
+    let bond_refund = bond - keeper_cut;
+
+    let mut refunded: u64 = 0;
+    for ((_, payout), e) in transfers.iter_mut().zip(entries) {
+        if e.revealed {
+            *payout = payout
+                .checked_add(bond_refund)
+                .ok_or(WorthHubError::PayoutOverflow)?;
+            refunded = refunded
+                .checked_add(bond_refund)
+                .ok_or(WorthHubError::PayoutOverflow)?;
+        }
+    }
+    // Every revealer posted a full bond, so the fund always covers the refunds
+    Ok(topic
+        .settle_fund
+        .checked_sub(refunded)
+        .ok_or(WorthHubError::InvariantViolated)?)
+}
+
+/// What settle will do for a given set of remaining accounts, computed
+/// without moving any lamports
+pub(crate) struct SettleRun {
+    pub plan: SettlePlan,
+    /// Commitments taking part (already-settled ones are skipped)
+    pub entry_count: usize,
+    /// (pair index in remaining_accounts, scaled payout plus any reveal
+    /// bond refund) per entry
+    pub transfers: Vec<(usize, u64)>,
+    /// Keeper reward for the settle signer, after participant payouts,
+    /// plus its share of the settle fund
+    pub keeper_reward: u64,
+    /// Remainder of the vault above rent, swept to the topic authority
+    pub protocol_fee: u64,
+    /// Stake of the entries when the vault held less than that, in which
+    /// case every entry is refunded pro-rata and no rewards are paid
+    pub underfunded_stake: Option<u64>,
+    /// What each transfer settles, in `transfers` order
+    pub outcomes: Vec<SettlementOutcome>,
+}
+
+/// Split settle's remaining accounts into the (commitment, participant)
+/// pairs and the trailing leaderboard entries
+pub(crate) fn split_leaderboards<'a, 'info>(
+    remaining: &'a [AccountInfo<'info>],
+) -> (&'a [AccountInfo<'info>], &'a [AccountInfo<'info>]) {
+    let is_leaderboard = |info: &AccountInfo| {
+        *info.owner == crate::ID
+            && info
+                .try_borrow_data()
+                .is_ok_and(|data| data.starts_with(Leaderboard::DISCRIMINATOR))
+    };
+    let trailing = remaining
+        .iter()
+        .rev()
+        .take_while(|info| is_leaderboard(info))
+        .count();
+    remaining.split_at(remaining.len() - trailing)
+}
+
+/// Parse the (commitment, participant) pairs, run the settle math and
+/// allocate the vault with `allocate_vault`. Trailing leaderboard entries
+/// are ignored.
+pub(crate) fn prepare_settle(
+    topic: &Topic,
+    topic_key: Pubkey,
+    remaining: &[AccountInfo],
+    vault_balance: u64,
+    rent_exempt_min: u64,
+) -> Result<SettleRun> {
+    let params = SettleParams::from_topic(topic);
+    let (remaining, _) = split_leaderboards(remaining);
+
+    // Parse remaining accounts as commitment + participant pairs
+    require!(
+        remaining.len().is_multiple_of(2),
+        WorthHubError::NoRevealedCommitments
+    );
+    require!(!remaining.is_empty(), WorthHubError::NoRevealedCommitments);
+
+    let pair_count = remaining.len() / 2;
+    // Every commitment of the topic, each once: anything left out would be
+    // swept to the topic authority as protocol fee
+    require!(
+        pair_count == topic.commitment_count as usize,
+        WorthHubError::MissingCommitments
+    );
+
+    // Deserialize all commitments. `pair_indices[k]` is the index of the
+    // pair in remaining_accounts that produced `entries[k]`. Abstainers
+    // are kept apart as (pair index, stake): they only get their stake back.
+    let mut entries: Vec<SettleEntry> = Vec::with_capacity(pair_count);
+    let mut pair_indices: Vec<usize> = Vec::with_capacity(pair_count);
+    let mut abstainers: Vec<(usize, u64)> = Vec::new();
+    let mut participants: Vec<Pubkey> = vec![Pubkey::default(); pair_count];
+
+    for i in 0..pair_count {
+        let commitment_info = &remaining[i * 2];
+        // Only this program's accounts can be commitments, and settle later
+        // rewrites them with the settled flag set
+        require!(
+            *commitment_info.owner == crate::ID,
+            WorthHubError::InvalidCommitmentAccount
+        );
+        let data = commitment_info.try_borrow_data()?;
+        require!(
+            data.len() >= Commitment::MAX_SIZE,
+            WorthHubError::CommitmentAccountTooSmall
+        );
+
+        let commitment: Commitment = Commitment::try_deserialize(&mut &data[..])
+            .map_err(|_| WorthHubError::NoRevealedCommitments)?;
+        require!(
+            commitment.topic == topic_key,
+            WorthHubError::InvalidCommitmentAccount
+        );
+        commitment.verify_address(commitment_info.key)?;
+        // The payout account must be the commitment's own participant, or
+        // any settler could redirect every payout to themselves
+        require_keys_eq!(
+            remaining[i * 2 + 1].key(),
+            commitment.participant,
+            WorthHubError::InvalidCommitmentAccount
+        );
+        require!(
+            (0..i).all(|j| remaining[j * 2].key != commitment_info.key),
+            WorthHubError::MissingCommitments
+        );
+
+        // Already refunded via refund_participant: stake is gone from the
+        // vault, so it takes no part in consensus, scoring, or payouts
+        if Commitment::is_settled(&data)? {
+            continue;
+        }
+        participants[i] = commitment.participant;
+        if commitment.abstained {
+            abstainers.push((i, commitment.stake_amount));
+            continue;
+        }
+
+        entries.push(SettleEntry {
+            stake: commitment.stake_amount,
+            weight: commitment.weight,
+            prediction: commitment.prediction_value,
+            submit_order: commitment.submit_order,
+            revealed: commitment.revealed,
+            interval: (commitment.interval_low, commitment.interval_high),
+            extra_predictions: commitment.extra_predictions,
+        });
+        pair_indices.push(i);
+    }
+
+    // The settle fund is paid out separately by `add_settle_fund`
+    let vault_balance = vault_balance.saturating_sub(topic.settle_fund);
+
+    // Pay out of the escrow buckets, never more than the vault holds
+    let max_distributable = vault_balance
+        .min(topic.escrowed_total()?)
+        .saturating_sub(rent_exempt_min);
+
+    // The vault should hold every stake; if it does not, the reward math
+    // cannot be honoured fairly, so everyone gets a pro-rata refund instead
+    let entry_count = entries.len() + abstainers.len();
+    let mut stakes: Vec<u64> = entries.iter().map(|e| e.stake).collect();
+    stakes.extend(abstainers.iter().map(|&(_, stake)| stake));
+    pair_indices.extend(abstainers.iter().map(|&(pair, _)| pair));
+
+    let mut total_stake: u64 = 0;
+    for &stake in &stakes {
+        total_stake = total_stake
+            .checked_add(stake)
+            .ok_or(WorthHubError::PayoutOverflow)?;
+    }
+    // With `guarantee_stake_return`, revealed participants' stakes are paid
+    // before anything else is scaled down; abstainers are not covered
+    let mut floors: Vec<u64> = vec![0; stakes.len()];
+    if topic.options.guarantee_stake_return {
+        for (floor, e) in floors.iter_mut().zip(&entries) {
+            if e.revealed {
+                *floor = e.stake;
+            }
+        }
+    }
+
+    if vault_balance < total_stake {
+        let refunds = if topic.options.guarantee_stake_return {
+            scale_payouts_above_floors(&stakes, &floors, max_distributable)?
+        } else {
+            pro_rata_refunds(&stakes, max_distributable)
+        };
+        // Refunds are a scaled-down payout, so their rounding dust stays
+        // with participants
+        let allocation = allocate_vault(
+            &refunds,
+            &floors,
+            0,
+            vault_balance,
+            rent_exempt_min,
+            max_distributable,
+            true,
+        )?;
+        let mut transfers: Vec<(usize, u64)> = pair_indices
+            .into_iter()
+            .zip(allocation.payouts.iter().copied())
+            .collect();
+        let keeper_reward = add_settle_fund(topic, &entries, &mut transfers)?;
+        let outcomes = settlement_outcomes(&entries, &[], &participants, &transfers);
+        return Ok(SettleRun {
+            plan: SettlePlan {
+                consensus: 0,
+                loser_pool: 0,
+                payouts: allocation.payouts[..entries.len()].to_vec(),
+                scores: vec![0; entries.len()],
+                keeper_reward: 0,
+                prediction_variance: 0,
+            },
+            entry_count,
+            transfers,
+            keeper_reward,
+            protocol_fee: allocation.protocol_fee,
+            underfunded_stake: Some(total_stake),
+            outcomes,
+        });
+    }
+
+    let plan = compute_settle_plan(&entries, &params)?;
+
+    // Abstainers get their stake back alongside the participant payouts
+    let mut payouts = plan.payouts.clone();
+    payouts.extend(abstainers.iter().map(|&(_, stake)| stake));
+    let mut allocation = allocate_vault(
+        &payouts,
+        &floors,
+        plan.keeper_reward,
+        vault_balance,
+        rent_exempt_min,
+        max_distributable,
+        topic.options.leftover_to_participants,
+    )?;
+    pay_reveal_rebates(&mut allocation, &entries, topic.options.reveal_rebate);
+    let mut transfers: Vec<(usize, u64)> =
+        pair_indices.into_iter().zip(allocation.payouts).collect();
+    let fund_keeper_reward = add_settle_fund(topic, &entries, &mut transfers)?;
+    let outcomes = settlement_outcomes(&entries, &plan.scores, &participants, &transfers);
+
+    Ok(SettleRun {
+        plan,
+        entry_count,
+        transfers,
+        keeper_reward: allocation
+            .keeper_reward
+            .checked_add(fund_keeper_reward)
+            .ok_or(WorthHubError::PayoutOverflow)?,
+        protocol_fee: allocation.protocol_fee,
+        underfunded_stake: None,
+        outcomes,
+    })
+}
+
+/// One `SettlementOutcome` per transfer. Transfers list the entries first,
+/// in order, then the abstainers.
+fn settlement_outcomes(
+    entries: &[SettleEntry],
+    scores: &[u128],
+    participants: &[Pubkey],
+    transfers: &[(usize, u64)],
+) -> Vec<SettlementOutcome> {
+    transfers
+        .iter()
+        .enumerate()
+        .map(|(k, &(pair, payout))| {
+            let entry = entries.get(k);
+            let revealed = entry.is_some_and(|e| e.revealed);
+            SettlementOutcome {
+                participant: participants[pair],
+                prediction: if revealed {
+                    entry.map_or(0, |e| e.prediction)
+                } else {
+                    0
+                },
+                score: scores.get(k).copied().unwrap_or(0),
+                payout,
+                revealed,
+                abstained: entry.is_none(),
+            }
+        })
+        .collect()
+}
+
+/// Distribute the vault per `compute_settle_plan`. Returns the total lamports
+/// paid to participants (excluding the protocol fee).
+pub fn handle_settle<'info>(ctx: Context<'_, '_, 'info, 'info, SettleTopic<'info>>) -> Result<u64> {
+    let authority = ctx.accounts.authority.to_account_info();
+    let topic_authority = ctx.accounts.topic_authority.to_account_info();
+    let vault = ctx.accounts.vault.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let paid = settle_topic(
+        &mut ctx.accounts.topic,
+        &authority,
+        &topic_authority,
+        &vault,
+        &system_program,
+        &mut ctx.accounts.settlement_record,
+        ctx.bumps.settlement_record,
+        ctx.remaining_accounts,
+    )?;
+    record_truth(
+        &mut ctx.accounts.truth_history,
+        &ctx.accounts.topic,
+        ctx.bumps.truth_history,
+    )?;
+    Ok(paid)
+}
+
+/// Append a just-settled topic's truth to its symbol's history,
+/// initializing the history on the symbol's first settle
+pub(crate) fn record_truth(history: &mut TruthHistory, topic: &Topic, bump: u8) -> Result<()> {
+    if history.total_recorded == 0 {
+        history.symbol = topic.symbol.clone();
+        history.bump = bump;
+    }
+    history.push(TruthRecord {
+        topic_id: topic.topic_id,
+        truth_value: topic.truth_value,
+        settled_at: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+/// Grow `record` to hold `outcomes`, the settle signer topping up its rent,
+/// and fill it in
+fn write_settlement_record<'info>(
+    record: &mut Account<'info, SettlementRecord>,
+    bump: u8,
+    topic: &Account<'info, Topic>,
+    consensus: i128,
+    outcomes: Vec<SettlementOutcome>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let record_info = record.to_account_info();
+    let space = SettlementRecord::space(outcomes.len());
+    if record_info.data_len() < space {
+        let shortfall = Rent::get()?
+            .minimum_balance(space)
+            .saturating_sub(record_info.lamports());
+        if shortfall > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    system_program::Transfer {
+                        from: payer.clone(),
+                        to: record_info.clone(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+        record_info.resize(space)?;
+    }
+
+    record.topic = topic.key();
+    record.topic_id = topic.topic_id;
+    record.truth_value = topic.truth_value;
+    record.consensus = consensus;
+    record.settled_at = Clock::get()?.unix_timestamp;
+    record.outcomes = outcomes;
+    record.bump = bump;
+    Ok(())
+}
+
+/// Credit each leaderboard entry with its participant's settled
+/// commitments; `stakes` runs parallel to `outcomes`
+fn credit_leaderboards(
+    leaderboards: &[AccountInfo],
+    topic_id: u64,
+    outcomes: &[SettlementOutcome],
+    stakes: &[u64],
+) -> Result<()> {
+    let mut seen: Vec<Pubkey> = Vec::with_capacity(leaderboards.len());
+    for info in leaderboards {
+        require!(
+            info.is_writable && !seen.contains(info.key),
+            WorthHubError::InvalidLeaderboardAccount
+        );
+        seen.push(info.key());
+
+        // split_leaderboards only returns this program's accounts
+        let mut data = info.try_borrow_mut_data()?;
+        let mut leaderboard = Leaderboard::try_deserialize(&mut &data[..])?;
+        let mut credited = false;
+        for (outcome, &stake) in outcomes.iter().zip(stakes) {
+            if outcome.participant == leaderboard.participant {
+                leaderboard.credit(topic_id, outcome, stake);
+                credited = true;
+            }
+        }
+        require!(credited, WorthHubError::InvalidLeaderboardAccount);
+        leaderboard.try_serialize(&mut &mut data[..])?;
+    }
+    Ok(())
+}
+
+/// Settle a finalized topic: pay every (commitment, participant) pair in
+/// `remaining`, the keeper reward to `authority` and the protocol fee to
+/// `topic_authority`, credit any trailing leaderboard entries, write
+/// `record`, then mark the topic Settled. Callers
+/// have checked the accounts as `SettleTopic` does.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn settle_topic<'info>(
+    topic: &mut Account<'info, Topic>,
+    authority: &AccountInfo<'info>,
+    topic_authority: &AccountInfo<'info>,
+    vault: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    record: &mut Account<'info, SettlementRecord>,
+    record_bump: u8,
+    remaining: &[AccountInfo<'info>],
+) -> Result<u64> {
+    let truth = topic.truth_value;
+    let topic_key = topic.key();
+
+    // We need to keep the vault rent-exempt. A 0-data account needs ~890_880 lamports.
+    // Reserve this from the pool.
+    let rent = Rent::get()?;
+    let rent_exempt_min = rent.minimum_balance(0);
+
+    let vault_info = vault.clone();
+    let vault_balance = vault_info.lamports();
+    let SettleRun {
+        plan,
+        entry_count,
+        transfers,
+        keeper_reward,
+        protocol_fee,
+        underfunded_stake,
+        outcomes,
+    } = prepare_settle(topic, topic_key, remaining, vault_balance, rent_exempt_min)?;
+
+    if let Some(total_stake) = underfunded_stake {
+        emit!(VaultUnderfunded {
+            topic_id: topic.topic_id,
+            vault_balance,
+            total_stake,
+        });
+        msg!(
+            "Vault underfunded: balance={}, stakes={}; refunding pro-rata",
+            vault_balance,
+            total_stake
+        );
+    }
+
+    // ── Distribute rewards ──────────────────────────────────────────────
+
+    // Distribute rewards via CPI invoke_signed
+    let authority_info = authority.clone();
+    let topic_authority_info = topic_authority.clone();
+    let system_prog = system_program.clone();
+    let topic_key_bytes = topic_key.as_ref();
+    let vault_bump = topic.vault_bump;
+    let bump_slice = &[vault_bump];
+    let vault_signer_seeds: &[&[u8]] = &[b"vault", topic_key_bytes, bump_slice];
+
+    let mut total_settled_payout: u64 = 0;
+    let mut stakes: Vec<u64> = Vec::with_capacity(transfers.len());
+
+    for &(pair, payout) in &transfers {
+        let commitment_info = &remaining[pair * 2];
+        let participant_info = &remaining[pair * 2 + 1];
+
+        if payout > 0 {
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    system_prog.clone(),
+                    system_program::Transfer {
+                        from: vault_info.clone(),
+                        to: participant_info.clone(),
+                    },
+                    &[vault_signer_seeds],
+                ),
+                payout,
+            )?;
+            total_settled_payout = total_settled_payout
+                .checked_add(payout)
+                .ok_or(WorthHubError::PayoutOverflow)?;
+        }
+
+        // Mark commitment as settled
+        let commitment = Commitment::mark_settled(&mut commitment_info.try_borrow_mut_data()?)?;
+        stakes.push(commitment.stake_amount);
+    }
+
+    // Keeper reward to the settle signer
+    if keeper_reward > 0 {
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                system_prog.clone(),
+                system_program::Transfer {
+                    from: vault_info.clone(),
+                    to: authority_info.clone(),
+                },
+                &[vault_signer_seeds],
+            ),
+            keeper_reward,
+        )?;
+    }
+
+    // Transfer remaining vault balance (minus rent) to the topic authority as protocol fee
+    if protocol_fee > 0 {
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                system_prog.clone(),
+                system_program::Transfer {
+                    from: vault_info.clone(),
+                    to: topic_authority_info.clone(),
+                },
+                &[vault_signer_seeds],
+            ),
+            protocol_fee,
+        )?;
+    }
+
+    let (_, leaderboards) = split_leaderboards(remaining);
+    credit_leaderboards(leaderboards, topic.topic_id, &outcomes, &stakes)?;
+    write_settlement_record(
+        record,
+        record_bump,
+        topic,
+        plan.consensus,
+        outcomes,
+        authority,
+        system_program,
+    )?;
+
+    // Mark topic as settled
+    topic.status = TopicStatus::Settled;
+    topic.total_settled_payout = total_settled_payout;
+    // Everything escrowed has been paid out or swept
+    topic.escrowed_stake = 0;
+    topic.escrowed_fees = 0;
+    topic.escrowed_prize = 0;
+    topic.settle_fund = 0;
+    topic.prediction_variance = plan.prediction_variance;
+
+    emit!(TopicSettled {
+        topic_id: topic.topic_id,
+        truth_value: truth,
+        consensus: plan.consensus,
+        participant_count: entry_count as u32,
+        loser_pool: plan.loser_pool as u64,
+        total_settled_payout,
+        keeper_reward,
+        protocol_fee,
+        prediction_variance: plan.prediction_variance,
+    });
+
+    msg!(
+        "Topic settled: id={}, truth={}, consensus={}, participants={}, loser_pool={}, paid={}",
+        topic.topic_id,
+        truth,
+        plan.consensus,
+        entry_count,
+        plan.loser_pool,
+        total_settled_payout
+    );
+
+    Ok(total_settled_payout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rent-exempt minimum for a 0-data account
+    const RENT_EXEMPT_MIN: u64 = 890_880;
+
+    /// Deterministic xorshift64 PRNG so failures are reproducible
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn range(&mut self, lo: u64, hi: u64) -> u64 {
+            lo + self.next() % (hi - lo + 1)
+        }
+
+        fn signed(&mut self, magnitude: i64) -> i64 {
+            self.range(0, 2 * magnitude as u64) as i64 - magnitude
+        }
+    }
+
+    fn random_entries(rng: &mut Rng, max_stake: u64, max_abs_prediction: i64) -> Vec<SettleEntry> {
+        let count = rng.range(1, 40) as usize;
+        (0..count)
+            .map(|i| {
+                let stake = rng.range(1, max_stake);
+                SettleEntry {
+                    stake,
+                    weight: if rng.range(0, 3) == 0 {
+                        rng.range(1, max_stake)
+                    } else {
+                        stake
+                    },
+                    prediction: rng.signed(max_abs_prediction),
+                    submit_order: i as u32,
+                    revealed: rng.range(0, 3) != 0,
+                    interval: (0, 0),
+                    extra_predictions: [0; 3],
+                }
+            })
+            .collect()
+    }
+
+    /// Mirror of the transfer loop in handle_settle. Returns the lamports sent
+    /// to participants and keeper, and the authority fee, asserting conservation.
+    fn simulate_transfers(entries: &[SettleEntry], truth: i64, vault_balance: u64) -> (u64, u64) {
+        simulate_transfers_with(entries, truth, vault_balance, &TopicOptions::default())
+    }
+
+    fn simulate_transfers_with(
+        entries: &[SettleEntry],
+        truth: i64,
+        vault_balance: u64,
+        options: &TopicOptions,
+    ) -> (u64, u64) {
+        let params = SettleParams {
+            truth,
+            truth_band: None,
+            extra_truths: &[],
+            consensus: None,
+            seed: [7u8; 32],
+            options,
+        };
+        let plan = compute_settle_plan(entries, &params).expect("settle math failed");
+        let max_distributable = vault_balance.saturating_sub(RENT_EXEMPT_MIN);
+        let allocation = allocate_vault(
+            &plan.payouts,
+            &vec![0; plan.payouts.len()],
+            plan.keeper_reward,
+            vault_balance,
+            RENT_EXEMPT_MIN,
+            max_distributable,
+            options.leftover_to_participants,
+        )
+        .unwrap();
+
+        // Participants first, then the keeper
+        let mut vault = vault_balance;
+        let mut paid: u64 = 0;
+        for payout in allocation.payouts.iter().chain([&allocation.keeper_reward]) {
+            vault = vault
+                .checked_sub(*payout)
+                .expect("transfer overdrew the vault");
+            paid += payout;
+        }
+        let fee = allocation.protocol_fee;
+        vault = vault.checked_sub(fee).expect("fee overdrew the vault");
+
+        assert_eq!(paid + fee + vault, vault_balance, "lamports not conserved");
+        assert!(
+            paid <= max_distributable,
+            "paid {} > distributable {}",
+            paid,
+            max_distributable
+        );
+        assert_eq!(
+            vault,
+            vault_balance.min(RENT_EXEMPT_MIN),
+            "vault left below rent-exempt minimum"
+        );
+        (paid, fee)
+    }
+
+    #[test]
+    fn ln_table_is_strictly_increasing() {
+        for n in 1..LN_TABLE.len() {
+            assert!(
+                LN_TABLE[n] > LN_TABLE[n - 1],
+                "LN_TABLE not increasing at {}",
+                n
+            );
+        }
+    }
+
+    #[test]
+    fn ln_approx_is_continuous_at_table_boundary() {
+        let step = ln_approx(64) - ln_approx(63);
+        // True ln(64 + e) - ln(63 + e) ≈ 0.0151
+        assert!(step > 0 && step < 20_000, "jump at N=64 is {}", step);
+    }
+
+    #[test]
+    fn time_decay_is_monotonic_across_full_range() {
+        let decay = |n: u32| PRECISION * PRECISION / ln_approx(n);
+        let mut prev = decay(0);
+        for n in 1..10_000u32 {
+            let cur = decay(n);
+            assert!(cur < prev, "decay not decreasing at N={}", n);
+            prev = cur;
+        }
+        for n in [u32::MAX / 2, u32::MAX - 1, u32::MAX] {
+            assert!(decay(n) <= prev);
+            prev = decay(n);
+        }
+    }
+
+    #[test]
+    fn ln_approx_tracks_true_ln() {
+        let reference = |n: u32| ((n as f64) + std::f64::consts::E).ln() * PRECISION as f64;
+        for n in (64u32..5_000).chain([100_000, 1 << 20, u32::MAX / 3, u32::MAX]) {
+            let err = (ln_approx(n) as f64 - reference(n)).abs();
+            assert!(err <= 1.0, "N={} error={}", n, err);
+        }
+        // Table entries agree with the computed path
+        for n in 0..LN_TABLE.len() as u32 {
+            let err = (ln_approx(n) as f64 - reference(n)).abs();
+            assert!(err <= 1.0, "N={} error={}", n, err);
+        }
+    }
+
+    #[test]
+    fn settle_conserves_lamports_randomized() {
+        let mut rng = Rng(0x5eed_1234_abcd_ef01);
+        for _ in 0..2_000 {
+            let entries = random_entries(&mut rng, 1_000_000_000_000, 1_000_000_000_000_000);
+            let truth = rng.signed(1_000_000_000_000_000);
+            let total_stake: u64 = entries.iter().map(|e| e.stake).sum();
+
+            // Fully funded, underfunded, and overfunded vaults
+            simulate_transfers(&entries, truth, total_stake);
+            simulate_transfers(&entries, truth, total_stake / 2);
+            simulate_transfers(&entries, truth, total_stake + RENT_EXEMPT_MIN);
+        }
+    }
+
+    #[test]
+    fn settle_handles_huge_stakes_without_overflow() {
+        // Loser pools this large overflowed loser_pool × score in u128
+        let mut rng = Rng(0xdead_beef_0bad_f00d);
+        for _ in 0..500 {
+            let entries = random_entries(&mut rng, u64::MAX / 64, 1_000_000_000);
+            let truth = rng.signed(1_000_000_000);
+            let total_stake: u64 = entries.iter().map(|e| e.stake).sum();
+            simulate_transfers(&entries, truth, total_stake);
+        }
+    }
+
+    #[test]
+    fn settle_payouts_never_exceed_stakes_in_the_pool() {
+        let mut rng = Rng(42);
+        for _ in 0..1_000 {
+            let entries = random_entries(&mut rng, 10_000_000_000, 1_000_000_000);
+            let options = TopicOptions::default();
+            let params = SettleParams {
+                truth: rng.signed(1_000_000_000),
+                truth_band: None,
+                extra_truths: &[],
+                consensus: None,
+                seed: [0u8; 32],
+                options: &options,
+            };
+            let plan = compute_settle_plan(&entries, &params).unwrap();
+            let total_stake: u128 = entries.iter().map(|e| e.stake as u128).sum();
+            let total_payout: u128 = plan.payouts.iter().map(|&p| p as u128).sum();
+            assert!(total_payout <= total_stake);
+            // A unanimous reveal (e.g. a lone revealer) refunds the pool to everyone
+            let refunded =
+                plan.scores.iter().all(|&s| s == 0) && is_unanimous(&entries, plan.consensus);
+            for (e, &payout) in entries.iter().zip(&plan.payouts) {
+                if e.revealed {
+                    assert!(payout >= e.stake);
+                } else if refunded {
+                    assert!(payout <= e.stake);
+                } else {
+                    assert_eq!(payout, 0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn scale_payouts_respects_cap() {
+        let scaled = scale_payouts(&[300, 300, 400], 500).unwrap();
+        assert!(scaled.iter().sum::<u64>() <= 500);
+        assert_eq!(scale_payouts(&[1, 2, 3], 10).unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn scaled_payouts_sum_to_max_distributable() {
+        // 66.67 / 133.33 / 200: the lost lamport goes to the largest remainder
+        assert_eq!(
+            scale_payouts(&[100, 200, 300], 400).unwrap(),
+            vec![67, 133, 200]
+        );
+        // Equal remainders: earliest entries first
+        assert_eq!(scale_payouts(&[1, 1, 1], 2).unwrap(), vec![1, 1, 0]);
+
+        let mut rng = Rng(0x1a_2e57);
+        for _ in 0..1_000 {
+            let payouts: Vec<u64> = (0..rng.range(1, 20))
+                .map(|_| rng.range(0, 1_000_000_000))
+                .collect();
+            let total: u64 = payouts.iter().sum();
+            let max_distributable = rng.range(0, total);
+            let scaled = scale_payouts(&payouts, max_distributable).unwrap();
+            assert_eq!(scaled.iter().sum::<u64>(), max_distributable);
+            for (&s, &p) in scaled.iter().zip(&payouts) {
+                let exact = p as u128 * max_distributable as u128 / total.max(1) as u128;
+                assert!(s as u128 == exact || s as u128 == exact + 1);
+            }
+        }
+    }
+
+    #[test]
+    fn mul_div_floor_falls_back_on_overflow() {
+        assert_eq!(mul_div_floor(10, 3, 4), 7);
+        let big = u128::MAX / 2;
+        assert_eq!(mul_div_floor(big, big, big), big);
+        assert!(mul_div_floor(u128::MAX, 3, 7) <= u128::MAX / 7 * 3 + 3);
+    }
+
+    fn tied_entries() -> Vec<SettleEntry> {
+        (0..4)
+            .map(|i| SettleEntry {
+                stake: 100,
+                weight: 100,
+                prediction: 0,
+                submit_order: i,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tiebreak_picks_earliest_latest_and_seeded() {
+        let entries = tied_entries();
+        let scores = [5u128, 9, 9, 9];
+
+        assert_eq!(
+            pick_winner(&entries, &scores, TieBreak::EarliestOrder, &[0; 32]),
+            Some(1)
+        );
+        assert_eq!(
+            pick_winner(&entries, &scores, TieBreak::LatestOrder, &[0; 32]),
+            Some(3)
+        );
+
+        // Seeded pick is deterministic and indexes the tied set by submit order
+        let mut seed = [0u8; 32];
+        seed[0] = 4; // 4 % 3 == 1 → second tied entry
+        assert_eq!(
+            pick_winner(&entries, &scores, TieBreak::Random, &seed),
+            Some(2)
+        );
+        assert_eq!(
+            pick_winner(&entries, &scores, TieBreak::Random, &seed),
+            Some(2)
+        );
+        seed[0] = 3;
+        assert_eq!(
+            pick_winner(&entries, &scores, TieBreak::Random, &seed),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn tiebreak_ignores_unrevealed_and_zero_scores() {
+        let mut entries = tied_entries();
+        entries[1].revealed = false;
+        assert_eq!(
+            pick_winner(&entries, &[0, 9, 9, 0], TieBreak::EarliestOrder, &[0; 32]),
+            Some(2)
+        );
+        assert_eq!(
+            pick_winner(&entries, &[0, 0, 0, 0], TieBreak::EarliestOrder, &[0; 32]),
+            None
+        );
+    }
+
+    #[test]
+    fn winner_take_all_awards_pool_to_tiebreak_winner() {
+        // Two identical bold predictions, one consensus-side, one forfeiter
+        let entries = vec![
+            SettleEntry {
+                stake: 1_000,
+                weight: 1_000,
+                prediction: 90_000_000,
+                submit_order: 0,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 1_000,
+                weight: 1_000,
+                prediction: 110_000_000,
+                submit_order: 1,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 1_000,
+                weight: 1_000,
+                prediction: 110_000_000,
+                submit_order: 2,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 5_000,
+                weight: 1,
+                prediction: 0,
+                submit_order: 3,
+                revealed: false,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+        ];
+        let options = TopicOptions {
+            reward_mode: RewardMode::WinnerTakeAll,
+            tiebreak: TieBreak::LatestOrder,
+            ..TopicOptions::default()
+        };
+        let params = SettleParams {
+            truth: 110_000_000,
+            truth_band: None,
+            extra_truths: &[],
+            consensus: None,
+            seed: [0; 32],
+            options: &options,
+        };
+        let plan = compute_settle_plan(&entries, &params).unwrap();
+        // Time decay favors submit_order 1, so LatestOrder only matters on an exact tie
+        assert_eq!(plan.payouts, vec![1_000, 6_000, 1_000, 0]);
+    }
+
+    #[test]
+    fn proportional_dust_goes_to_top_scorer() {
+        let entries = vec![
+            SettleEntry {
+                stake: 1_000,
+                weight: 1_000,
+                prediction: 90_000_000,
+                submit_order: 0,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 1_000,
+                weight: 1_000,
+                prediction: 105_000_000,
+                submit_order: 1,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 1_000,
+                weight: 1_000,
+                prediction: 108_000_000,
+                submit_order: 2,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 1_001,
+                weight: 1,
+                prediction: 0,
+                submit_order: 3,
+                revealed: false,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+        ];
+        let options = TopicOptions::default();
+        let params = SettleParams {
+            truth: 110_000_000,
+            truth_band: None,
+            extra_truths: &[],
+            consensus: None,
+            seed: [0; 32],
+            options: &options,
+        };
+        let plan = compute_settle_plan(&entries, &params).unwrap();
+        // Whole loser pool distributed: no rounding dust left for the authority
+        assert_eq!(plan.payouts.iter().sum::<u64>(), 4_001);
+    }
+
+    #[test]
+    fn underdog_boost_favors_small_accurate_stakes() {
+        // A whale and a minnow make the same bold, accurate call
+        let entries = vec![
+            SettleEntry {
+                stake: 90_000,
+                weight: 90_000,
+                prediction: 110_000_000,
+                submit_order: 0,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 1_000,
+                weight: 1_000,
+                prediction: 110_000_000,
+                submit_order: 1,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 100_000,
+                weight: 100_000,
+                prediction: 95_000_000,
+                submit_order: 2,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 50_000,
+                weight: 1,
+                prediction: 0,
+                submit_order: 3,
+                revealed: false,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+        ];
+        let default_opts = TopicOptions::default();
+        let underdog_opts = TopicOptions {
+            reward_mode: RewardMode::UnderdogBoost,
+            ..TopicOptions::default()
+        };
+        let plan_for = |options: &TopicOptions| {
+            compute_settle_plan(
+                &entries,
+                &SettleParams {
+                    truth: 110_000_000,
+                    truth_band: None,
+                    extra_truths: &[],
+                    consensus: None,
+                    seed: [0; 32],
+                    options,
+                },
+            )
+            .unwrap()
+        };
+        let default_plan = plan_for(&default_opts);
+        let underdog_plan = plan_for(&underdog_opts);
+
+        let bonus = |plan: &SettlePlan, i: usize| plan.payouts[i] - entries[i].stake;
+        assert!(bonus(&underdog_plan, 1) > bonus(&default_plan, 1));
+        assert!(bonus(&underdog_plan, 0) < bonus(&default_plan, 0));
+        // Boosting redistributes the same pool; it never mints lamports
+        assert_eq!(default_plan.payouts.iter().sum::<u64>(), 241_000);
+        assert_eq!(underdog_plan.payouts.iter().sum::<u64>(), 241_000);
+    }
+
+    #[test]
+    fn underdog_boost_is_capped() {
+        assert_eq!(underdog_boost(1, 1_000_000), MAX_UNDERDOG_BOOST * PRECISION);
+        assert_eq!(underdog_boost(500, 1_000), 2 * PRECISION);
+        assert_eq!(underdog_boost(1_000, 1_000), PRECISION);
+        assert_eq!(underdog_boost(0, u128::MAX), MAX_UNDERDOG_BOOST * PRECISION);
+    }
+
+    #[test]
+    fn interval_scoring_prefers_tight_correct_intervals() {
+        let interval_entry = |order: u32, low: i64, high: i64| SettleEntry {
+            stake: 1_000,
+            weight: 1_000,
+            prediction: Commitment::interval_midpoint(low, high),
+            submit_order: order,
+            revealed: true,
+            interval: (low, high),
+            extra_predictions: [0; 3],
+        };
+        let entries = vec![
+            // Wide, but submitted first so time decay favors it
+            interval_entry(0, 50_000_000, 150_000_000),
+            interval_entry(1, 99_000_000, 101_000_000),
+            // Tightest, but misses the truth
+            interval_entry(2, 101_000_000, 101_000_001),
+            SettleEntry {
+                stake: 3_000,
+                weight: 1,
+                prediction: 0,
+                submit_order: 3,
+                revealed: false,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+        ];
+        let options = TopicOptions {
+            reward_mode: RewardMode::IntervalScoring,
+            ..TopicOptions::default()
+        };
+        let params = SettleParams {
+            truth: 100_000_000,
+            truth_band: None,
+            extra_truths: &[],
+            consensus: None,
+            seed: [0; 32],
+            options: &options,
+        };
+        let plan = compute_settle_plan(&entries, &params).unwrap();
+
+        assert!(
+            plan.payouts[1] > plan.payouts[0],
+            "tight {} <= wide {}",
+            plan.payouts[1],
+            plan.payouts[0]
+        );
+        assert!(plan.payouts[0] > 1_000);
+        assert_eq!(plan.payouts[2], 1_000);
+        assert_eq!(plan.payouts.iter().sum::<u64>(), 6_000);
+    }
+
+    #[test]
+    fn reveal_rebate_pays_revealers_when_nobody_scores() {
+        // Everyone predicts the truth exactly, so consensus == truth and all scores are zero
+        let entries = vec![
+            SettleEntry {
+                stake: 1_000,
+                weight: 1_000,
+                prediction: 100,
+                submit_order: 0,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 2_000,
+                weight: 2_000,
+                prediction: 100,
+                submit_order: 1,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 4_001,
+                weight: 1,
+                prediction: 0,
+                submit_order: 2,
+                revealed: false,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+        ];
+        let options = TopicOptions {
+            reveal_rebate_bps: 5_000,
+            unanimous_policy: UnanimousPolicy::Sweep,
+            ..TopicOptions::default()
+        };
+        let params = SettleParams {
+            truth: 100,
+            truth_band: None,
+            extra_truths: &[],
+            consensus: None,
+            seed: [0; 32],
+            options: &options,
+        };
+        let plan = compute_settle_plan(&entries, &params).unwrap();
+        // 2_000 of the 4_001 pool split two ways; the rest goes to the authority
+        assert_eq!(plan.payouts, vec![2_000, 3_000, 0]);
+
+        let no_rebate = TopicOptions {
+            unanimous_policy: UnanimousPolicy::Sweep,
+            ..TopicOptions::default()
+        };
+        let params = SettleParams {
+            truth: 100,
+            truth_band: None,
+            extra_truths: &[],
+            consensus: None,
+            seed: [0; 32],
+            options: &no_rebate,
+        };
+        assert_eq!(
+            compute_settle_plan(&entries, &params).unwrap().payouts,
+            vec![1_000, 2_000, 0]
+        );
+    }
+
+    #[test]
+    fn reveal_rebate_never_leaves_revealers_worse_off() {
+        let mut rng = Rng(0x0bad_cafe);
+        for _ in 0..1_000 {
+            let entries = random_entries(&mut rng, 10_000_000_000, 1_000_000_000);
+            let options = TopicOptions {
+                reveal_rebate_bps: rng.range(0, TopicOptions::MAX_BPS as u64) as u16,
+                ..TopicOptions::default()
+            };
+            let params = SettleParams {
+                truth: rng.signed(1_000_000_000),
+                truth_band: None,
+                extra_truths: &[],
+                consensus: None,
+                seed: [0u8; 32],
+                options: &options,
+            };
+            let plan = compute_settle_plan(&entries, &params).unwrap();
+
+            let revealers = entries.iter().filter(|e| e.revealed).count() as u128;
+            // A refunded unanimous reveal releases nothing to rebate
+            let refunded =
+                plan.scores.iter().all(|&s| s == 0) && is_unanimous(&entries, plan.consensus);
+            let rebate = (if refunded { 0 } else { plan.loser_pool }
+                * options.reveal_rebate_bps as u128
+                / TopicOptions::MAX_BPS as u128)
+                .checked_div(revealers)
+                .unwrap_or(0);
+            let total_stake: u128 = entries.iter().map(|e| e.stake as u128).sum();
+            let total_payout: u128 = plan.payouts.iter().map(|&p| p as u128).sum();
+            assert!(total_payout <= total_stake);
+            for (e, &payout) in entries.iter().zip(&plan.payouts) {
+                if e.revealed {
+                    assert!(payout as u128 >= e.stake as u128 + rebate);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn keeper_reward_is_carved_from_loser_pool() {
+        let entries = vec![
+            SettleEntry {
+                stake: 1_000,
+                weight: 1_000,
+                prediction: 100,
+                submit_order: 0,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 1_000,
+                weight: 1_000,
+                prediction: 120,
+                submit_order: 1,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 10_000,
+                weight: 1,
+                prediction: 0,
+                submit_order: 2,
+                revealed: false,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+        ];
+        let options = TopicOptions {
+            keeper_reward_bps: 250,
+            ..TopicOptions::default()
+        };
+        let params = SettleParams {
+            truth: 120,
+            truth_band: None,
+            extra_truths: &[],
+            consensus: None,
+            seed: [0; 32],
+            options: &options,
+        };
+        let plan = compute_settle_plan(&entries, &params).unwrap();
+        assert_eq!(plan.keeper_reward, 250);
+        // The rest of the loser pool still goes to participants
+        assert_eq!(plan.payouts.iter().sum::<u64>(), 12_000 - 250);
+    }
+
+    #[test]
+    fn keeper_reward_conserves_lamports_randomized() {
+        let mut rng = Rng(0x6b65_6570_6572);
+        for _ in 0..1_000 {
+            let entries = random_entries(&mut rng, 1_000_000_000_000, 1_000_000_000);
+            let truth = rng.signed(1_000_000_000);
+            let options = TopicOptions {
+                keeper_reward_bps: rng.range(0, TopicOptions::MAX_BPS as u64) as u16,
+                reveal_rebate_bps: rng.range(0, TopicOptions::MAX_BPS as u64) as u16,
+                ..TopicOptions::default()
+            };
+            let total_stake: u64 = entries.iter().map(|e| e.stake).sum();
+            simulate_transfers_with(&entries, truth, total_stake, &options);
+            simulate_transfers_with(&entries, truth, total_stake / 2, &options);
+        }
+    }
+
+    #[test]
+    fn pow_ratio_matches_float_pow() {
+        for ratio in [1u128, 1_000, 250_000, 500_000, 999_999, PRECISION] {
+            for bps in [0u16, 5_000, 10_000, 12_500, 20_000, 30_000] {
+                let expected = (ratio as f64 / PRECISION as f64).powf(bps as f64 / 10_000.0)
+                    * PRECISION as f64;
+                let got = pow_ratio(ratio, bps) as f64;
+                assert!(
+                    (got - expected).abs() <= 2.0 + expected * 1e-3,
+                    "{}^{} = {} vs {}",
+                    ratio,
+                    bps,
+                    got,
+                    expected
+                );
+            }
+        }
+        assert_eq!(pow_ratio(PRECISION, 10_000), PRECISION);
+    }
+
+    #[test]
+    fn score_weights_shift_payouts_between_bold_and_accurate() {
+        let entries = vec![
+            // Consensus anchor
+            SettleEntry {
+                stake: 1_000_000,
+                weight: 10_000,
+                prediction: 100_000_000,
+                submit_order: 0,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            // Bold, overshoots
+            SettleEntry {
+                stake: 1_000_000,
+                weight: 1_000,
+                prediction: 130_000_000,
+                submit_order: 1,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            // Timid, nearly exact
+            SettleEntry {
+                stake: 1_000_000,
+                weight: 1_000,
+                prediction: 109_000_000,
+                submit_order: 2,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 10_000_000,
+                weight: 1,
+                prediction: 0,
+                submit_order: 3,
+                revealed: false,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+        ];
+        let plan_for = |accuracy_weight_bps: u16, boldness_weight_bps: u16| {
+            let options = TopicOptions {
+                accuracy_weight_bps,
+                boldness_weight_bps,
+                ..TopicOptions::default()
+            };
+            let params = SettleParams {
+                truth: 110_000_000,
+                truth_band: None,
+                extra_truths: &[],
+                consensus: None,
+                seed: [0; 32],
+                options: &options,
+            };
+            compute_settle_plan(&entries, &params).unwrap()
+        };
+        let accuracy_heavy = plan_for(20_000, 5_000);
+        let boldness_heavy = plan_for(5_000, 20_000);
+
+        assert!(boldness_heavy.payouts[1] > accuracy_heavy.payouts[1]);
+        assert!(accuracy_heavy.payouts[2] > boldness_heavy.payouts[2]);
+        for plan in [&accuracy_heavy, &boldness_heavy] {
+            assert_eq!(plan.payouts.iter().sum::<u64>(), 13_000_000);
+        }
+    }
+
+    fn settle_error(entries: &[SettleEntry], truth: i64) -> Error {
+        let options = TopicOptions::default();
+        let params = SettleParams {
+            truth,
+            truth_band: None,
+            extra_truths: &[],
+            consensus: None,
+            seed: [0; 32],
+            options: &options,
+        };
+        compute_settle_plan(entries, &params).unwrap_err()
+    }
+
+    #[test]
+    fn consensus_overflow_is_reported_as_such() {
+        // Each prediction × weight is ~9.2e36; twenty of them exceed i128
+        let entries: Vec<SettleEntry> = (0..20)
+            .map(|i| SettleEntry {
+                stake: 1,
+                weight: Commitment::MAX_WEIGHT,
+                prediction: i64::MAX - 1,
+                submit_order: i,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            })
+            .collect();
+        assert_eq!(
+            settle_error(&entries, 0),
+            WorthHubError::ConsensusOverflow.into()
+        );
+    }
+
+    #[test]
+    fn payout_overflow_is_reported_as_such() {
+        // A revealed stake plus a bonus from an equally huge loser pool exceeds u64
+        let half = u64::MAX / 2 + 1;
+        let entries = vec![
+            SettleEntry {
+                stake: half,
+                weight: 1,
+                prediction: 100,
+                submit_order: 0,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 1,
+                weight: 1,
+                prediction: 200,
+                submit_order: 1,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: half,
+                weight: 1,
+                prediction: 0,
+                submit_order: 2,
+                revealed: false,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+        ];
+        assert_eq!(
+            settle_error(&entries, 100),
+            WorthHubError::PayoutOverflow.into()
+        );
+    }
+
+    #[test]
+    fn truth_band_gives_full_accuracy_inside_it() {
+        // Both bold calls land in the band; the point truth favors the closer one
+        let entries = vec![
+            SettleEntry {
+                stake: 1_000,
+                weight: 1_000,
+                prediction: 109_000_000,
+                submit_order: 0,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 1_000,
+                weight: 1_000,
+                prediction: 112_000_000,
+                submit_order: 1,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 10_000,
+                weight: 10_000,
+                prediction: 95_000_000,
+                submit_order: 2,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 5_000,
+                weight: 1,
+                prediction: 0,
+                submit_order: 3,
+                revealed: false,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+        ];
+        let options = TopicOptions::default();
+        let plan_for = |truth_band| {
+            compute_settle_plan(
+                &entries,
+                &SettleParams {
+                    truth: 110_000_000,
+                    truth_band,
+                    extra_truths: &[],
+                    consensus: None,
+                    seed: [0; 32],
+                    options: &options,
+                },
+            )
+            .unwrap()
+        };
+        let point = plan_for(None);
+        let banded = plan_for(Some((108_000_000, 112_000_000)));
+
+        // A degenerate band is the point truth
+        assert_eq!(
+            plan_for(Some((110_000_000, 110_000_000))).payouts,
+            point.payouts
+        );
+        // Inside the band accuracy is equal, so the bolder call gains at the closer one's expense
+        assert!(banded.payouts[1] > point.payouts[1]);
+        assert!(banded.payouts[0] < point.payouts[0]);
+        assert_eq!(
+            banded.payouts.iter().sum::<u64>(),
+            point.payouts.iter().sum::<u64>()
+        );
+    }
+
+    #[test]
+    fn capped_shares_redistributes_excess_to_next_scorers() {
+        // The leader is capped at half; the rest splits 15:5
+        assert_eq!(
+            capped_shares(10_000, &[80, 15, 5], 5_000, 0),
+            vec![5_000, 3_750, 1_250]
+        );
+        // Redistribution can push the runner-up over the cap too
+        assert_eq!(
+            capped_shares(10_000, &[60, 30, 10], 4_000, 0),
+            vec![4_000, 4_000, 2_000]
+        );
+        // Zero scores take nothing, even when the pool cannot be fully placed
+        assert_eq!(
+            capped_shares(10_000, &[1, 1, 0], 3_000, 0),
+            vec![3_000, 3_000, 0]
+        );
+        // A cap nobody reaches changes nothing
+        assert_eq!(
+            capped_shares(10_000, &[3, 1], 10_000, 0),
+            vec![7_500, 2_500]
+        );
+    }
+
+    #[test]
+    fn max_payout_caps_bonus_and_conserves_lamports_randomized() {
+        let mut rng = Rng(0x00ca_9ca9);
+        for _ in 0..1_000 {
+            let entries = random_entries(&mut rng, 10_000_000_000, 1_000_000_000);
+            let options = TopicOptions {
+                max_payout_bps: rng.range(1, TopicOptions::MAX_BPS as u64) as u16,
+                ..TopicOptions::default()
+            };
+            let truth = rng.signed(1_000_000_000);
+            let params = SettleParams {
+                truth,
+                truth_band: None,
+                extra_truths: &[],
+                consensus: None,
+                seed: [0u8; 32],
+                options: &options,
+            };
+            let plan = compute_settle_plan(&entries, &params).unwrap();
+
+            let cap =
+                plan.loser_pool * options.max_payout_bps as u128 / TopicOptions::MAX_BPS as u128;
+            // A refunded unanimous reveal returns the pool by stake, outside the cap
+            let refunded =
+                plan.scores.iter().all(|&s| s == 0) && is_unanimous(&entries, plan.consensus);
+            let total_stake: u128 = entries.iter().map(|e| e.stake as u128).sum();
+            let total_payout: u128 = plan.payouts.iter().map(|&p| p as u128).sum();
+            assert!(total_payout <= total_stake);
+            for (e, &payout) in entries.iter().zip(&plan.payouts) {
+                if e.revealed {
+                    assert!(payout >= e.stake && (refunded || (payout - e.stake) as u128 <= cap));
+                }
+            }
+            let total_stake = total_stake as u64;
+            simulate_transfers_with(&entries, truth, total_stake, &options);
+        }
+    }
+
+    #[test]
+    fn compute_consensus_matches_direct_weighted_mean() {
+        let mut rng = Rng(0x5eed_c0de);
+        for _ in 0..1_000 {
+            let entries = random_entries(&mut rng, 10_000_000_000, 1_000_000_000);
+            let revealed = entries.iter().filter(|e| e.revealed);
+            let num: i128 = revealed
+                .clone()
+                .map(|e| e.prediction as i128 * e.weight as i128)
+                .sum();
+            let den: i128 = revealed.clone().map(|e| e.weight as i128).sum();
+            let totals = compute_consensus(&entries).unwrap();
+            assert_eq!(totals.consensus, if den > 0 { num / den } else { 0 });
+            assert_eq!(
+                totals.total_revealed_stake,
+                revealed.map(|e| e.stake).sum::<u64>()
+            );
+            assert_eq!(
+                totals.total_unrevealed_stake,
+                entries
+                    .iter()
+                    .filter(|e| !e.revealed)
+                    .map(|e| e.stake)
+                    .sum::<u64>()
+            );
+        }
+    }
+
+    #[test]
+    fn compute_consensus_on_known_entries() {
+        let entry = |stake: u64, weight: u64, prediction: i64, revealed: bool| SettleEntry {
+            stake,
+            weight,
+            prediction,
+            submit_order: 0,
+            revealed,
+            interval: (prediction, prediction),
+            extra_predictions: [0; 3],
+        };
+
+        // (100 × 1 + 200 × 3) / 4 = 175; the unrevealed entry only adds stake
+        let entries = vec![
+            entry(10, 1, 100, true),
+            entry(30, 3, 200, true),
+            entry(50, 9, 900, false),
+        ];
+        assert_eq!(
+            compute_consensus(&entries).unwrap(),
+            ConsensusTotals {
+                consensus: 175,
+                total_revealed_stake: 40,
+                total_unrevealed_stake: 50
+            }
+        );
+
+        // Truncates toward zero for negative means: -250 / 3 = -83
+        let entries = vec![entry(1, 1, -100, true), entry(1, 2, -75, true)];
+        assert_eq!(compute_consensus(&entries).unwrap().consensus, -83);
+
+        // Nobody revealed: no weight to divide by, consensus is 0
+        let entries = vec![entry(10, 1, 100, false), entry(20, 2, 200, false)];
+        assert_eq!(
+            compute_consensus(&entries).unwrap(),
+            ConsensusTotals {
+                consensus: 0,
+                total_revealed_stake: 0,
+                total_unrevealed_stake: 30
+            }
+        );
+        assert_eq!(
+            compute_consensus(&[]).unwrap(),
+            ConsensusTotals {
+                consensus: 0,
+                total_revealed_stake: 0,
+                total_unrevealed_stake: 0
+            }
+        );
+    }
+
+    #[test]
+    fn stored_consensus_replaces_recomputed_one() {
+        let entries = vec![
+            SettleEntry {
+                stake: 1_000,
+                weight: 1_000,
+                prediction: 100,
+                submit_order: 0,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 1_000,
+                weight: 1_000,
+                prediction: 200,
+                submit_order: 1,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+        ];
+        let options = TopicOptions::default();
+        let params = SettleParams {
+            truth: 180,
+            truth_band: None,
+            extra_truths: &[],
+            consensus: Some(120),
+            seed: [0; 32],
+            options: &options,
+        };
+        assert_eq!(
+            compute_settle_plan(&entries, &params).unwrap().consensus,
+            120
+        );
+        let params = SettleParams {
+            consensus: None,
+            ..params
+        };
+        assert_eq!(
+            compute_settle_plan(&entries, &params).unwrap().consensus,
+            150
+        );
+    }
+
+    #[test]
+    fn pro_rata_refunds_split_underfunded_vault_by_stake() {
+        // Half the stakes are missing: everyone, revealed or not, gets half back
+        assert_eq!(
+            pro_rata_refunds(&[3_000, 1_000, 6_000], 5_000),
+            vec![1_500, 500, 3_000]
+        );
+
+        let mut rng = Rng(0x0dd_ba11);
+        for _ in 0..1_000 {
+            let entries = random_entries(&mut rng, 10_000_000_000, 1_000_000_000);
+            let stakes: Vec<u64> = entries.iter().map(|e| e.stake).collect();
+            let available = rng.range(0, stakes.iter().sum());
+            let refunds = pro_rata_refunds(&stakes, available);
+            assert!(refunds.iter().sum::<u64>() <= available);
+            for (e, &refund) in entries.iter().zip(&refunds) {
+                assert!(refund <= e.stake);
+            }
+        }
+    }
+
+    #[test]
+    fn nearest_neighbor_distances_ignore_unrevealed() {
+        let entry = |prediction: i64, revealed: bool| SettleEntry {
+            stake: 1_000,
+            weight: 1_000,
+            prediction,
+            submit_order: 0,
+            revealed,
+            interval: (0, 0),
+            extra_predictions: [0; 3],
+        };
+        let entries = vec![
+            entry(100, true),
+            entry(130, true),
+            entry(101, false),
+            entry(104, true),
+        ];
+        assert_eq!(
+            nearest_neighbor_distances(&entries),
+            vec![Some(4), Some(26), None, Some(4)]
+        );
+        assert_eq!(nearest_neighbor_distances(&entries[..1]), vec![None]);
+        assert_eq!(diversity_boost(None, 100), PRECISION);
+        assert_eq!(diversity_boost(Some(50), 100), PRECISION * 3 / 2);
+        assert_eq!(
+            diversity_boost(Some(u128::MAX), 1),
+            MAX_DIVERSITY_BOOST * PRECISION
+        );
+    }
+
+    #[test]
+    fn diversity_bonus_rewards_isolated_predictions() {
+        // Two bold calls right of consensus: one crowded by a twin, one isolated
+        let entries = vec![
+            SettleEntry {
+                stake: 1_000,
+                weight: 1_000,
+                prediction: 108_000_000,
+                submit_order: 0,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 1_000,
+                weight: 1_000,
+                prediction: 108_000_000,
+                submit_order: 1,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 1_000,
+                weight: 1_000,
+                prediction: 116_000_000,
+                submit_order: 2,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 10_000,
+                weight: 10_000,
+                prediction: 95_000_000,
+                submit_order: 3,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 10_000,
+                weight: 1,
+                prediction: 0,
+                submit_order: 4,
+                revealed: false,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+        ];
+        let plan_for = |reward_mode| {
+            let options = TopicOptions {
+                reward_mode,
+                ..TopicOptions::default()
+            };
+            compute_settle_plan(
+                &entries,
+                &SettleParams {
+                    truth: 112_000_000,
+                    truth_band: None,
+                    extra_truths: &[],
+                    consensus: None,
+                    seed: [0; 32],
+                    options: &options,
+                },
+            )
+            .unwrap()
+        };
+        let plain = plan_for(RewardMode::Proportional);
+        let diverse = plan_for(RewardMode::DiversityBonus);
+
+        let bonus = |plan: &SettlePlan, i: usize| plan.payouts[i] - entries[i].stake;
+        assert!(bonus(&diverse, 2) > bonus(&plain, 2));
+        assert!(bonus(&diverse, 0) < bonus(&plain, 0));
+        assert_eq!(
+            diverse.payouts.iter().sum::<u64>(),
+            plain.payouts.iter().sum::<u64>()
+        );
+    }
+
+    #[test]
+    fn allocate_vault_conserves_lamports_in_priority_order() {
+        let mut rng = Rng(0xa110_ca7e);
+        for _ in 0..5_000 {
+            let payouts: Vec<u64> = (0..rng.range(0, 8))
+                .map(|_| rng.range(0, 1_000_000_000))
+                .collect();
+            let keeper_reward = rng.range(0, 100_000_000);
+            let vault_balance = rng.range(0, 10_000_000_000);
+            let rent_exempt_min = rng.range(0, 2 * RENT_EXEMPT_MIN);
+            // Escrow can cap payouts below the balance, never above it
+            let max_distributable = rng.range(0, vault_balance);
+            let floors: Vec<u64> = payouts
+                .iter()
+                .map(|_| rng.range(0, 1_000_000_000))
+                .collect();
+            let leftover_to_participants = rng.next().is_multiple_of(2);
+
+            let allocation = allocate_vault(
+                &payouts,
+                &floors,
+                keeper_reward,
+                vault_balance,
+                rent_exempt_min,
+                max_distributable,
+                leftover_to_participants,
+            )
+            .unwrap();
+            let paid: u64 = allocation.payouts.iter().sum();
+            let available = vault_balance.saturating_sub(rent_exempt_min);
+            assert_eq!(
+                paid + allocation.keeper_reward + allocation.protocol_fee,
+                available
+            );
+            assert!(paid + allocation.keeper_reward <= max_distributable.min(available));
+
+            // Floors that fit are paid in full before anything above them
+            let kept: Vec<u64> = payouts
+                .iter()
+                .zip(&floors)
+                .map(|(&p, &f)| p.min(f))
+                .collect();
+            if kept.iter().sum::<u64>() <= max_distributable.min(available) {
+                assert!(allocation.payouts.iter().zip(&kept).all(|(p, k)| p >= k));
+            }
+
+            // Participants before the keeper, the keeper before the fee
+            let cap = max_distributable.min(available);
+            let wanted: u64 = payouts.iter().sum();
+            if wanted > cap {
+                // Scaled down: no keeper, and nothing owed to participants
+                // reaches the fee
+                assert_eq!(allocation.keeper_reward, 0);
+                assert!(paid == cap || paid == 0);
+            } else {
+                assert!(allocation.payouts.iter().zip(&payouts).all(|(p, w)| p >= w));
+                if !leftover_to_participants {
+                    assert_eq!(allocation.payouts, payouts);
+                } else if wanted > 0 {
+                    assert_eq!(paid + allocation.keeper_reward, cap);
+                }
+                if paid == wanted && allocation.keeper_reward < keeper_reward {
+                    assert_eq!(paid + allocation.keeper_reward, cap);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn disabled_time_decay_makes_commit_order_irrelevant() {
+        let entries = vec![
+            SettleEntry {
+                stake: 2_000_000,
+                weight: 2_000_000,
+                prediction: 104_000_000,
+                submit_order: 0,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 3_000_000,
+                weight: 3_000_000,
+                prediction: 112_000_000,
+                submit_order: 1,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 1_000_000,
+                weight: 1_000_000,
+                prediction: 118_000_000,
+                submit_order: 2,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 5_000_000,
+                weight: 5_000_000,
+                prediction: 90_000_000,
+                submit_order: 3,
+                revealed: false,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+        ];
+        // Same commitments, committed in the opposite order
+        let mut reordered = entries.clone();
+        for e in &mut reordered {
+            e.submit_order = 3 - e.submit_order + 500;
+        }
+        let payouts = |entries: &[SettleEntry], time_decay_enabled: bool| {
+            let options = TopicOptions {
+                time_decay_enabled,
+                ..TopicOptions::default()
+            };
+            let params = SettleParams {
+                truth: 115_000_000,
+                truth_band: None,
+                extra_truths: &[],
+                consensus: None,
+                seed: [0u8; 32],
+                options: &options,
+            };
+            compute_settle_plan(entries, &params).unwrap().payouts
+        };
+
+        assert_eq!(payouts(&entries, false), payouts(&reordered, false));
+        assert_ne!(payouts(&entries, true), payouts(&reordered, true));
+    }
+
+    #[test]
+    fn prediction_variance_of_known_spread() {
+        let entry = |stake: u64, prediction: i64, revealed: bool| SettleEntry {
+            stake,
+            weight: stake,
+            prediction,
+            submit_order: 0,
+            revealed,
+            interval: (0, 0),
+            extra_predictions: [0; 3],
+        };
+        // 90, 100, 110 at equal stake around 100: (100 + 0 + 100) / 3 = 66.67
+        let entries = vec![
+            entry(1_000, 90_000_000, true),
+            entry(1_000, 100_000_000, true),
+            entry(1_000, 110_000_000, true),
+            entry(9_000, 500_000_000, false),
+        ];
+        assert_eq!(prediction_variance(&entries, 100_000_000), 66_666_666);
+
+        // Stake-weighted around 101: (3_000 × 1² + 1_000 × 3²) / 4_000 = 3.0
+        let entries = vec![
+            entry(3_000, 100_000_000, true),
+            entry(1_000, 104_000_000, true),
+        ];
+        assert_eq!(prediction_variance(&entries, 101_000_000), 3_000_000);
+
+        // Nobody revealed, and extreme spreads saturate instead of overflowing
+        assert_eq!(prediction_variance(&[entry(1_000, 0, false)], 0), 0);
+        let extreme = vec![
+            entry(u64::MAX, i64::MIN + 1, true),
+            entry(u64::MAX, i64::MAX - 1, true),
+        ];
+        assert_eq!(prediction_variance(&extreme, 0), u64::MAX);
+    }
+
+    #[test]
+    fn prepare_settle_rejects_foreign_and_undersized_commitments() {
+        let topic = Topic {
+            commitment_count: 1,
+            ..Topic::default()
+        };
+        let (commitment_key, participant_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let system_owner = Pubkey::default();
+        let settle_with = |owner: &Pubkey, data: &mut [u8]| {
+            let (mut commitment_lamports, mut participant_lamports) = (1_000_000, 0);
+            let accounts = [
+                AccountInfo::new(
+                    &commitment_key,
+                    false,
+                    true,
+                    &mut commitment_lamports,
+                    data,
+                    owner,
+                    false,
+                    0,
+                ),
+                AccountInfo::new(
+                    &participant_key,
+                    false,
+                    true,
+                    &mut participant_lamports,
+                    &mut [],
+                    &system_owner,
+                    false,
+                    0,
+                ),
+            ];
+            match prepare_settle(
+                &topic,
+                Pubkey::new_unique(),
+                &accounts,
+                10_000_000,
+                RENT_EXEMPT_MIN,
+            ) {
+                Err(err) => err,
+                Ok(_) => panic!("settle accepted a bogus commitment"),
+            }
+        };
+
+        let mut data = vec![0u8; Commitment::MAX_SIZE];
+        assert_eq!(
+            settle_with(&Pubkey::new_unique(), &mut data),
+            WorthHubError::InvalidCommitmentAccount.into()
+        );
+        let mut data = vec![0u8; 157];
+        assert_eq!(
+            settle_with(&crate::ID, &mut data),
+            WorthHubError::CommitmentAccountTooSmall.into()
+        );
+        // A well-formed commitment of another topic
+        let mut data = Commitment::DISCRIMINATOR.to_vec();
+        data.resize(Commitment::MAX_SIZE, 0);
+        assert_eq!(
+            settle_with(&crate::ID, &mut data),
+            WorthHubError::InvalidCommitmentAccount.into()
+        );
+    }
+
+    #[test]
+    fn prepare_settle_requires_every_commitment() {
+        let topic = Topic {
+            commitment_count: 2,
+            ..Topic::default()
+        };
+        let (commitment_key, participant_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mut commitment_lamports, mut participant_lamports) = (1_000_000, 0);
+        let mut data = vec![0u8; Commitment::MAX_SIZE];
+        let accounts = [
+            AccountInfo::new(
+                &commitment_key,
+                false,
+                true,
+                &mut commitment_lamports,
+                &mut data,
+                &crate::ID,
+                false,
+                0,
+            ),
+            AccountInfo::new(
+                &participant_key,
+                false,
+                true,
+                &mut participant_lamports,
+                &mut [],
+                &crate::ID,
+                false,
+                0,
+            ),
+        ];
+        match prepare_settle(
+            &topic,
+            Pubkey::new_unique(),
+            &accounts,
+            10_000_000,
+            RENT_EXEMPT_MIN,
+        ) {
+            Err(err) => assert_eq!(err, WorthHubError::MissingCommitments.into()),
+            Ok(_) => panic!("settle accepted an incomplete commitment set"),
+        }
+    }
+
+    #[test]
+    fn closest_wins_pays_nearest_prediction_and_earliest_on_ties() {
+        let entry = |prediction: i64, submit_order: u32, revealed: bool| SettleEntry {
+            stake: 1_000,
+            weight: 1_000,
+            prediction,
+            submit_order,
+            revealed,
+            interval: (0, 0),
+            extra_predictions: [0; 3],
+        };
+        let options = TopicOptions {
+            reward_mode: RewardMode::ClosestWins,
+            ..TopicOptions::default()
+        };
+        let payouts = |entries: &[SettleEntry], truth: i64| {
+            let params = SettleParams {
+                truth,
+                truth_band: None,
+                extra_truths: &[],
+                consensus: None,
+                seed: [0u8; 32],
+                options: &options,
+            };
+            compute_settle_plan(entries, &params).unwrap().payouts
+        };
+
+        // 103 is closest to 101 even though it sits on the consensus side;
+        // the forfeited 1_000 all goes to it
+        let entries = vec![
+            entry(90, 0, true),
+            entry(103, 1, true),
+            entry(120, 2, true),
+            entry(0, 3, false),
+        ];
+        assert_eq!(payouts(&entries, 101), vec![1_000, 2_000, 1_000, 0]);
+
+        // 98 and 104 are both 3 away from 101: the earlier commit wins
+        let entries = vec![entry(104, 5, true), entry(98, 2, true), entry(0, 3, false)];
+        assert_eq!(payouts(&entries, 101), vec![1_000, 2_000, 0]);
+    }
+
+    #[test]
+    fn guaranteed_stakes_survive_the_vault_clamp() {
+        // A revealed winner staking 5M with a 1M bonus, a revealed scorer
+        // with no bonus, and an abstainer, in a vault 900k short
+        let payouts = [6_000_000, 3_000_000, 1_000_000];
+        let max_distributable = 9_100_000;
+
+        // Plain scaling leaves the bonus-less scorer short of their stake
+        let plain = scale_payouts(&payouts, max_distributable).unwrap();
+        assert!(plain[1] < 3_000_000);
+
+        let floors = [5_000_000, 3_000_000, 0];
+        let guaranteed = scale_payouts_above_floors(&payouts, &floors, max_distributable).unwrap();
+        assert!(guaranteed[0] >= 5_000_000 && guaranteed[1] == 3_000_000);
+        // Only the bonus and the unguaranteed stake absorb the shortfall
+        assert_eq!(guaranteed.iter().sum::<u64>(), max_distributable);
+        assert_eq!(guaranteed[0] - 5_000_000 + guaranteed[2], 1_100_000);
+
+        // Floors the vault cannot cover fall back to plain scaling
+        assert_eq!(
+            scale_payouts_above_floors(&payouts, &floors, 7_000_000).unwrap(),
+            scale_payouts(&payouts, 7_000_000).unwrap()
+        );
+    }
+
+    #[test]
+    fn settle_fund_refunds_revealers_and_pays_the_keeper() {
+        let mut topic = Topic {
+            settle_fund: 2_000,
+            ..Topic::default()
+        };
+        topic.options.reveal_bond = 1_000;
+        topic.options.reveal_bond_keeper_bps = 2_500;
+        let entries = vec![
+            SettleEntry {
+                stake: 10,
+                weight: 10,
+                prediction: 1,
+                submit_order: 0,
+                revealed: true,
+                interval: (1, 1),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 10,
+                weight: 10,
+                prediction: 0,
+                submit_order: 1,
+                revealed: false,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 10,
+                weight: 10,
+                prediction: 2,
+                submit_order: 2,
+                revealed: true,
+                interval: (2, 2),
+                extra_predictions: [0; 3],
+            },
+        ];
+        // The trailing transfer is an abstainer's, which posted no bond
+        let mut transfers = vec![(0, 50), (1, 0), (2, 10), (3, 10)];
+
+        let keeper = add_settle_fund(&topic, &entries, &mut transfers).unwrap();
+        assert_eq!(transfers, vec![(0, 800), (1, 0), (2, 760), (3, 10)]);
+        assert_eq!(keeper, 500);
+
+        // A fund short of the refunds it owes is an accounting bug
+        topic.settle_fund = 1_000;
+        assert!(add_settle_fund(&topic, &entries, &mut transfers).is_err());
+    }
+
+    #[test]
+    fn consensus_floor_keeps_small_consensus_deviations_apart() {
+        // Consensus lands near 1 raw unit, so without a floor every edge
+        // saturates MAX_PCT and only accuracy separates the payouts
+        let entries = vec![
+            SettleEntry {
+                stake: 1_000_000,
+                weight: 1_000_000,
+                prediction: 400,
+                submit_order: 0,
+                revealed: true,
+                interval: (400, 400),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 1_000_000,
+                weight: 1_000_000,
+                prediction: 4_000,
+                submit_order: 1,
+                revealed: true,
+                interval: (4_000, 4_000),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 1_000_000,
+                weight: 1_000_000,
+                prediction: -4_396,
+                submit_order: 2,
+                revealed: true,
+                interval: (-4_396, -4_396),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 3_000_000,
+                weight: 3_000_000,
+                prediction: 0,
+                submit_order: 3,
+                revealed: false,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+        ];
+        let bonuses = |consensus_floor: u64| {
+            let options = TopicOptions {
+                consensus_floor,
+                ..TopicOptions::default()
+            };
+            let plan = compute_settle_plan(
+                &entries,
+                &SettleParams {
+                    truth: 5_000,
+                    truth_band: None,
+                    extra_truths: &[],
+                    consensus: None,
+                    seed: [0; 32],
+                    options: &options,
+                },
+            )
+            .unwrap();
+            assert!(plan.consensus.abs() <= 2);
+            (
+                plan.payouts[0] - entries[0].stake,
+                plan.payouts[1] - entries[1].stake,
+            )
+        };
+
+        let (plain_small, plain_large) = bonuses(0);
+        let (floored_small, floored_large) = bonuses(1_000_000);
+        assert!(plain_small > 0 && floored_small > 0);
+        // The bolder prediction's edge now counts, widening the payout spread
+        assert!(floored_large * plain_small > plain_large * floored_small * 5);
+    }
+
+    #[test]
+    fn wrong_direction_penalty_moves_stake_to_the_right_side() {
+        let entries = vec![
+            SettleEntry {
+                stake: 2_000_000,
+                weight: 2_000_000,
+                prediction: 112_000_000,
+                submit_order: 0,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 2_000_000,
+                weight: 2_000_000,
+                prediction: 90_000_000,
+                submit_order: 1,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 1_000_000,
+                weight: 1_000_000,
+                prediction: 100_000_000,
+                submit_order: 2,
+                revealed: false,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+        ];
+        let plan_for = |wrong_direction_penalty_bps: u16| {
+            let options = TopicOptions {
+                wrong_direction_penalty_bps,
+                ..TopicOptions::default()
+            };
+            compute_settle_plan(
+                &entries,
+                &SettleParams {
+                    truth: 115_000_000,
+                    truth_band: None,
+                    extra_truths: &[],
+                    consensus: None,
+                    seed: [0; 32],
+                    options: &options,
+                },
+            )
+            .unwrap()
+        };
+        let free = plan_for(0);
+        let penalized = plan_for(2_500);
+
+        // Boldly wrong used to cost nothing; now a quarter of the stake
+        assert_eq!(free.payouts[1], 2_000_000);
+        assert_eq!(penalized.payouts[1], 1_500_000);
+        assert_eq!(penalized.loser_pool, free.loser_pool + 500_000);
+        assert_eq!(penalized.payouts[0], free.payouts[0] + 500_000);
+        // Every lamport staked is still paid out
+        let total: u64 = entries.iter().map(|e| e.stake).sum();
+        assert_eq!(free.payouts.iter().sum::<u64>(), total);
+        assert_eq!(penalized.payouts.iter().sum::<u64>(), total);
+    }
+
+    #[test]
+    fn leftover_goes_to_participants_when_scaled_or_configured() {
+        // Scaled down: 1_000 distributable against 1_500 owed; the rounding
+        // dust stays with participants and the fee only gets the excess
+        // beyond the escrow
+        let scaled =
+            allocate_vault(&[500, 500, 500], &[0, 0, 0], 100, 2_000, 0, 1_000, false).unwrap();
+        assert_eq!(scaled.payouts.iter().sum::<u64>(), 1_000);
+        assert_eq!((scaled.keeper_reward, scaled.protocol_fee), (0, 1_000));
+
+        // Unscaled: 400 left after payouts and keeper goes to the authority
+        // by default, or back to participants pro-rata when configured
+        let swept = allocate_vault(&[300, 100], &[0, 0], 100, 1_000, 0, 900, false).unwrap();
+        assert_eq!(swept.payouts, vec![300, 100]);
+        assert_eq!((swept.keeper_reward, swept.protocol_fee), (100, 500));
+
+        let returned = allocate_vault(&[300, 100], &[0, 0], 100, 1_000, 0, 900, true).unwrap();
+        assert_eq!(returned.payouts, vec![600, 200]);
+        assert_eq!((returned.keeper_reward, returned.protocol_fee), (100, 100));
+    }
+
+    #[test]
+    fn pool_release_follows_the_reveal_ratio() {
+        let entry = |stake: u64, prediction: i64, submit_order: u32, revealed: bool| SettleEntry {
+            stake,
+            weight: stake,
+            prediction,
+            submit_order,
+            revealed,
+            interval: (prediction, prediction),
+            extra_predictions: [0; 3],
+        };
+        let plan_for = |entries: &[SettleEntry], pool_release_by_reveal_ratio: bool| {
+            let options = TopicOptions {
+                pool_release_by_reveal_ratio,
+                ..TopicOptions::default()
+            };
+            compute_settle_plan(
+                entries,
+                &SettleParams {
+                    truth: 115_000_000,
+                    truth_band: None,
+                    extra_truths: &[],
+                    consensus: None,
+                    seed: [0; 32],
+                    options: &options,
+                },
+            )
+            .unwrap()
+        };
+
+        // 2 of 4 revealed: half the 4M pool is released, 2M returns pro-rata
+        let half = vec![
+            entry(2_000_000, 112_000_000, 0, true),
+            entry(2_000_000, 90_000_000, 1, true),
+            entry(1_000_000, 100_000_000, 2, false),
+            entry(3_000_000, 100_000_000, 3, false),
+        ];
+        let plan = plan_for(&half, true);
+        assert_eq!(plan.payouts[1], 2_000_000 + 500_000);
+        assert_eq!(plan.payouts[2], 250_000);
+        assert_eq!(plan.payouts[3], 750_000);
+        assert_eq!(plan.payouts[0], 2_000_000 + 2_000_000 + 500_000);
+        assert_eq!(plan.payouts.iter().sum::<u64>(), 8_000_000);
+        assert_eq!(plan_for(&half, false).payouts[0], 2_000_000 + 4_000_000);
+
+        // 3 of 4 revealed: three quarters released
+        let mut most = half.clone();
+        most[2].revealed = true;
+        most[2].prediction = 113_000_000;
+        let plan = plan_for(&most, true);
+        let returned: u64 = most.iter().map(|e| e.stake * 750_000 / 8_000_000).sum();
+        assert_eq!(returned, 750_000);
+        assert_eq!(plan.payouts[3], 3_000_000 * 750_000 / 8_000_000);
+        assert_eq!(plan.payouts.iter().sum::<u64>(), 8_000_000);
+    }
+
+    #[test]
+    fn extra_dimensions_are_scored_and_summed() {
+        let entry = |prediction: i64, extra: i64, submit_order: u32, revealed: bool| SettleEntry {
+            stake: 1_000_000,
+            weight: 1_000_000,
+            prediction,
+            submit_order,
+            revealed,
+            interval: (prediction, prediction),
+            extra_predictions: [extra, 0, 0],
+        };
+        // Same first-dimension call; only entry 1 also reads the second right
+        let entries = vec![
+            entry(112_000_000, 190_000_000, 0, true),
+            entry(112_000_000, 212_000_000, 1, true),
+            entry(95_000_000, 200_000_000, 2, true),
+            entry(0, 0, 3, false),
+        ];
+        let options = TopicOptions {
+            dimensions: 2,
+            ..TopicOptions::default()
+        };
+        let plan_for = |extra_truths: &[i64]| {
+            compute_settle_plan(
+                &entries,
+                &SettleParams {
+                    truth: 115_000_000,
+                    truth_band: None,
+                    extra_truths,
+                    consensus: None,
+                    seed: [0; 32],
+                    options: &options,
+                },
+            )
+            .unwrap()
+        };
+
+        let single = plan_for(&[]);
+        assert!(single.payouts[0] > single.payouts[1]);
+
+        let both = plan_for(&[215_000_000]);
+        assert!(both.payouts[1] > both.payouts[0]);
+        assert!(both.payouts[1] > single.payouts[1]);
+        assert_eq!(both.payouts.iter().sum::<u64>(), 4_000_000);
+        assert_eq!(both.consensus, single.consensus);
+    }
+
+    #[test]
+    fn forfeit_by_order_lets_early_non_revealers_keep_more() {
+        let entries = vec![
+            SettleEntry {
+                stake: 1_000,
+                weight: 1_000,
+                prediction: 90_000_000,
+                submit_order: 1,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 1_000,
+                weight: 1_000,
+                prediction: 110_000_000,
+                submit_order: 2,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 1_000,
+                weight: 1,
+                prediction: 0,
+                submit_order: 0,
+                revealed: false,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 1_000,
+                weight: 1,
+                prediction: 0,
+                submit_order: 7,
+                revealed: false,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+        ];
+        let mut options = TopicOptions {
+            reward_mode: RewardMode::WinnerTakeAll,
+            ..TopicOptions::default()
+        };
+        let plan_with = |options: &TopicOptions| {
+            let params = SettleParams {
+                truth: 110_000_000,
+                truth_band: None,
+                extra_truths: &[],
+                consensus: None,
+                seed: [0; 32],
+                options,
+            };
+            compute_settle_plan(&entries, &params).unwrap()
+        };
+
+        let plan = plan_with(&options);
+        assert_eq!(plan.loser_pool, 2_000);
+        assert_eq!(plan.payouts, vec![1_000, 3_000, 0, 0]);
+
+        // The first committer forfeits half, anyone past the curve all of it
+        options.forfeit_by_order = true;
+        let plan = plan_with(&options);
+        assert_eq!(plan.loser_pool, 1_500);
+        assert_eq!(plan.payouts, vec![1_000, 2_500, 500, 0]);
+    }
+
+    #[test]
+    fn settlement_outcomes_follow_transfer_order() {
+        let entries = vec![
+            SettleEntry {
+                stake: 1_000,
+                weight: 1_000,
+                prediction: 110,
+                submit_order: 0,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 1_000,
+                weight: 1_000,
+                prediction: 0,
+                submit_order: 1,
+                revealed: false,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+        ];
+        let participants = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        // Entries came from pairs 2 and 0; the abstainer from pair 1
+        let transfers = [(2, 1_500), (0, 0), (1, 1_000)];
+        let outcomes = settlement_outcomes(&entries, &[7, 0], &participants, &transfers);
+
+        assert_eq!(outcomes.len(), 3);
+        assert_eq!(outcomes[0].participant, participants[2]);
+        assert_eq!(
+            (
+                outcomes[0].prediction,
+                outcomes[0].score,
+                outcomes[0].payout
+            ),
+            (110, 7, 1_500)
+        );
+        assert!(outcomes[0].revealed && !outcomes[0].abstained);
+        assert_eq!(outcomes[1].participant, participants[0]);
+        assert!(!outcomes[1].revealed && !outcomes[1].abstained);
+        assert_eq!(outcomes[2].participant, participants[1]);
+        assert_eq!(outcomes[2].payout, 1_000);
+        assert!(outcomes[2].abstained);
+    }
+
+    #[test]
+    fn accuracy_tolerance_treats_near_misses_as_perfect_hits() {
+        let entry = |prediction: i64, weight: u64| SettleEntry {
+            stake: 1_000,
+            weight,
+            prediction,
+            submit_order: 0,
+            revealed: true,
+            interval: (0, 0),
+            extra_predictions: [0; 3],
+        };
+        let entries = vec![
+            entry(90_000_000, 3_000),
+            entry(112_000_000, 1_000),
+            entry(113_000_000, 1_000),
+            entry(120_000_000, 1_000),
+        ];
+        let mut options = TopicOptions {
+            time_decay_enabled: false,
+            ..TopicOptions::default()
+        };
+        let scores_with = |options: &TopicOptions| {
+            let params = SettleParams {
+                truth: 110_000_000,
+                truth_band: None,
+                extra_truths: &[],
+                consensus: None,
+                seed: [0; 32],
+                options,
+            };
+            compute_settle_plan(&entries, &params).unwrap().scores
+        };
+
+        // Exact accuracy: the closer 112 outscores the bolder 113
+        let exact = scores_with(&options);
+        assert!(exact[1] > exact[2]);
+
+        // Both within the 5 unit deadband are perfect hits, so boldness
+        // decides; 120 is scored on its 5 units beyond the deadband
+        options.accuracy_tolerance = 5_000_000;
+        let tolerant = scores_with(&options);
+        assert!(tolerant[2] > tolerant[1]);
+        assert!(tolerant[3] > exact[3]);
+        assert!(tolerant[3] < tolerant[2]);
+    }
+
+    #[test]
+    fn disabled_rewards_return_every_stake_but_keep_scores() {
+        let entries = vec![
+            SettleEntry {
+                stake: 1_000,
+                weight: 1_000,
+                prediction: 90_000_000,
+                submit_order: 0,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 2_000,
+                weight: 2_000,
+                prediction: 110_000_000,
+                submit_order: 1,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 3_000,
+                weight: 3_000,
+                prediction: 0,
+                submit_order: 2,
+                revealed: false,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+        ];
+        let options = TopicOptions {
+            reward_enabled: false,
+            keeper_reward_bps: 1_000,
+            wrong_direction_penalty_bps: 5_000,
+            ..TopicOptions::default()
+        };
+        let params = SettleParams {
+            truth: 108_000_000,
+            truth_band: None,
+            extra_truths: &[],
+            consensus: None,
+            seed: [0; 32],
+            options: &options,
+        };
+        let plan = compute_settle_plan(&entries, &params).unwrap();
+
+        assert_eq!(plan.payouts, vec![1_000, 2_000, 3_000]);
+        assert_eq!((plan.loser_pool, plan.keeper_reward), (0, 0));
+        assert!(plan.scores[1] > 0);
+        let rewarded = TopicOptions {
+            reward_enabled: true,
+            ..options.clone()
+        };
+        let rewarded_plan = compute_settle_plan(
+            &entries,
+            &SettleParams {
+                options: &rewarded,
+                ..params
+            },
+        )
+        .unwrap();
+        assert_eq!(plan.scores, rewarded_plan.scores);
+    }
+
+    #[test]
+    fn unanimous_reveals_refund_the_pool_unless_swept() {
+        let entries = vec![
+            SettleEntry {
+                stake: 1_000,
+                weight: 1_000,
+                prediction: 100_000_000,
+                submit_order: 0,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 2_000,
+                weight: 2_000,
+                prediction: 100_000_000,
+                submit_order: 1,
+                revealed: true,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+            SettleEntry {
+                stake: 3_000,
+                weight: 3_000,
+                prediction: 0,
+                submit_order: 2,
+                revealed: false,
+                interval: (0, 0),
+                extra_predictions: [0; 3],
+            },
+        ];
+        let options = TopicOptions {
+            keeper_reward_bps: 1_000,
+            ..TopicOptions::default()
+        };
+        let params = SettleParams {
+            truth: 104_000_000,
+            truth_band: None,
+            extra_truths: &[],
+            consensus: None,
+            seed: [0; 32],
+            options: &options,
+        };
+        let plan = compute_settle_plan(&entries, &params).unwrap();
+
+        assert_eq!(plan.consensus, 100_000_000);
+        assert!(plan.scores.iter().all(|&s| s == 0));
+        assert_eq!(plan.loser_pool, 3_000);
+        assert_eq!(plan.payouts, vec![1_500, 3_000, 1_500]);
+        assert_eq!(plan.keeper_reward, 0);
+
+        let sweep = TopicOptions {
+            unanimous_policy: UnanimousPolicy::Sweep,
+            ..options.clone()
+        };
+        let swept = compute_settle_plan(
+            &entries,
+            &SettleParams {
+                options: &sweep,
+                ..params
+            },
+        )
+        .unwrap();
+        assert_eq!(swept.payouts, vec![1_000, 2_000, 0]);
+        assert_eq!(swept.keeper_reward, 300);
+
+        // One revealer off the consensus is no longer the degenerate case
+        let mut split = entries.clone();
+        split[1].prediction = 99_000_000;
+        let plan = compute_settle_plan(&split, &params).unwrap();
+        assert_eq!(plan.payouts[2], 0);
+    }
+
+    #[test]
+    fn prepare_settle_rejects_a_commitment_off_its_canonical_address_or_participant() {
+        let topic_key = Pubkey::new_unique();
+        let topic = Topic {
+            commitment_count: 1,
+            ..Topic::default()
+        };
+        let participant_key = Pubkey::new_unique();
+        let (commitment_key, bump) = Pubkey::find_program_address(
+            &[
+                b"commitment",
+                topic_key.as_ref(),
+                participant_key.as_ref(),
+                0u32.to_le_bytes().as_ref(),
+            ],
+            &crate::ID,
+        );
+        let settle_with = |key: &Pubkey, bump: u8, recipient: &Pubkey| {
+            let commitment = Commitment {
+                topic: topic_key,
+                participant: participant_key,
+                commitment_hash: [0; 32],
+                stake_amount: 1_000,
+                submit_order: 0,
+                prediction_value: 0,
+                revealed: true,
+                salt: [0; 32],
+                settled: false,
+                bump,
+                weight: 1_000,
+                encrypted_prediction: [0; 64],
+                interval_low: 0,
+                interval_high: 0,
+                nonce: 0,
+                abstained: false,
+                extra_predictions: [0; 3],
+                aux_commitment: [0; 32],
+                rent_payer: Pubkey::default(),
+                stake_payer: Pubkey::default(),
+            };
+            let mut data = Vec::new();
+            commitment.try_serialize(&mut data).unwrap();
+            data.resize(Commitment::MAX_SIZE, 0);
+            let (mut commitment_lamports, mut participant_lamports) = (1_000_000, 0);
+            let system_owner = Pubkey::default();
+            let accounts = [
+                AccountInfo::new(
+                    key,
+                    false,
+                    true,
+                    &mut commitment_lamports,
+                    &mut data,
+                    &crate::ID,
+                    false,
+                    0,
+                ),
+                AccountInfo::new(
+                    recipient,
+                    false,
+                    true,
+                    &mut participant_lamports,
+                    &mut [],
+                    &system_owner,
+                    false,
+                    0,
+                ),
+            ];
+            prepare_settle(&topic, topic_key, &accounts, 10_000_000, RENT_EXEMPT_MIN).map(|_| ())
+        };
+
+        assert!(settle_with(&commitment_key, bump, &participant_key).is_ok());
+        assert_eq!(
+            settle_with(&commitment_key, bump.wrapping_sub(1), &participant_key).unwrap_err(),
+            WorthHubError::InvalidCommitmentAccount.into()
+        );
+        assert_eq!(
+            settle_with(&Pubkey::new_unique(), bump, &participant_key).unwrap_err(),
+            WorthHubError::InvalidCommitmentAccount.into()
+        );
+        // A payout account other than the commitment's participant
+        assert_eq!(
+            settle_with(&commitment_key, bump, &Pubkey::new_unique()).unwrap_err(),
+            WorthHubError::InvalidCommitmentAccount.into()
+        );
+    }
+
+    #[test]
+    fn reveal_rebates_come_out_of_the_protocol_fee() {
+        let entry = |revealed: bool| SettleEntry {
+            stake: 1_000,
+            weight: 1_000,
+            prediction: 100,
+            submit_order: 0,
+            revealed,
+            interval: (100, 100),
+            extra_predictions: [0; 3],
+        };
+        let entries = [entry(true), entry(false), entry(true)];
+        // The trailing payout is an abstainer's, which takes no rebate
+        let allocation = || VaultAllocation {
+            payouts: vec![1_000, 0, 1_000, 500],
+            keeper_reward: 50,
+            protocol_fee: 1_001,
+        };
+
+        let mut covered = allocation();
+        pay_reveal_rebates(&mut covered, &entries, 300);
+        assert_eq!(covered.payouts, vec![1_300, 0, 1_300, 500]);
+        assert_eq!((covered.keeper_reward, covered.protocol_fee), (50, 401));
+
+        // A short fee is split equally; the odd lamport stays with the fee
+        let mut short = allocation();
+        pay_reveal_rebates(&mut short, &entries, 800);
+        assert_eq!(short.payouts, vec![1_500, 0, 1_500, 500]);
+        assert_eq!(short.protocol_fee, 1);
+
+        let mut none = allocation();
+        pay_reveal_rebates(&mut none, &entries, 0);
+        assert_eq!(none.payouts, allocation().payouts);
+    }
 }
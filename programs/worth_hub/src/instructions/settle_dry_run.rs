@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use crate::errors::WorthHubError;
+use crate::instructions::settle::{prepare_settle, SettleRun};
+use crate::state::{Topic, TopicStatus};
+
+#[derive(Accounts)]
+pub struct SettleDryRun<'info> {
+    /// Would-be settle signer, held to the same settler check as settle
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = topic.status == TopicStatus::Finalized @ WorthHubError::TopicNotFinalized,
+        constraint = (topic.options.public_settle || topic.is_settler(&authority.key()))
+            @ WorthHubError::UnauthorizedSettler,
+    )]
+    pub topic: Account<'info, Topic>,
+
+    /// The vault PDA holding staked SOL
+    /// CHECK: Validated by seeds and against `topic.vault`
+    #[account(
+        seeds = [b"vault", topic.key().as_ref()],
+        bump = topic.vault_bump,
+        address = topic.vault @ WorthHubError::VaultMismatch,
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    // Remaining accounts: pairs of (commitment_account, participant_account),
+    // as for settle
+}
+
+/// Lamport movements a real settle would make with the same accounts
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SettlePreview {
+    /// Payout per (commitment, participant) pair, in remaining_accounts
+    /// order; 0 for non-revealers and already-settled commitments
+    pub payouts: Vec<u64>,
+    /// Reward for the settle signer
+    pub keeper_reward: u64,
+    /// Remainder swept to the topic authority
+    pub protocol_fee: u64,
+}
+
+/// Run the settle math against the real participant set and vault balance,
+/// logging and returning the would-be payouts. No lamports move, the topic
+/// stays Finalized, and the accounts settle creates are left alone.
+pub fn handle_settle_dry_run<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SettleDryRun<'info>>,
+) -> Result<SettlePreview> {
+    let topic = &ctx.accounts.topic;
+    let remaining = &ctx.remaining_accounts;
+    let rent_exempt_min = Rent::get()?.minimum_balance(0);
+    let vault_balance = ctx.accounts.vault.lamports();
+
+    let SettleRun {
+        plan,
+        transfers,
+        keeper_reward,
+        protocol_fee,
+        ..
+    } = prepare_settle(
+        topic,
+        topic.key(),
+        remaining,
+        vault_balance,
+        rent_exempt_min,
+    )?;
+
+    let mut payouts = vec![0u64; remaining.len() / 2];
+    for &(pair, payout) in &transfers {
+        payouts[pair] = payout;
+        msg!(
+            "Dry run payout: participant={}, lamports={}",
+            remaining[pair * 2 + 1].key(),
+            payout
+        );
+    }
+
+    msg!(
+        "Settle dry run: id={}, consensus={}, loser_pool={}, keeper_reward={}, protocol_fee={}",
+        topic.topic_id,
+        plan.consensus,
+        plan.loser_pool,
+        keeper_reward,
+        protocol_fee
+    );
+
+    Ok(SettlePreview {
+        payouts,
+        keeper_reward,
+        protocol_fee,
+    })
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::errors::WorthHubError;
+use crate::events::RevealTurnSkipped;
+use crate::instructions::reveal::check_reveal_window;
+use crate::state::Topic;
+
+#[derive(Accounts)]
+pub struct SkipRevealTurn<'info> {
+    #[account(
+        mut,
+        constraint = topic.options.ordered_reveal @ WorthHubError::OrderedRevealDisabled,
+    )]
+    pub topic: Account<'info, Topic>,
+}
+
+/// Pass over the pending ordered-reveal turn once it has lasted
+/// `options.reveal_turn_seconds`, so a committer who never reveals cannot
+/// stall everyone after them. Permissionless. The skipped commitment may
+/// still reveal later; it just no longer holds up the queue. A canceled
+/// commitment's order is skipped the same way.
+pub fn handle_skip_reveal_turn(ctx: Context<SkipRevealTurn>) -> Result<()> {
+    let topic = &mut ctx.accounts.topic;
+    check_reveal_window(topic)?;
+    require!(
+        topic.next_expected_reveal_order < topic.next_submit_order,
+        WorthHubError::NoRevealTurnToSkip
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now >= topic.reveal_turn_started() + topic.options.reveal_turn_seconds as i64,
+        WorthHubError::RevealTurnNotExpired
+    );
+
+    let skipped_order = topic.next_expected_reveal_order;
+    topic.next_expected_reveal_order += 1;
+    topic.reveal_turn_started_at = now;
+
+    emit!(RevealTurnSkipped {
+        topic_id: topic.topic_id,
+        skipped_order,
+        next_expected_reveal_order: topic.next_expected_reveal_order,
+    });
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use crate::errors::WorthHubError;
+use crate::instructions::settle::{compute_consensus, ConsensusTotals, SettleEntry};
+use crate::state::{Commitment, Topic, TopicStatus};
+
+#[derive(Accounts)]
+pub struct SnapshotConsensus<'info> {
+    #[account(
+        mut,
+        constraint = topic.status == TopicStatus::Finalized @ WorthHubError::TopicNotFinalized,
+        constraint = topic.consensus_snapshot_at == 0 @ WorthHubError::ConsensusAlreadySnapshotted,
+    )]
+    pub topic: Account<'info, Topic>,
+
+    // Remaining accounts: every commitment on the topic, each exactly once
+}
+
+/// Compute the weighted consensus and revealed/forfeited stake totals over
+/// all of the topic's commitments and store them on the topic. Anyone may
+/// call it: the full commitment set is required, so the result is the same
+/// whoever does. Refunded and abstained commitments are skipped, as in settle.
+pub fn handle_snapshot_consensus<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SnapshotConsensus<'info>>,
+) -> Result<()> {
+    let topic_key = ctx.accounts.topic.key();
+    let remaining = ctx.remaining_accounts;
+    require!(
+        remaining.len() == ctx.accounts.topic.commitment_count as usize,
+        WorthHubError::IncompleteCommitmentSet
+    );
+
+    let mut entries: Vec<SettleEntry> = Vec::with_capacity(remaining.len());
+    for (i, info) in remaining.iter().enumerate() {
+        require_keys_eq!(
+            *info.owner,
+            crate::ID,
+            WorthHubError::InvalidCommitmentAccount
+        );
+        require!(
+            remaining[..i].iter().all(|prev| prev.key() != info.key()),
+            WorthHubError::IncompleteCommitmentSet
+        );
+        let commitment = Commitment::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require_keys_eq!(
+            commitment.topic,
+            topic_key,
+            WorthHubError::InvalidCommitmentAccount
+        );
+        commitment.verify_address(info.key)?;
+        if commitment.settled || commitment.abstained {
+            continue;
+        }
+        entries.push(SettleEntry {
+            stake: commitment.stake_amount,
+            weight: commitment.weight,
+            prediction: commitment.prediction_value,
+            submit_order: commitment.submit_order,
+            revealed: commitment.revealed,
+            interval: (commitment.interval_low, commitment.interval_high),
+            extra_predictions: commitment.extra_predictions,
+        });
+    }
+
+    let ConsensusTotals {
+        consensus,
+        total_revealed_stake,
+        total_unrevealed_stake,
+    } = compute_consensus(&entries)?;
+
+    let topic = &mut ctx.accounts.topic;
+    topic.consensus = consensus;
+    topic.total_revealed_stake = total_revealed_stake;
+    topic.total_unrevealed_stake = total_unrevealed_stake;
+    topic.consensus_snapshot_at = Clock::get()?.unix_timestamp;
+
+    msg!(
+        "Consensus snapshot: id={}, consensus={}, revealed_stake={}, unrevealed_stake={}",
+        topic.topic_id,
+        consensus,
+        total_revealed_stake,
+        total_unrevealed_stake
+    );
+    Ok(())
+}
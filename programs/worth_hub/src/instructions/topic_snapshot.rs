@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::instructions::deadlines::ReadTopic;
+use crate::state::TopicStatus;
+
+/// A topic's phase, deadlines and counters, read at a single slot
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct TopicSnapshot {
+    pub status: TopicStatus,
+    pub commit_deadline: i64,
+    pub reveal_deadline: i64,
+    /// Cluster unix timestamp the snapshot was taken at
+    pub now: i64,
+    pub commitment_count: u32,
+    pub reveal_count: u32,
+    pub total_stake: u64,
+    pub truth_value: i64,
+}
+
+/// Return a consistent view of the topic alongside the current clock
+pub fn handle_topic_snapshot(ctx: Context<ReadTopic>) -> Result<TopicSnapshot> {
+    let topic = &ctx.accounts.topic;
+    let clock = Clock::get()?;
+    Ok(TopicSnapshot {
+        status: topic.status,
+        commit_deadline: topic.commit_deadline,
+        reveal_deadline: topic.reveal_deadline,
+        now: clock.unix_timestamp,
+        commitment_count: topic.commitment_count,
+        reveal_count: topic.reveal_count,
+        total_stake: topic.total_stake,
+        truth_value: topic.truth_value,
+    })
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::errors::WorthHubError;
+use crate::state::Commitment;
+
+/// Longest proof `verify_range_proof` accepts, leaving room for a real
+/// zero-knowledge proof to replace the placeholder
+pub const MAX_RANGE_PROOF_LEN: usize = 512;
+
+#[derive(Accounts)]
+pub struct VerifyRangeProof<'info> {
+    pub commitment: Account<'info, Commitment>,
+}
+
+/// Check a proof that the prediction behind the commitment's
+/// `aux_commitment` lies in `[low, high]`, without it being revealed.
+/// Until a zero-knowledge verifier is wired in, the only proof accepted is
+/// `Commitment::placeholder_range_proof`. Returns the result via return data.
+pub fn handle_verify_range_proof(
+    ctx: Context<VerifyRangeProof>,
+    low: i64,
+    high: i64,
+    proof: Vec<u8>,
+) -> Result<bool> {
+    let commitment = &ctx.accounts.commitment;
+    require!(
+        commitment.aux_commitment != [0u8; 32],
+        WorthHubError::MissingAuxCommitment
+    );
+    require!(
+        low <= high && proof.len() <= MAX_RANGE_PROOF_LEN,
+        WorthHubError::InvalidRangeProof
+    );
+
+    let valid =
+        proof[..] == Commitment::placeholder_range_proof(&commitment.aux_commitment, low, high);
+
+    msg!(
+        "Range proof {}: commitment={}, range=[{}, {}]",
+        if valid { "valid" } else { "invalid" },
+        commitment.key(),
+        low,
+        high
+    );
+    Ok(valid)
+}
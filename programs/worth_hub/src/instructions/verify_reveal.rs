@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::state::Commitment;
+
+#[derive(Accounts)]
+pub struct VerifyReveal<'info> {
+    pub commitment: Account<'info, Commitment>,
+}
+
+/// Recompute the commitment hash exactly as `handle_reveal` does and report
+/// whether it matches, so agents can check a pre-image before spending a
+/// reveal transaction. Returns the result via return data.
+pub fn handle_verify_reveal(
+    ctx: Context<VerifyReveal>,
+    prediction_value: i64,
+    salt: [u8; 32],
+    participant: Pubkey,
+) -> Result<bool> {
+    let computed_hash = Commitment::compute_hash(prediction_value, &salt, &participant);
+    let matches = computed_hash == ctx.accounts.commitment.commitment_hash;
+
+    msg!(
+        "Reveal pre-image {}: commitment={}",
+        if matches { "matches" } else { "does not match" },
+        ctx.accounts.commitment.key()
+    );
+    Ok(matches)
+}
@@ -0,0 +1,188 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::errors::WorthHubError;
+use crate::events::TopicVoided;
+use crate::state::{Commitment, ParticipantState, Topic, TopicConfig, TopicStatus};
+
+#[derive(Accounts)]
+pub struct VoidTopic<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        close = authority,
+        constraint = topic.authority == authority.key() @ WorthHubError::UnauthorizedAuthority,
+        constraint = (topic.status == TopicStatus::Open || topic.status == TopicStatus::Revealing)
+            @ WorthHubError::AlreadyFinalized,
+    )]
+    pub topic: Account<'info, Topic>,
+
+    /// The vault PDA holding staked SOL
+    /// CHECK: Validated by seeds and against `topic.vault`
+    #[account(
+        mut,
+        seeds = [b"vault", topic.key().as_ref()],
+        bump = topic.vault_bump,
+        address = topic.vault @ WorthHubError::VaultMismatch,
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// The topic's config PDA; required once `topic.config` is set, and
+    /// closed with the topic (rent back to the authority, who paid it)
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"topic_config", topic.key().as_ref()],
+        bump = topic_config.bump,
+    )]
+    pub topic_config: Option<Account<'info, TopicConfig>>,
+
+    pub system_program: Program<'info, System>,
+
+    // Remaining accounts: groups of (commitment_account, participant_account,
+    // rent_payer_account, stake_payer_account) covering every commitment of
+    // the topic, then pairs of (participant_state_account,
+    // rent_payer_account) covering at least every participant still holding
+    // a commitment
+}
+
+/// Refund every committer's stake (to its stake payer) and reveal bond (to
+/// the participant), close their commitments and participant states (rent
+/// back to whoever paid it), and close the topic and its config (rent back
+/// to the authority). Nothing keyed by the topic survives, so its id can be
+/// created afresh; a voided topic never reached settle, so it has no
+/// settlement record.
+pub fn handle_void_topic<'info>(
+    ctx: Context<'_, '_, 'info, 'info, VoidTopic<'info>>,
+) -> Result<()> {
+    let topic = &ctx.accounts.topic;
+    let topic_key = topic.key();
+
+    require!(
+        topic.config == Pubkey::default() || ctx.accounts.topic_config.is_some(),
+        WorthHubError::InvalidTopicConfig
+    );
+
+    let remaining = &ctx.remaining_accounts;
+    let commitment_accounts = 4 * topic.commitment_count as usize;
+    require!(
+        remaining.len() >= commitment_accounts
+            && (remaining.len() - commitment_accounts).is_multiple_of(2),
+        WorthHubError::MissingCommitments
+    );
+    let (commitment_groups, state_pairs) = remaining.split_at(commitment_accounts);
+
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let system_prog = ctx.accounts.system_program.to_account_info();
+    let bump_slice = &[topic.vault_bump];
+    let vault_signer_seeds: &[&[u8]] = &[b"vault", topic_key.as_ref(), bump_slice];
+
+    let mut refunded_stake: u64 = 0;
+
+    for group in commitment_groups.chunks(4) {
+        let commitment_info = &group[0];
+        let participant_info = &group[1];
+        let rent_payer_info = &group[2];
+        let stake_payer_info = &group[3];
+
+        let commitment: Account<Commitment> = Account::try_from(commitment_info)?;
+        require!(
+            commitment.topic == topic_key && commitment.participant == participant_info.key(),
+            WorthHubError::InvalidCommitmentAccount
+        );
+        require!(
+            commitment.rent_payer == rent_payer_info.key()
+                && commitment.stake_payer == stake_payer_info.key(),
+            WorthHubError::InvalidRefundRecipient
+        );
+
+        // Revealers also get their reveal bond back
+        let bond = if commitment.revealed {
+            topic.options.reveal_bond
+        } else {
+            0
+        };
+        for (to, amount) in [
+            (stake_payer_info, commitment.stake_amount),
+            (participant_info, bond),
+        ] {
+            if amount == 0 {
+                continue;
+            }
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    system_prog.clone(),
+                    system_program::Transfer {
+                        from: vault_info.clone(),
+                        to: to.clone(),
+                    },
+                    &[vault_signer_seeds],
+                ),
+                amount,
+            )?;
+        }
+        refunded_stake = refunded_stake
+            .checked_add(commitment.stake_amount)
+            .ok_or(WorthHubError::ArithmeticOverflow)?;
+
+        commitment.close(rent_payer_info.clone())?;
+    }
+
+    // A participant state closes once, so a repeated one fails to load; the
+    // holders counted must be exactly the topic's participants
+    let mut holders: u32 = 0;
+    for pair in state_pairs.chunks(2) {
+        let state_info = &pair[0];
+        let rent_payer_info = &pair[1];
+
+        // Only commit creates participant states, always at their PDA
+        let state: Account<ParticipantState> = Account::try_from(state_info)?;
+        require!(
+            state.topic == topic_key,
+            WorthHubError::InvalidParticipantState
+        );
+        require!(
+            state.rent_payer == rent_payer_info.key(),
+            WorthHubError::InvalidRefundRecipient
+        );
+        if state.open_commitments > 0 {
+            holders += 1;
+        }
+        state.close(rent_payer_info.clone())?;
+    }
+    require!(
+        holders == topic.participant_count,
+        WorthHubError::MissingCommitments
+    );
+
+    // Anything left in the vault was never staked; return it to the authority
+    let leftover = vault_info.lamports();
+    if leftover > 0 {
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                system_prog,
+                system_program::Transfer {
+                    from: vault_info,
+                    to: ctx.accounts.authority.to_account_info(),
+                },
+                &[vault_signer_seeds],
+            ),
+            leftover,
+        )?;
+    }
+
+    emit!(TopicVoided {
+        topic_id: topic.topic_id,
+        refunded_count: topic.commitment_count,
+        refunded_stake,
+    });
+
+    msg!(
+        "Topic voided: id={}, refunded {} commitments, {} lamports",
+        topic.topic_id,
+        topic.commitment_count,
+        refunded_stake
+    );
+    Ok(())
+}
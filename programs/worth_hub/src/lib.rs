@@ -1,63 +1,352 @@
 use anchor_lang::prelude::*;
 
 pub mod errors;
+pub mod events;
 pub mod instructions;
+pub mod price_feed;
 pub mod state;
 
 use instructions::*;
+use state::TopicOptions;
 
 declare_id!("8qXNZGRTwYeAw3fdPsaqJ3cq5ieyZWtxrXTZizmuZFeQ");
 
-#[program]
-pub mod worth_hub {
+// anchor-lang 0.31's `#[program]` expansion calls the deprecated
+// AccountInfo::realloc from code it emits beside the module
+#[allow(deprecated)]
+mod program_entry {
     use super::*;
 
-    /// Create a new prediction topic
-    pub fn create_topic(
-        ctx: Context<CreateTopic>,
-        topic_id: u64,
-        description: String,
-        symbol: String,
-        commit_deadline: i64,
-        reveal_deadline: i64,
-        min_stake: u64,
-    ) -> Result<()> {
-        handle_create_topic(
-            ctx,
-            topic_id,
-            description,
-            symbol,
-            commit_deadline,
-            reveal_deadline,
-            min_stake,
-        )
-    }
+    #[program]
+    pub mod worth_hub {
+        use super::*;
 
-    /// Submit a commitment (hash + stake) for a topic
-    pub fn commit(
-        ctx: Context<CommitPrediction>,
-        commitment_hash: [u8; 32],
-        stake_amount: u64,
-    ) -> Result<()> {
-        handle_commit(ctx, commitment_hash, stake_amount)
-    }
+        /// Create a new prediction topic
+        #[allow(clippy::too_many_arguments)]
+        pub fn create_topic(
+            ctx: Context<CreateTopic>,
+            topic_id: u64,
+            description: String,
+            symbol: String,
+            commit_deadline: i64,
+            reveal_deadline: i64,
+            min_stake: u64,
+            options: TopicOptions,
+            metadata_uri: Option<String>,
+        ) -> Result<()> {
+            handle_create_topic(
+                ctx,
+                topic_id,
+                description,
+                symbol,
+                commit_deadline,
+                reveal_deadline,
+                min_stake,
+                options,
+                metadata_uri,
+            )
+        }
 
-    /// Reveal the prediction value and salt
-    pub fn reveal(
-        ctx: Context<RevealPrediction>,
-        prediction_value: i64,
-        salt: [u8; 32],
-    ) -> Result<()> {
-        handle_reveal(ctx, prediction_value, salt)
-    }
+        /// Create a series of topics from one template, spaced `period` seconds
+        /// apart, with ids `base_topic_id..base_topic_id + count`
+        #[allow(clippy::too_many_arguments)]
+        pub fn create_recurring_topics<'info>(
+            ctx: Context<'_, '_, 'info, 'info, CreateRecurringTopics<'info>>,
+            base_topic_id: u64,
+            count: u8,
+            period: i64,
+            description: String,
+            symbol: String,
+            first_commit_deadline: i64,
+            first_reveal_deadline: i64,
+            min_stake: u64,
+            options: TopicOptions,
+            metadata_uri: Option<String>,
+        ) -> Result<()> {
+            handle_create_recurring_topics(
+                ctx,
+                base_topic_id,
+                count,
+                period,
+                description,
+                symbol,
+                first_commit_deadline,
+                first_reveal_deadline,
+                min_stake,
+                options,
+                metadata_uri,
+            )
+        }
 
-    /// Oracle submits the true value
-    pub fn finalize(ctx: Context<FinalizeTopic>, truth_value: i64) -> Result<()> {
-        handle_finalize(ctx, truth_value)
-    }
+        /// Submit a commitment (hash + stake, optional scoring weight and
+        /// auditor-encrypted prediction) for a topic
+        pub fn commit(
+            ctx: Context<CommitPrediction>,
+            commitment_hash: [u8; 32],
+            stake_amount: u64,
+            weight: Option<u64>,
+            encrypted_prediction: Option<[u8; 64]>,
+            stake_from_rent_payer: bool,
+            aux_commitment: Option<[u8; 32]>,
+        ) -> Result<()> {
+            handle_commit(
+                ctx,
+                commitment_hash,
+                stake_amount,
+                weight,
+                encrypted_prediction,
+                stake_from_rent_payer,
+                aux_commitment,
+            )
+        }
+
+        /// Withdraw a commitment and its stake before the commit deadline
+        pub fn cancel_commitment(ctx: Context<CancelCommitment>) -> Result<()> {
+            handle_cancel_commitment(ctx)
+        }
+
+        /// Reveal the prediction value and salt
+        pub fn reveal(
+            ctx: Context<RevealPrediction>,
+            prediction_value: i64,
+            salt: [u8; 32],
+        ) -> Result<()> {
+            handle_reveal(ctx, prediction_value, salt)
+        }
+
+        /// Reveal an interval prediction and salt
+        pub fn reveal_interval(
+            ctx: Context<RevealPrediction>,
+            low: i64,
+            high: i64,
+            salt: [u8; 32],
+        ) -> Result<()> {
+            handle_reveal_interval(ctx, low, high, salt)
+        }
+
+        /// Reveal one prediction per truth dimension, and the salt
+        pub fn reveal_vector(
+            ctx: Context<RevealPrediction>,
+            predictions: Vec<i64>,
+            salt: [u8; 32],
+        ) -> Result<()> {
+            handle_reveal_vector(ctx, predictions, salt)
+        }
+
+        /// Prove a commitment but opt out of scoring; the stake is returned at settle
+        pub fn abstain(
+            ctx: Context<RevealPrediction>,
+            prediction_value: i64,
+            salt: [u8; 32],
+        ) -> Result<()> {
+            handle_abstain(ctx, prediction_value, salt)
+        }
+
+        /// Reveal several of the signer's commitments in one transaction
+        pub fn reveal_many<'info>(
+            ctx: Context<'_, '_, 'info, 'info, RevealMany<'info>>,
+            reveals: Vec<RevealEntry>,
+        ) -> Result<()> {
+            handle_reveal_many(ctx, reveals)
+        }
+
+        /// Skip an ordered-reveal turn that has run past `reveal_turn_seconds`
+        pub fn skip_reveal_turn(ctx: Context<SkipRevealTurn>) -> Result<()> {
+            handle_skip_reveal_turn(ctx)
+        }
+
+        /// Oracle explicitly opens the reveal phase
+        pub fn open_reveal(ctx: Context<OpenReveal>) -> Result<()> {
+            handle_open_reveal(ctx)
+        }
+
+        /// Oracle submits the true value, optionally with a confidence band
+        pub fn finalize(
+            ctx: Context<FinalizeTopic>,
+            truth_value: i64,
+            truth_low: Option<i64>,
+            truth_high: Option<i64>,
+        ) -> Result<()> {
+            handle_finalize(ctx, truth_value, truth_low, truth_high)
+        }
+
+        /// Oracle submits one true value per dimension of a multi-dimension topic
+        pub fn finalize_vector(ctx: Context<FinalizeTopic>, truths: Vec<i64>) -> Result<()> {
+            handle_finalize_vector(ctx, truths)
+        }
+
+        /// Oracle records a truth the finalize sanity checks reject, counted in
+        /// the topic's failed_finalize_count
+        pub fn report_rejected_truth(
+            ctx: Context<FinalizeTopic>,
+            truth_value: i64,
+            truth_low: Option<i64>,
+            truth_high: Option<i64>,
+            extra_truths: Vec<i64>,
+        ) -> Result<()> {
+            handle_report_rejected_truth(ctx, truth_value, truth_low, truth_high, extra_truths)
+        }
+
+        /// Finalize a small topic and settle it in the same transaction; returns
+        /// lamports paid to participants
+        pub fn finalize_and_settle<'info>(
+            ctx: Context<'_, '_, 'info, 'info, FinalizeAndSettle<'info>>,
+            truth_value: i64,
+            truth_low: Option<i64>,
+            truth_high: Option<i64>,
+        ) -> Result<u64> {
+            handle_finalize_and_settle(ctx, truth_value, truth_low, truth_high)
+        }
+
+        /// Calculate rewards and distribute SOL; returns lamports paid to participants
+        pub fn settle<'info>(
+            ctx: Context<'_, '_, 'info, 'info, SettleTopic<'info>>,
+        ) -> Result<u64> {
+            handle_settle(ctx)
+        }
+
+        /// Create the signer's leaderboard entry, credited by every settle it is
+        /// passed to
+        pub fn init_leaderboard(ctx: Context<InitLeaderboard>) -> Result<()> {
+            handle_init_leaderboard(ctx)
+        }
+
+        /// Compute and log settle's payouts without transferring or settling
+        pub fn settle_dry_run<'info>(
+            ctx: Context<'_, '_, 'info, 'info, SettleDryRun<'info>>,
+        ) -> Result<SettlePreview> {
+            handle_settle_dry_run(ctx)
+        }
 
-    /// Calculate rewards and distribute SOL
-    pub fn settle<'info>(ctx: Context<'_, '_, 'info, 'info, SettleTopic<'info>>) -> Result<()> {
-        handle_settle(ctx)
+        /// Store the finalized topic's consensus and stake totals
+        pub fn snapshot_consensus<'info>(
+            ctx: Context<'_, '_, 'info, 'info, SnapshotConsensus<'info>>,
+        ) -> Result<()> {
+            handle_snapshot_consensus(ctx)
+        }
+
+        /// Read-only: the loser pool settle would distribute, live during the
+        /// reveal phase and exact once finalized
+        pub fn loser_pool_preview<'info>(
+            ctx: Context<'_, '_, 'info, 'info, LoserPoolPreview<'info>>,
+        ) -> Result<u64> {
+            handle_loser_pool_preview(ctx)
+        }
+
+        /// Read-only: lamports `settle` would pay one commitment, against the finalized truth
+        pub fn projected_payout<'info>(
+            ctx: Context<'_, '_, 'info, 'info, ProjectedPayout<'info>>,
+        ) -> Result<u64> {
+            handle_projected_payout(ctx)
+        }
+
+        /// Last-resort refund of one participant's stake after a stuck settle
+        pub fn refund_participant(ctx: Context<RefundParticipant>) -> Result<()> {
+            handle_refund_participant(ctx)
+        }
+
+        /// A revealed participant's own refund once a finalized topic has gone
+        /// unsettled past `self_refund_timeout`
+        pub fn self_refund(ctx: Context<SelfRefund>) -> Result<()> {
+            handle_self_refund(ctx)
+        }
+
+        /// Void a topic before finalization, refunding every committer
+        pub fn void_topic<'info>(
+            ctx: Context<'_, '_, 'info, 'info, VoidTopic<'info>>,
+        ) -> Result<()> {
+            handle_void_topic(ctx)
+        }
+
+        /// Restart the commit phase of a topic that received no commitments
+        pub fn reopen_topic(
+            ctx: Context<ReopenTopic>,
+            commit_deadline: i64,
+            reveal_deadline: i64,
+        ) -> Result<()> {
+            handle_reopen_topic(ctx, commit_deadline, reveal_deadline)
+        }
+
+        /// Change the minimum stake of a topic that has no commitments yet
+        pub fn set_min_stake(ctx: Context<SetMinStake>, min_stake: u64) -> Result<()> {
+            handle_set_min_stake(ctx, min_stake)
+        }
+
+        /// Extend the allowed prediction grid of a topic that has no
+        /// commitments yet, kept in its TopicConfig PDA
+        pub fn add_allowed_values(ctx: Context<AddAllowedValues>, values: Vec<i64>) -> Result<()> {
+            handle_add_allowed_values(ctx, values)
+        }
+
+        /// Transfer a topic to a new authority before it is settled
+        pub fn set_authority(ctx: Context<SetAuthority>, new_authority: Pubkey) -> Result<()> {
+            handle_set_authority(ctx, new_authority)
+        }
+
+        /// Replace a topic's oracle before it is settled (topic authority only)
+        pub fn authority_replace_oracle(
+            ctx: Context<AuthorityReplaceOracle>,
+            new_oracle: Pubkey,
+        ) -> Result<()> {
+            handle_authority_replace_oracle(ctx, new_oracle)
+        }
+
+        /// Check (without mutating state) whether a pre-image matches a commitment
+        pub fn verify_reveal(
+            ctx: Context<VerifyReveal>,
+            prediction_value: i64,
+            salt: [u8; 32],
+            participant: Pubkey,
+        ) -> Result<bool> {
+            handle_verify_reveal(ctx, prediction_value, salt, participant)
+        }
+
+        /// Check (without mutating state) a proof that the prediction behind a
+        /// commitment's aux commitment lies in `[low, high]` (placeholder verifier)
+        pub fn verify_range_proof(
+            ctx: Context<VerifyRangeProof>,
+            low: i64,
+            high: i64,
+            proof: Vec<u8>,
+        ) -> Result<bool> {
+            handle_verify_range_proof(ctx, low, high, proof)
+        }
+
+        /// Seconds remaining until the commit deadline (0 if passed)
+        pub fn time_until_commit_deadline(ctx: Context<ReadTopic>) -> Result<u64> {
+            handle_time_until_commit_deadline(ctx)
+        }
+
+        /// Seconds remaining until the reveal deadline (0 if passed)
+        pub fn time_until_reveal_deadline(ctx: Context<ReadTopic>) -> Result<u64> {
+            handle_time_until_reveal_deadline(ctx)
+        }
+
+        /// Status, deadlines, clock and counters in one consistent read
+        pub fn topic_snapshot(ctx: Context<ReadTopic>) -> Result<TopicSnapshot> {
+            handle_topic_snapshot(ctx)
+        }
+
+        /// Emit a reminder event while the reveal window is open
+        pub fn ping_reveal_window(ctx: Context<ReadTopic>) -> Result<()> {
+            handle_ping_reveal_window(ctx)
+        }
+
+        /// Read-only: one page of the time-decay LN_TABLE plus PRECISION and MAX_PCT
+        pub fn get_decay_table(ctx: Context<GetDecayTable>, page: u32) -> Result<DecayTablePage> {
+            handle_get_decay_table(ctx, page)
+        }
+
+        /// Read-only: whether a participant's commitment exists, is revealed or
+        /// settled, and its stake
+        pub fn participant_status(
+            ctx: Context<ParticipantStatus>,
+            participant: Pubkey,
+            nonce: u32,
+        ) -> Result<CommitmentStatus> {
+            handle_participant_status(ctx, participant, nonce)
+        }
     }
 }
+
+pub use program_entry::*;
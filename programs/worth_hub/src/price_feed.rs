@@ -0,0 +1,240 @@
+use anchor_lang::prelude::*;
+use crate::errors::WorthHubError;
+
+/// A price read from an on-chain price account: `price × 10^expo`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FeedPrice {
+    pub price: i64,
+    pub expo: i32,
+}
+
+/// Decimal places of the fixed point truths and predictions use (1e6)
+const FIXED_POINT_DECIMALS: i32 = 6;
+
+impl FeedPrice {
+    /// The price in the 1e6 fixed point of truth values, rounded toward
+    /// zero; None if it does not fit
+    pub fn to_fixed_point(&self) -> Option<i128> {
+        let shift = self.expo.checked_add(FIXED_POINT_DECIMALS)?;
+        let scale = 10i128.checked_pow(shift.unsigned_abs())?;
+        if shift >= 0 {
+            (self.price as i128).checked_mul(scale)
+        } else {
+            Some(self.price as i128 / scale)
+        }
+    }
+}
+
+/// A price update as parsed from a price account, with what `read_price`
+/// checks it by
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PriceUpdate {
+    pub price: FeedPrice,
+    /// The provider's id of the feed the price is for
+    pub feed_id: [u8; 32],
+    /// Unix timestamp the price was published at
+    pub publish_time: i64,
+    /// Whether the provider fully verified the update
+    pub fully_verified: bool,
+}
+
+/// A price account layout finalize can check truths against. Layouts are
+/// told apart by their leading discriminator, so another provider's
+/// account only needs its own implementation and an entry in `read_price`.
+pub trait PriceAccount {
+    /// Program that must own accounts of this layout
+    const OWNER: Pubkey;
+
+    /// Leading bytes identifying the layout
+    const DISCRIMINATOR: [u8; 8];
+
+    /// The update held in `data`, discriminator included; None if `data`
+    /// is too short for the layout
+    fn parse(data: &[u8]) -> Option<PriceUpdate>;
+}
+
+/// Pyth pull-oracle `PriceUpdateV2`: discriminator(8) + write_authority(32)
+/// + verification_level(1, or 2 for `Partial { num_signatures }`)
+/// + feed_id(32) + price(i64) + conf(u64) + exponent(i32)
+/// + publish_time(i64) + ...
+pub struct PythPriceUpdate;
+
+impl PriceAccount for PythPriceUpdate {
+    /// The Pyth Solana receiver program
+    const OWNER: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+    const DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+    fn parse(data: &[u8]) -> Option<PriceUpdate> {
+        let level_at = 8 + 32;
+        let (message_at, fully_verified) = match *data.get(level_at)? {
+            0 => (level_at + 2, false),
+            _ => (level_at + 1, true),
+        };
+        let feed_id = data.get(message_at..message_at + 32)?.try_into().ok()?;
+        let price_at = message_at + 32;
+        let price = i64::from_le_bytes(data.get(price_at..price_at + 8)?.try_into().ok()?);
+        let expo_at = price_at + 8 + 8;
+        let expo = i32::from_le_bytes(data.get(expo_at..expo_at + 4)?.try_into().ok()?);
+        let publish_at = expo_at + 4;
+        let publish_time =
+            i64::from_le_bytes(data.get(publish_at..publish_at + 8)?.try_into().ok()?);
+        Some(PriceUpdate {
+            price: FeedPrice { price, expo },
+            feed_id,
+            publish_time,
+            fully_verified,
+        })
+    }
+}
+
+/// Read the price from a price account of any supported layout, owned by
+/// that layout's program, for `feed_id`, fully verified and published at
+/// most `max_age` seconds before `now`
+pub fn read_price(
+    owner: &Pubkey,
+    data: &[u8],
+    feed_id: &[u8; 32],
+    now: i64,
+    max_age: u32,
+) -> Result<FeedPrice> {
+    let update = if data.starts_with(&PythPriceUpdate::DISCRIMINATOR) {
+        require_keys_eq!(
+            *owner,
+            PythPriceUpdate::OWNER,
+            WorthHubError::InvalidPriceFeed
+        );
+        PythPriceUpdate::parse(data).ok_or(WorthHubError::InvalidPriceFeed)?
+    } else {
+        return err!(WorthHubError::InvalidPriceFeed);
+    };
+    require!(
+        update.feed_id == *feed_id && update.fully_verified,
+        WorthHubError::InvalidPriceFeed
+    );
+    require!(
+        now.saturating_sub(update.publish_time) <= max_age as i64,
+        WorthHubError::StalePrice
+    );
+    Ok(update.price)
+}
+
+/// Whether `truth` lies within `tolerance_bps` of `price`, both in fixed
+/// point
+pub fn within_tolerance(truth: i64, price: i128, tolerance_bps: u16) -> bool {
+    let allowed = price.unsigned_abs().saturating_mul(tolerance_bps as u128) / 10_000;
+    (truth as i128).abs_diff(price) <= allowed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FEED_ID: [u8; 32] = [7; 32];
+    const PUBLISHED: i64 = 1_700_000_000;
+
+    fn pyth_account(partial: bool, price: i64, expo: i32) -> Vec<u8> {
+        let mut data = PythPriceUpdate::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[0; 32]);
+        if partial {
+            data.extend_from_slice(&[0, 3]);
+        } else {
+            data.push(1);
+        }
+        data.extend_from_slice(&FEED_ID);
+        data.extend_from_slice(&price.to_le_bytes());
+        data.extend_from_slice(&5u64.to_le_bytes());
+        data.extend_from_slice(&expo.to_le_bytes());
+        data.extend_from_slice(&PUBLISHED.to_le_bytes());
+        data.extend_from_slice(&[0; 8 * 4]);
+        data
+    }
+
+    fn read_pyth(data: &[u8]) -> Result<FeedPrice> {
+        read_price(&PythPriceUpdate::OWNER, data, &FEED_ID, PUBLISHED + 30, 60)
+    }
+
+    #[test]
+    fn parses_pyth_price_updates_at_either_verification_level() {
+        for partial in [false, true] {
+            let update =
+                PythPriceUpdate::parse(&pyth_account(partial, 10_012_345_678, -8)).unwrap();
+            assert_eq!(
+                update.price,
+                FeedPrice {
+                    price: 10_012_345_678,
+                    expo: -8
+                }
+            );
+            assert_eq!(update.price.to_fixed_point(), Some(100_123_456));
+            assert_eq!(update.feed_id, FEED_ID);
+            assert_eq!(update.publish_time, PUBLISHED);
+            assert_eq!(update.fully_verified, !partial);
+        }
+        assert_eq!(
+            read_pyth(&pyth_account(false, 10_012_345_678, -8)).unwrap(),
+            FeedPrice {
+                price: 10_012_345_678,
+                expo: -8
+            }
+        );
+        assert!(read_pyth(&[0; 64]).is_err());
+        assert!(read_pyth(&pyth_account(false, 1, -8)[..60]).is_err());
+    }
+
+    #[test]
+    fn rejects_a_price_account_not_owned_by_the_receiver() {
+        let data = pyth_account(false, 1, -8);
+        assert!(read_price(&Pubkey::new_unique(), &data, &FEED_ID, PUBLISHED, 60).is_err());
+        assert!(read_price(&crate::ID, &data, &FEED_ID, PUBLISHED, 60).is_err());
+    }
+
+    #[test]
+    fn rejects_a_stale_price() {
+        let data = pyth_account(false, 1, -8);
+        assert!(read_price(&PythPriceUpdate::OWNER, &data, &FEED_ID, PUBLISHED + 60, 60).is_ok());
+        assert_eq!(
+            read_price(&PythPriceUpdate::OWNER, &data, &FEED_ID, PUBLISHED + 61, 60).unwrap_err(),
+            WorthHubError::StalePrice.into()
+        );
+    }
+
+    #[test]
+    fn rejects_another_feeds_price() {
+        let data = pyth_account(false, 1, -8);
+        assert_eq!(
+            read_price(&PythPriceUpdate::OWNER, &data, &[8; 32], PUBLISHED, 60).unwrap_err(),
+            WorthHubError::InvalidPriceFeed.into()
+        );
+    }
+
+    #[test]
+    fn rejects_a_partially_verified_price() {
+        assert_eq!(
+            read_pyth(&pyth_account(true, 1, -8)).unwrap_err(),
+            WorthHubError::InvalidPriceFeed.into()
+        );
+    }
+
+    #[test]
+    fn fixed_point_conversion_and_tolerance() {
+        assert_eq!(
+            FeedPrice { price: 42, expo: 0 }.to_fixed_point(),
+            Some(42_000_000)
+        );
+        assert_eq!(
+            FeedPrice {
+                price: -15,
+                expo: -1
+            }
+            .to_fixed_point(),
+            Some(-1_500_000)
+        );
+        assert_eq!(FeedPrice { price: 1, expo: 40 }.to_fixed_point(), None);
+
+        assert!(within_tolerance(100_500_000, 100_000_000, 50));
+        assert!(!within_tolerance(100_500_001, 100_000_000, 50));
+        assert!(within_tolerance(99_500_000, 100_000_000, 50));
+        assert!(!within_tolerance(100_000_001, 100_000_000, 0));
+    }
+}
@@ -1,9 +1,12 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
 
-/// Status of a prediction topic
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+/// Status of a prediction topic. Serialized as a single byte holding the
+/// variant index, so variants must only ever be appended.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
 pub enum TopicStatus {
     /// Accepting commitments
+    #[default]
     Open,
     /// Commit phase ended, reveal phase active
     Revealing,
@@ -13,15 +16,299 @@ pub enum TopicStatus {
     Settled,
 }
 
+/// Phase a topic is effectively in, from its status and the clock. Status
+/// alone lags behind the deadlines: a topic stays `Open` past its commit
+/// deadline until a reveal or `open_reveal` moves it on.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Phase {
+    /// Before the commit deadline
+    Commit,
+    /// Between the commit and reveal deadlines
+    Reveal,
+    /// Past the reveal deadline, waiting for the oracle
+    AwaitingFinalize,
+    /// Oracle has submitted the truth value
+    Finalized,
+    /// Rewards have been distributed
+    Settled,
+}
+
+/// How the loser pool is distributed among revealed participants
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum RewardMode {
+    /// Split the pool in proportion to each participant's score
+    #[default]
+    Proportional,
+    /// The single highest scorer takes the whole pool
+    WinnerTakeAll,
+    /// Proportional, with each score boosted by the inverse of the
+    /// participant's share of revealed stake (capped)
+    UnderdogBoost,
+    /// Score revealed intervals: zero if truth falls outside, otherwise
+    /// inversely proportional to the interval width
+    IntervalScoring,
+    /// Proportional, with each score boosted by the distance from the
+    /// participant's prediction to the nearest other revealed one (capped)
+    DiversityBonus,
+    /// The revealed prediction closest to the truth takes the whole pool,
+    /// regardless of direction or consensus; ties go to the earliest commit
+    ClosestWins,
+}
+
+/// How a winner is picked among participants with identical top scores
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum TieBreak {
+    /// Lowest submit_order wins
+    #[default]
+    EarliestOrder,
+    /// Highest submit_order wins
+    LatestOrder,
+    /// Pseudo-random pick seeded by the blockhash recorded at finalize
+    Random,
+}
+
+/// What settle does with the loser pool when every revealed prediction
+/// equals the consensus, leaving no one an edge to score on
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum UnanimousPolicy {
+    /// Return the pool to every committer pro-rata to stake
+    #[default]
+    Refund,
+    /// Leave the pool undistributed, as for any other pool nobody scored on
+    Sweep,
+}
+
+/// Per-topic configuration chosen at creation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct TopicOptions {
+    /// Seconds after finalization before the authority may refund a
+    /// participant directly via `refund_participant` (at least
+    /// `MIN_REFUND_TIMEOUT`)
+    pub refund_timeout: i64,
+    /// If true, reveals are only accepted after the oracle calls `open_reveal`
+    pub manual_reveal_gate: bool,
+    /// Loser pool distribution mode
+    pub reward_mode: RewardMode,
+    /// Tie-break among equal top scorers, used for the winner-take-all pick
+    /// and for the rounding dust left over in proportional mode
+    pub tiebreak: TieBreak,
+    /// Share of the loser pool, in basis points, split equally among all
+    /// revealers before the score-weighted distribution
+    pub reveal_rebate_bps: u16,
+    /// Share of the loser pool, in basis points, paid to whoever signs
+    /// `settle`, taken before the rebate and score-weighted distribution
+    pub keeper_reward_bps: u16,
+    /// If true, anyone may call `settle` once the topic is finalized
+    pub public_settle: bool,
+    /// Exponent, in basis points, applied to the accuracy factor of the
+    /// score (10_000 = neutral, at most MAX_WEIGHT_BPS)
+    pub accuracy_weight_bps: u16,
+    /// Exponent, in basis points, applied to the boldness (alignment) factor
+    /// of the score (10_000 = neutral, at most MAX_WEIGHT_BPS)
+    pub boldness_weight_bps: u16,
+    /// Most any one participant may take of the score-weighted pool, in
+    /// basis points (0 = uncapped); the excess goes to the next-highest
+    /// scorers. Ignored by WinnerTakeAll and ClosestWins.
+    pub max_payout_bps: u16,
+    /// Share of commitments, in basis points, that once revealed lets the
+    /// oracle finalize before `reveal_deadline` (0 = always wait)
+    pub early_finalize_threshold_bps: u16,
+    /// If false, commit order does not matter: every score gets the same
+    /// time-decay factor, as for instant-resolution markets
+    pub time_decay_enabled: bool,
+    /// If true, revealed participants are paid back at least their stake
+    /// whenever the vault can cover every such stake; only bonuses are
+    /// scaled down when the vault falls short
+    pub guarantee_stake_return: bool,
+    /// Inclusive lower bound on predictions and on the finalized truth
+    pub min_prediction: Option<i64>,
+    /// Inclusive upper bound on predictions and on the finalized truth
+    pub max_prediction: Option<i64>,
+    /// Lamports every reveal posts into the topic's settle fund (0 = none),
+    /// returned at settle less the keeper's share
+    pub reveal_bond: u64,
+    /// Share of each reveal bond, in basis points, paid to whoever signs
+    /// `settle`
+    pub reveal_bond_keeper_bps: u16,
+    /// Smallest |consensus| used to normalize percentage deviations, so a
+    /// consensus near zero does not push every deviation to the cap
+    /// (0 = no floor beyond 1)
+    pub consensus_floor: u64,
+    /// Share of a wrong-direction revealer's stake, in basis points, that
+    /// is forfeited into the loser pool (0 = stake returned in full)
+    pub wrong_direction_penalty_bps: u16,
+    /// If true, escrowed lamports left after participant payouts and the
+    /// keeper reward (e.g. a pool nobody scored on) go back to participants
+    /// pro-rata instead of to the topic authority
+    pub leftover_to_participants: bool,
+    /// If true, only the revealed fraction (by commitment count) of the
+    /// loser pool is distributed; the rest goes back to every committer
+    /// pro-rata to stake
+    pub pool_release_by_reveal_ratio: bool,
+    /// Number of truth dimensions (1 to MAX_DIMENSIONS). Multi-dimension
+    /// topics are revealed with `reveal_vector` and finalized with
+    /// `finalize_vector`; each dimension is scored on its own and the
+    /// scores are summed.
+    pub dimensions: u8,
+    /// If true, commitments must be revealed in `submit_order`; a later
+    /// commitment waits until every earlier one has revealed or had its
+    /// turn skipped via `skip_reveal_turn`
+    pub ordered_reveal: bool,
+    /// Seconds each ordered-reveal turn lasts before anyone may skip it
+    /// (must be non-zero when `ordered_reveal` is set)
+    pub reveal_turn_seconds: u32,
+    /// If true, each salt must start with `Commitment::salt_prefix` for its
+    /// commitment, so no salt is reused across commitments
+    pub bind_salt: bool,
+    /// If true, a non-revealer forfeits only the share of its stake the
+    /// forfeit curve gives its submit order, earlier committers less, and
+    /// gets the rest back at settle
+    pub forfeit_by_order: bool,
+    /// Deadband around the truth band: a prediction within this distance
+    /// of it counts as a perfect hit, and accuracy only measures the error
+    /// beyond it (0 = exact)
+    pub accuracy_tolerance: u64,
+    /// Largest distance, in basis points of the price, between a truth and
+    /// the topic's price feed (only used with a price feed)
+    pub price_tolerance_bps: u16,
+    /// Provider id of the feed the price feed account must hold (only used
+    /// with a price feed)
+    pub price_feed_id: [u8; 32],
+    /// Oldest, in seconds before finalize, the feed's price may be
+    /// published (only used with a price feed, where it must be non-zero)
+    pub price_max_age: u32,
+    /// If true, a finalize before the reveal phase is over changes nothing
+    /// and returns the seconds left instead of failing
+    pub soft_finalize: bool,
+    /// Reveal window required per SOL of minimum stake, in seconds; see
+    /// `min_reveal_window` (0 disables)
+    pub reveal_seconds_per_sol: u32,
+    /// If false, settle redistributes nothing: every commitment, revealed
+    /// or not, gets its stake back, and scores only feed the record and
+    /// leaderboards
+    pub reward_enabled: bool,
+    /// Loser pool handling when every revealed prediction sits on the
+    /// consensus (score-based reward modes only)
+    pub unanimous_policy: UnanimousPolicy,
+    /// Seconds after finalization before a revealed or abstained participant
+    /// may take their own stake back via `self_refund` if the topic is still
+    /// unsettled (at least `refund_timeout`)
+    pub self_refund_timeout: i64,
+    /// Lamports paid at settle to each revealer out of the topic
+    /// authority's protocol fee (never the loser pool), reimbursing the cost
+    /// of revealing; split equally when the fee falls short (0 = none)
+    pub reveal_rebate: u64,
+    /// Fewest distinct participants the topic needs before it can be
+    /// finalized; short of it the oracle cannot finalize and the authority
+    /// voids the topic, refunding everyone (0 disables)
+    pub min_participants: u32,
+}
+
+impl Default for TopicOptions {
+    fn default() -> Self {
+        Self {
+            refund_timeout: Self::DEFAULT_REFUND_TIMEOUT,
+            manual_reveal_gate: false,
+            reward_mode: RewardMode::default(),
+            tiebreak: TieBreak::default(),
+            reveal_rebate_bps: 0,
+            keeper_reward_bps: 0,
+            public_settle: false,
+            accuracy_weight_bps: Self::NEUTRAL_WEIGHT_BPS,
+            boldness_weight_bps: Self::NEUTRAL_WEIGHT_BPS,
+            max_payout_bps: 0,
+            early_finalize_threshold_bps: 0,
+            time_decay_enabled: true,
+            guarantee_stake_return: false,
+            min_prediction: None,
+            max_prediction: None,
+            reveal_bond: 0,
+            reveal_bond_keeper_bps: 0,
+            consensus_floor: 0,
+            wrong_direction_penalty_bps: 0,
+            leftover_to_participants: false,
+            pool_release_by_reveal_ratio: false,
+            dimensions: 1,
+            ordered_reveal: false,
+            reveal_turn_seconds: 0,
+            bind_salt: false,
+            forfeit_by_order: false,
+            accuracy_tolerance: 0,
+            price_tolerance_bps: 0,
+            price_feed_id: [0; 32],
+            price_max_age: 0,
+            soft_finalize: false,
+            reveal_seconds_per_sol: 0,
+            reward_enabled: true,
+            unanimous_policy: UnanimousPolicy::default(),
+            self_refund_timeout: Self::DEFAULT_SELF_REFUND_TIMEOUT,
+            reveal_rebate: 0,
+            min_participants: 0,
+        }
+    }
+}
+
+impl TopicOptions {
+    /// Upper bound for basis-point fields
+    pub const MAX_BPS: u16 = 10_000;
+
+    /// Default `refund_timeout` (7 days)
+    pub const DEFAULT_REFUND_TIMEOUT: i64 = 7 * 24 * 60 * 60;
+
+    /// Shortest `refund_timeout`, so settle always gets a window after
+    /// finalization before the authority can pull stakes out from under it
+    pub const MIN_REFUND_TIMEOUT: i64 = 60;
+
+    /// Default `self_refund_timeout` (30 days)
+    pub const DEFAULT_SELF_REFUND_TIMEOUT: i64 = 30 * 24 * 60 * 60;
+
+    /// Score weight exponent of 1.0
+    pub const NEUTRAL_WEIGHT_BPS: u16 = 10_000;
+
+    /// Largest score weight exponent (3.0)
+    pub const MAX_WEIGHT_BPS: u16 = 30_000;
+
+    /// i64(8) + bool(1) + enum(1)*2 + u16(2)*2 + bool(1) + u16(2)*4 + bool(1)*2
+    /// + option<i64>(9)*2 + u64(8) + u16(2) + u64(8) + u16(2) + bool(1)*2 + u8(1)
+    /// + bool(1) + u32(4) + bool(1)*2 + u64(8) + u16(2) + feed_id(32) + u32(4)
+    /// + bool(1) + u32(4) + bool(1) + enum(1) + i64(8) + u64(8) + u32(4)
+    pub const MAX_SIZE: usize = 8 + 1 + 1 + 1 + 2 + 2 + 1 + 2 + 2 + 2 + 2 + 1 + 1
+        + 9 + 9 + 8 + 2 + 8 + 2 + 1 + 1 + 1
+        + 1 + 4 + 1 + 1 + 8 + 2 + 32 + 4
+        + 1 + 4 + 1 + 1 + 8 + 8 + 4;
+
+    /// Most truth dimensions a topic may carry; dimensions past the first
+    /// live in the `extra_*` arrays of Topic and Commitment
+    pub const MAX_DIMENSIONS: u8 = 4;
+
+    /// Whether `value` lies within `[min_prediction, max_prediction]`,
+    /// either bound being open when unset
+    pub fn within_bounds(&self, value: i64) -> bool {
+        self.min_prediction.is_none_or(|min| value >= min)
+            && self.max_prediction.is_none_or(|max| value <= max)
+    }
+}
+
+/// Byte offset of `Topic::status` in the account data: discriminator(8) +
+/// pubkey(32)*3 + topic_id(8). Keepers memcmp this byte against a
+/// `TopicStatus` index to find, e.g., finalized topics awaiting settle.
+#[constant]
+pub const TOPIC_STATUS_OFFSET: usize = 8 + 32 * 3 + 8;
+
 /// A prediction topic that agents can bet on
 #[account]
+#[derive(Default)]
 pub struct Topic {
     /// Authority who created this topic
     pub authority: Pubkey,
     /// Oracle authority who can finalize
     pub oracle_authority: Pubkey,
+    /// Optional keeper allowed to settle (Pubkey::default() if unset)
+    pub settle_authority: Pubkey,
     /// Unique topic identifier
     pub topic_id: u64,
+    /// Current status; kept ahead of the strings at `TOPIC_STATUS_OFFSET`
+    pub status: TopicStatus,
     /// Human-readable description (max 256 bytes)
     pub description: String,
     /// Trading symbol (e.g., "AAPL", "BTC-USD") max 32 bytes
@@ -30,8 +317,6 @@ pub struct Topic {
     pub commit_deadline: i64,
     /// Unix timestamp: reveal phase deadline
     pub reveal_deadline: i64,
-    /// Current status
-    pub status: TopicStatus,
     /// The true value submitted by oracle (fixed-point, 1e6 precision)
     pub truth_value: i64,
     /// Total SOL staked across all commitments (lamports)
@@ -46,14 +331,183 @@ pub struct Topic {
     pub vault_bump: u8,
     /// Bump seed for this topic PDA
     pub bump: u8,
+    /// Unix timestamp when the oracle finalized (0 until then)
+    pub finalized_at: i64,
+    /// Unix timestamp when the oracle opened reveals (0 unless `open_reveal` was called)
+    pub reveal_opened_at: i64,
+    /// Most recent blockhash at finalize, seeding the random tie-break
+    pub finalize_seed: [u8; 32],
+    /// Lamports paid to participants at settle, excluding the protocol fee
+    pub total_settled_payout: u64,
+    /// Lower bound of the oracle's confidence band (equals `truth_value` when none was given)
+    pub truth_low: i64,
+    /// Upper bound of the oracle's confidence band (equals `truth_value` when none was given)
+    pub truth_high: i64,
+    /// Weighted consensus stored by `snapshot_consensus`
+    pub consensus: i128,
+    /// Revealed stake stored by `snapshot_consensus`
+    pub total_revealed_stake: u64,
+    /// Forfeited (unrevealed) stake stored by `snapshot_consensus`
+    pub total_unrevealed_stake: u64,
+    /// Unix timestamp of `snapshot_consensus` (0 until then, and again once
+    /// a refund changes the stakes it covered)
+    pub consensus_snapshot_at: i64,
+    /// URI of off-chain JSON with the full market rules (empty if unset)
+    pub metadata_uri: String,
+    /// Participant stakes currently held in the vault
+    pub escrowed_stake: u64,
+    /// Fees currently held in the vault
+    pub escrowed_fees: u64,
+    /// Prize funds currently held in the vault
+    pub escrowed_prize: u64,
+    /// Optional custodian who must co-sign every commit (Pubkey::default() if unset)
+    pub cosigner: Pubkey,
+    /// Stake-weighted variance of the revealed predictions around the
+    /// consensus, recorded at settle (fixed-point, 1e6 precision)
+    pub prediction_variance: u64,
+    /// Reveal bonds currently held in the vault, kept apart from the escrow
+    /// buckets so they only ever go back to revealers or to the settler
+    pub settle_fund: u64,
+    /// Truth of dimensions 2.. for multi-dimension topics (unused entries 0)
+    pub extra_truths: [i64; 3],
+    /// Lowest submit_order whose ordered-reveal turn is still pending
+    pub next_expected_reveal_order: u32,
+    /// Unix timestamp the current ordered-reveal turn began (0 until the
+    /// first reveal or skip; the reveal window's start until then)
+    pub reveal_turn_started_at: i64,
+    /// The vault PDA derived at creation; every instruction moving lamports
+    /// in or out of the vault checks the passed account against it
+    pub vault: Pubkey,
+    /// Truths failing the finalize sanity checks, as recorded by
+    /// `report_rejected_truth`; the topic authority's grounds for
+    /// `authority_replace_oracle`
+    pub failed_finalize_count: u32,
+    /// Price account every truth must match within
+    /// `options.price_tolerance_bps` (Pubkey::default() if unset)
+    pub price_feed: Pubkey,
+    /// The topic's `TopicConfig` PDA once `add_allowed_values` has created
+    /// it (Pubkey::default() if unset)
+    pub config: Pubkey,
+    /// Distinct participants holding at least one commitment; a participant
+    /// with several commitments counts once
+    pub participant_count: u32,
+    /// Submit order the next commitment gets. Unlike `commitment_count` a
+    /// cancel never lowers it, so live commitments never share an order
+    pub next_submit_order: u32,
+    /// Per-topic configuration
+    pub options: TopicOptions,
 }
 
 impl Topic {
     /// Account space calculation
-    /// discriminator(8) + pubkey(32)*2 + u64(8) + string(4+256) + string(4+32)
-    /// + i64(8)*3 + status(1) + u64(8) + u32(4)*2 + u64(8) + u8(1)*2
-    pub const MAX_SIZE: usize = 8 + 32 + 32 + 8 + (4 + 256) + (4 + 32)
-        + 8 + 8 + 1 + 8 + 8 + 4 + 4 + 8 + 1 + 1;
+    /// discriminator(8) + pubkey(32)*3 + u64(8) + status(1) + string(4+256)
+    /// + string(4+32) + i64(8)*3 + u64(8) + u32(4)*2 + u64(8) + u8(1)*2
+    /// + i64(8)*2 + seed(32) + u64(8) + i64(8)*2 + i128(16) + u64(8)*2 + i64(8)
+    /// + string(4+128) + u64(8)*3 + pubkey(32) + u64(8)*2 + i64(8)*3 + u32(4) + i64(8)
+    /// + pubkey(32) + u32(4) + pubkey(32)*2 + u32(4)*2 + options
+    pub const MAX_SIZE: usize = 8 + 32 + 32 + 32 + 8 + 1 + (4 + 256) + (4 + 32)
+        + 8 + 8 + 8 + 8 + 4 + 4 + 8 + 1 + 1
+        + 8 + 8 + 32 + 8 + 8 + 8 + 16 + 8 + 8 + 8
+        + (4 + 128) + 8 + 8 + 8 + 32 + 8 + 8 + 8 * 3 + 4 + 8
+        + 32 + 4 + 32 + 32 + 4 + 4 + TopicOptions::MAX_SIZE;
+
+    /// Predictions and truth values exclude i64::MIN and i64::MAX, whose
+    /// negation or absolute value overflows
+    pub fn is_value_in_range(value: i64) -> bool {
+        value > i64::MIN && value < i64::MAX
+    }
+
+    /// Phase boundaries are inclusive at the start and exclusive at the end:
+    /// commits are accepted while `now < commit_deadline`, reveals from
+    /// `commit_deadline` while `now < reveal_deadline`, and finalize from
+    /// `reveal_deadline` on. Each deadline second belongs to the later phase,
+    /// so adjacent phases neither overlap nor leave a gap.
+    pub fn is_commit_phase_over(&self, now: i64) -> bool {
+        now >= self.commit_deadline
+    }
+
+    /// Whether the reveal phase has ended, opening finalize; see
+    /// `is_commit_phase_over` for the boundary convention
+    pub fn is_reveal_phase_over(&self, now: i64) -> bool {
+        now >= self.reveal_deadline
+    }
+
+    /// Effective phase at `now`. Finalized and settled topics are past every
+    /// deadline-driven phase; otherwise the clock decides, whatever the
+    /// `Open`/`Revealing` status says.
+    pub fn effective_phase(&self, now: i64) -> Phase {
+        match self.status {
+            TopicStatus::Finalized => Phase::Finalized,
+            TopicStatus::Settled => Phase::Settled,
+            TopicStatus::Open | TopicStatus::Revealing => {
+                if !self.is_commit_phase_over(now) {
+                    Phase::Commit
+                } else if !self.is_reveal_phase_over(now) {
+                    Phase::Reveal
+                } else {
+                    Phase::AwaitingFinalize
+                }
+            }
+        }
+    }
+
+    /// Whether enough commitments are revealed, per
+    /// `options.early_finalize_threshold_bps`, to finalize before the
+    /// reveal deadline. Never true for a topic without commitments.
+    pub fn is_early_finalize_ready(&self) -> bool {
+        let threshold = self.options.early_finalize_threshold_bps as u64;
+        if threshold == 0 || self.commitment_count == 0 {
+            return false;
+        }
+        self.reveal_count as u64 * TopicOptions::MAX_BPS as u64 / self.commitment_count as u64
+            >= threshold
+    }
+
+    /// Whether enough distinct participants committed, per
+    /// `options.min_participants`, for the topic to be finalized
+    pub fn has_min_participants(&self) -> bool {
+        self.participant_count >= self.options.min_participants
+    }
+
+    /// When the current ordered-reveal turn began: the last reveal or skip
+    /// that advanced it, or the start of the reveal window before any
+    pub fn reveal_turn_started(&self) -> i64 {
+        self.reveal_turn_started_at
+            .max(self.commit_deadline)
+            .max(self.reveal_opened_at)
+    }
+
+    /// Lamports the vault should hold across all escrow buckets
+    pub fn escrowed_total(&self) -> Result<u64> {
+        self.escrowed_stake
+            .checked_add(self.escrowed_fees)
+            .and_then(|total| total.checked_add(self.escrowed_prize))
+            .ok_or(error!(crate::errors::WorthHubError::ArithmeticOverflow))
+    }
+
+    /// Counter invariants of a topic that has not been finalized: no more
+    /// reveals or participants than commitments, every commitment staking at least
+    /// `min_stake`, and all stake still escrowed (refunds only come after
+    /// finalize). A failure means another instruction corrupted the topic.
+    pub fn check_pre_finalize_invariants(&self) -> Result<()> {
+        let min_total_stake = self.min_stake as u128 * self.commitment_count as u128;
+        require!(
+            self.reveal_count <= self.commitment_count
+                && self.participant_count <= self.commitment_count
+                && self.total_stake as u128 >= min_total_stake
+                && self.escrowed_stake == self.total_stake,
+            crate::errors::WorthHubError::InvariantViolated
+        );
+        Ok(())
+    }
+
+    /// Whether `key` may call settle: the topic authority, the oracle,
+    /// or the dedicated settle authority when one is configured
+    pub fn is_settler(&self, key: &Pubkey) -> bool {
+        *key == self.authority
+            || *key == self.oracle_authority
+            || (self.settle_authority != Pubkey::default() && *key == self.settle_authority)
+    }
 }
 
 /// A single participant's commitment to a topic
@@ -79,10 +533,823 @@ pub struct Commitment {
     pub settled: bool,
     /// Bump seed for this commitment PDA
     pub bump: u8,
+    /// Scoring weight used for consensus (defaults to stake_amount)
+    pub weight: u64,
+    /// Prediction encrypted off-chain to an auditor key, stored verbatim
+    /// for audit and never used in scoring (all zeros if not supplied)
+    pub encrypted_prediction: [u8; 64],
+    /// Revealed interval bounds; both equal prediction_value for a point reveal
+    pub interval_low: i64,
+    pub interval_high: i64,
+    /// The participant's commit nonce this commitment was created under
+    /// (part of the PDA seeds)
+    pub nonce: u32,
+    /// Whether the participant proved their commitment but opted out of
+    /// scoring; the stake is returned at settle
+    pub abstained: bool,
+    /// Revealed predictions for dimensions 2.. of a multi-dimension topic
+    /// (unused entries 0)
+    pub extra_predictions: [i64; 3],
+    /// Zero-knowledge-friendly commitment to the prediction (e.g. a
+    /// Pedersen commitment), stored alongside the keccak hash for range
+    /// proofs via `verify_range_proof` (all zeros if not supplied)
+    pub aux_commitment: [u8; 32],
+    /// Who paid this account's rent; the rent goes back to them when the
+    /// commitment is closed
+    pub rent_payer: Pubkey,
+    /// Who supplied the stake (the participant, or a sponsoring rent payer);
+    /// cancel, void and refunds return the stake to them
+    pub stake_payer: Pubkey,
 }
 
 impl Commitment {
     /// discriminator(8) + pubkey(32)*2 + hash(32) + u64(8) + u32(4) + i64(8)
-    /// + bool(1) + salt(32) + bool(1) + u8(1)
-    pub const MAX_SIZE: usize = 8 + 32 + 32 + 32 + 8 + 4 + 8 + 1 + 32 + 1 + 1;
+    /// + bool(1) + salt(32) + bool(1) + u8(1) + u64(8) + encrypted(64) + i64(8)*2
+    /// + u32(4) + bool(1) + i64(8)*3 + aux(32) + pubkey(32)*2
+    pub const MAX_SIZE: usize = 8 + 32 + 32 + 32 + 8 + 4 + 8
+        + 1 + 32 + 1 + 1 + 8 + 64 + 8 + 8
+        + 4 + 1 + 8 * 3 + 32 + 32 + 32;
+
+    /// Upper bound on a commitment's scoring weight (1e18), keeping
+    /// prediction × weight products well inside i128
+    pub const MAX_WEIGHT: u64 = 1_000_000_000_000_000_000;
+
+    /// Re-derive the commitment PDA from the stored topic, participant and
+    /// nonce with the canonical bump, and require `key` to be that address
+    /// and the stored bump to be that bump. The stored bump is never
+    /// trusted on its own.
+    pub fn verify_address(&self, key: &Pubkey) -> Result<()> {
+        let (expected, bump) = Pubkey::find_program_address(
+            &[
+                b"commitment",
+                self.topic.as_ref(),
+                self.participant.as_ref(),
+                self.nonce.to_le_bytes().as_ref(),
+            ],
+            &crate::ID,
+        );
+        require!(
+            *key == expected && self.bump == bump,
+            crate::errors::WorthHubError::InvalidCommitmentAccount
+        );
+        Ok(())
+    }
+
+    /// Whether `cancel_commitment` may withdraw this commitment at `now`.
+    /// A revealed commitment is locked first of all: today only the Commit
+    /// phase allows cancels and only later phases allow reveals, so the
+    /// phase checks alone already keep the two apart, but the lock keeps
+    /// reveal-then-cancel impossible should a reveal path ever open earlier.
+    pub fn check_cancelable(&self, topic: &Topic, now: i64) -> Result<()> {
+        use crate::errors::WorthHubError;
+
+        require!(!self.revealed, WorthHubError::CannotCancelAfterReveal);
+        require!(
+            topic.status == TopicStatus::Open,
+            WorthHubError::TopicNotOpen
+        );
+        require!(
+            !topic.is_commit_phase_over(now),
+            WorthHubError::CommitPhaseEnded
+        );
+        Ok(())
+    }
+
+    /// Point prediction scored for a revealed interval: its midpoint,
+    /// rounded down. Summed in i128 so extreme bounds cannot overflow.
+    pub fn interval_midpoint(low: i64, high: i64) -> i64 {
+        (low as i128 + high as i128).div_euclid(2) as i64
+    }
+
+    /// Whether the commitment serialized in `data` has been paid out or
+    /// refunded, read through the full typed layout
+    pub fn is_settled(data: &[u8]) -> Result<bool> {
+        Ok(Commitment::try_deserialize(&mut &data[..])?.settled)
+    }
+
+    /// Mark the commitment serialized in `data` settled by rewriting the
+    /// whole account through its typed layout; returns the updated
+    /// commitment
+    pub fn mark_settled(data: &mut [u8]) -> Result<Commitment> {
+        let mut commitment = Commitment::try_deserialize(&mut &data[..])?;
+        commitment.settled = true;
+        commitment.try_serialize(&mut &mut data[..])?;
+        Ok(commitment)
+    }
+
+    /// keccak256(prediction_value_le || salt || participant_address)
+    pub fn compute_hash(prediction_value: i64, salt: &[u8; 32], participant: &Pubkey) -> [u8; 32] {
+        let mut hash_input = Vec::with_capacity(8 + 32 + 32);
+        hash_input.extend_from_slice(&prediction_value.to_le_bytes());
+        hash_input.extend_from_slice(salt);
+        hash_input.extend_from_slice(participant.as_ref());
+        keccak::hash(&hash_input).0
+    }
+
+    /// keccak256(low_le || high_le || salt || participant_address)
+    pub fn compute_interval_hash(
+        low: i64,
+        high: i64,
+        salt: &[u8; 32],
+        participant: &Pubkey,
+    ) -> [u8; 32] {
+        let mut hash_input = Vec::with_capacity(8 + 8 + 32 + 32);
+        hash_input.extend_from_slice(&low.to_le_bytes());
+        hash_input.extend_from_slice(&high.to_le_bytes());
+        hash_input.extend_from_slice(salt);
+        hash_input.extend_from_slice(participant.as_ref());
+        keccak::hash(&hash_input).0
+    }
+
+    /// The only proof the placeholder range-proof verifier accepts for
+    /// `[low, high]`: keccak256(aux_commitment || low_le || high_le). It
+    /// stands in for a zero-knowledge verifier and proves nothing about
+    /// the prediction itself.
+    pub fn placeholder_range_proof(aux_commitment: &[u8; 32], low: i64, high: i64) -> [u8; 32] {
+        let mut proof_input = Vec::with_capacity(32 + 8 + 8);
+        proof_input.extend_from_slice(aux_commitment);
+        proof_input.extend_from_slice(&low.to_le_bytes());
+        proof_input.extend_from_slice(&high.to_le_bytes());
+        keccak::hash(&proof_input).0
+    }
+
+    /// Leading salt bytes a `bind_salt` topic requires of the commitment
+    /// under `nonce`: keccak256(topic || participant_address || nonce_le)[..8].
+    /// Distinct per commitment, so a salt can never be reused.
+    pub fn salt_prefix(topic: &Pubkey, participant: &Pubkey, nonce: u32) -> [u8; 8] {
+        let mut hash_input = Vec::with_capacity(32 + 32 + 4);
+        hash_input.extend_from_slice(topic.as_ref());
+        hash_input.extend_from_slice(participant.as_ref());
+        hash_input.extend_from_slice(&nonce.to_le_bytes());
+        let mut prefix = [0u8; 8];
+        prefix.copy_from_slice(&keccak::hash(&hash_input).0[..8]);
+        prefix
+    }
+
+    /// keccak256(prediction_0_le || … || prediction_n_le || salt || participant_address)
+    pub fn compute_vector_hash(
+        predictions: &[i64],
+        salt: &[u8; 32],
+        participant: &Pubkey,
+    ) -> [u8; 32] {
+        let mut hash_input = Vec::with_capacity(8 * predictions.len() + 32 + 32);
+        for prediction in predictions {
+            hash_input.extend_from_slice(&prediction.to_le_bytes());
+        }
+        hash_input.extend_from_slice(salt);
+        hash_input.extend_from_slice(participant.as_ref());
+        keccak::hash(&hash_input).0
+    }
+}
+
+/// Per-participant, per-topic state. The commit nonce is part of the
+/// commitment PDA seeds and advances on every commit, so a participant may
+/// hold several commitments and a canceled commitment's address is never
+/// reused.
+#[account]
+pub struct ParticipantState {
+    /// The topic this state belongs to
+    pub topic: Pubkey,
+    /// The participant this state belongs to
+    pub participant: Pubkey,
+    /// Nonce for the participant's next commitment (commitments made so far)
+    pub commit_nonce: u32,
+    /// Commitments the participant currently holds (made minus canceled);
+    /// the participant counts toward `Topic::participant_count` while it
+    /// is above 0
+    pub open_commitments: u32,
+    /// Bump seed for this PDA
+    pub bump: u8,
+    /// Who paid this account's rent; the rent goes back to them when the
+    /// topic is voided
+    pub rent_payer: Pubkey,
+}
+
+impl ParticipantState {
+    /// discriminator(8) + pubkey(32)*2 + u32(4)*2 + u8(1) + pubkey(32)
+    pub const MAX_SIZE: usize = 8 + 32 + 32 + 4 + 4 + 1 + 32;
+}
+
+/// One settled outcome in a symbol's `TruthHistory`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct TruthRecord {
+    pub topic_id: u64,
+    pub truth_value: i64,
+    /// Unix timestamp of the settle
+    pub settled_at: i64,
+}
+
+/// Ring buffer of a symbol's most recent settled truths, appended to by
+/// every settle of a topic with that symbol. Allocated at full capacity on
+/// the first settle, so appending never reallocates.
+#[account]
+#[derive(Default)]
+pub struct TruthHistory {
+    /// The symbol whose topics this history records
+    pub symbol: String,
+    /// Slot the next record is written to once the buffer is full
+    pub head: u16,
+    /// Records appended over the account's lifetime
+    pub total_recorded: u64,
+    /// Up to CAPACITY records; oldest at `head` once full
+    pub records: Vec<TruthRecord>,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+impl TruthHistory {
+    /// Most records kept per symbol
+    pub const CAPACITY: usize = 16;
+
+    /// discriminator(8) + string(4+32) + u16(2) + u64(8) + vec(4 + record(24)*16) + u8(1)
+    pub const MAX_SIZE: usize = 8 + (4 + 32) + 2 + 8 + (4 + 24 * Self::CAPACITY) + 1;
+
+    /// Append `record`, overwriting the oldest once the buffer is full
+    pub fn push(&mut self, record: TruthRecord) {
+        if self.records.len() < Self::CAPACITY {
+            self.records.push(record);
+        } else {
+            self.records[self.head as usize] = record;
+            self.head = ((self.head as usize + 1) % Self::CAPACITY) as u16;
+        }
+        self.total_recorded += 1;
+    }
+
+    /// Records held, oldest first
+    pub fn in_order(&self) -> Vec<TruthRecord> {
+        let (newer, older) = self.records.split_at(self.head as usize);
+        older.iter().chain(newer).copied().collect()
+    }
+}
+
+/// One participant's result in a `SettlementRecord`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct SettlementOutcome {
+    pub participant: Pubkey,
+    /// Revealed prediction (0 for non-revealers and abstainers)
+    pub prediction: i64,
+    /// Score from the settle math, summed across dimensions
+    pub score: u128,
+    /// Lamports paid at settle, reveal bond refund included
+    pub payout: u64,
+    pub revealed: bool,
+    pub abstained: bool,
+}
+
+/// Per-topic audit trail of a settle: every settled commitment's
+/// prediction, score and payout. Written once by settle and kept however
+/// the commitments are closed later. Created header-only and grown to the
+/// participant count when settle writes it.
+#[account]
+#[derive(Default)]
+pub struct SettlementRecord {
+    pub topic: Pubkey,
+    pub topic_id: u64,
+    pub truth_value: i64,
+    pub consensus: i128,
+    /// Unix timestamp of the settle
+    pub settled_at: i64,
+    /// One entry per settled commitment, in remaining-accounts order
+    pub outcomes: Vec<SettlementOutcome>,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+impl SettlementRecord {
+    /// pubkey(32) + i64(8) + u128(16) + u64(8) + bool(1)*2
+    pub const OUTCOME_SIZE: usize = 32 + 8 + 16 + 8 + 1 + 1;
+
+    /// discriminator(8) + pubkey(32) + u64(8) + i64(8) + i128(16) + i64(8)
+    /// + vec(4 + outcome(66)*n) + u8(1)
+    pub const fn space(outcomes: usize) -> usize {
+        8 + 32 + 8 + 8 + 16 + 8 + (4 + Self::OUTCOME_SIZE * outcomes) + 1
+    }
+}
+
+/// Rarely-read configuration of a topic, kept in a side PDA so that the
+/// Topic account every instruction loads stays small however long these
+/// vectors grow. Seeds: `[b"topic_config", topic]`.
+#[account]
+#[derive(Default)]
+pub struct TopicConfig {
+    pub topic: Pubkey,
+    /// If non-empty, reveals must land on one of these values
+    /// (discrete-outcome markets), at most MAX_ALLOWED_VALUES entries
+    pub allowed_values: Vec<i64>,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+impl TopicConfig {
+    /// Maximum number of entries in `allowed_values`
+    pub const MAX_ALLOWED_VALUES: usize = 512;
+
+    /// discriminator(8) + pubkey(32) + vec(4 + 8*512) + u8(1)
+    pub const MAX_SIZE: usize = 8 + 32 + (4 + 8 * Self::MAX_ALLOWED_VALUES) + 1;
+
+    /// Whether `value` is on the topic's allowed grid, if it has one
+    pub fn allows(&self, value: i64) -> bool {
+        self.allowed_values.is_empty() || self.allowed_values.contains(&value)
+    }
+}
+
+/// A participant's running record across topics: every settle the entry is
+/// passed to credits the participant's settled commitments
+#[account]
+#[derive(Default)]
+pub struct Leaderboard {
+    pub participant: Pubkey,
+    /// Settled commitments credited
+    pub settled_count: u32,
+    /// Credited commitments paid more than their stake
+    pub win_count: u32,
+    /// Lamports paid at settle, summed
+    pub cumulative_payout: u64,
+    /// Settle scores, summed (saturating)
+    pub total_score: u128,
+    /// Id of the topic last credited
+    pub last_topic_id: u64,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+impl Leaderboard {
+    /// discriminator(8) + pubkey(32) + u32(4)*2 + u64(8) + u128(16) + u64(8) + u8(1)
+    pub const MAX_SIZE: usize = 8 + 32 + 4 + 4 + 8 + 16 + 8 + 1;
+
+    /// Add one settled commitment of `stake` lamports on `topic_id`
+    pub fn credit(&mut self, topic_id: u64, outcome: &SettlementOutcome, stake: u64) {
+        self.settled_count = self.settled_count.saturating_add(1);
+        if outcome.payout > stake {
+            self.win_count = self.win_count.saturating_add(1);
+        }
+        self.cumulative_payout = self.cumulative_payout.saturating_add(outcome.payout);
+        self.total_score = self.total_score.saturating_add(outcome.score);
+        self.last_topic_id = topic_id;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn topic_with_deadlines(commit_deadline: i64, reveal_deadline: i64) -> Topic {
+        Topic {
+            commit_deadline,
+            reveal_deadline,
+            ..Topic::default()
+        }
+    }
+
+    fn test_commitment() -> Commitment {
+        Commitment {
+            topic: Pubkey::new_unique(),
+            participant: Pubkey::new_unique(),
+            commitment_hash: [0; 32],
+            stake_amount: 1_000,
+            submit_order: 0,
+            prediction_value: 0,
+            revealed: false,
+            salt: [0; 32],
+            settled: false,
+            bump: 0,
+            weight: 1_000,
+            encrypted_prediction: [0; 64],
+            interval_low: 0,
+            interval_high: 0,
+            nonce: 0,
+            abstained: false,
+            extra_predictions: [0; 3],
+            aux_commitment: [0; 32],
+            rent_payer: Pubkey::default(),
+            stake_payer: Pubkey::default(),
+        }
+    }
+
+    #[test]
+    fn reveal_deadline_second_belongs_to_finalize() {
+        let topic = topic_with_deadlines(1_000, 2_000);
+        // reveal_deadline - 1: last second to reveal, too early to finalize
+        assert!(!topic.is_reveal_phase_over(1_999));
+        // reveal_deadline: reveals rejected, finalize accepted
+        assert!(topic.is_reveal_phase_over(2_000));
+        // reveal_deadline + 1
+        assert!(topic.is_reveal_phase_over(2_001));
+    }
+
+    #[test]
+    fn commit_deadline_second_belongs_to_reveal() {
+        let topic = topic_with_deadlines(1_000, 2_000);
+        assert!(!topic.is_commit_phase_over(999));
+        assert!(topic.is_commit_phase_over(1_000));
+        assert!(topic.is_commit_phase_over(1_001));
+    }
+
+    #[test]
+    fn open_topic_past_commit_deadline_is_in_reveal_phase() {
+        // Status stays Open until someone reveals or calls open_reveal
+        let topic = topic_with_deadlines(1_000, 2_000);
+        assert_eq!(topic.status, TopicStatus::Open);
+        assert_eq!(topic.effective_phase(999), Phase::Commit);
+        assert_eq!(topic.effective_phase(1_000), Phase::Reveal);
+        assert_eq!(topic.effective_phase(1_999), Phase::Reveal);
+        assert_eq!(topic.effective_phase(2_000), Phase::AwaitingFinalize);
+    }
+
+    #[test]
+    fn effective_phase_follows_status_once_finalized() {
+        let mut topic = topic_with_deadlines(1_000, 2_000);
+        topic.status = TopicStatus::Revealing;
+        assert_eq!(topic.effective_phase(1_500), Phase::Reveal);
+        assert_eq!(topic.effective_phase(2_500), Phase::AwaitingFinalize);
+        // Early finalize: before the reveal deadline, yet finalized
+        topic.status = TopicStatus::Finalized;
+        assert_eq!(topic.effective_phase(1_500), Phase::Finalized);
+        topic.status = TopicStatus::Settled;
+        assert_eq!(topic.effective_phase(500), Phase::Settled);
+    }
+
+    #[test]
+    fn finalize_needs_min_participants() {
+        let mut topic = topic_with_deadlines(1_000, 2_000);
+        // Disabled by default, even on an empty topic
+        assert!(topic.has_min_participants());
+
+        topic.options.min_participants = 3;
+        topic.commitment_count = 4;
+        topic.participant_count = 2;
+        assert!(!topic.has_min_participants());
+        topic.participant_count = 3;
+        assert!(topic.has_min_participants());
+        topic.participant_count = 4;
+        assert!(topic.has_min_participants());
+
+        // More participants than commitments is a corrupted counter
+        topic.participant_count = 5;
+        assert!(topic.check_pre_finalize_invariants().is_err());
+    }
+
+    #[test]
+    fn early_finalize_needs_threshold_share_of_reveals() {
+        let mut topic = topic_with_deadlines(1_000, 2_000);
+        topic.options.early_finalize_threshold_bps = 6_000;
+        // Empty topic: no division by zero, never ready
+        assert!(!topic.is_early_finalize_ready());
+
+        topic.commitment_count = 5;
+        topic.reveal_count = 2;
+        assert!(!topic.is_early_finalize_ready());
+        topic.reveal_count = 3;
+        assert!(topic.is_early_finalize_ready());
+
+        topic.options.early_finalize_threshold_bps = 0;
+        topic.reveal_count = 5;
+        assert!(!topic.is_early_finalize_ready());
+    }
+
+    #[test]
+    fn escrowed_total_sums_buckets_and_reports_overflow() {
+        let mut topic = Topic {
+            escrowed_stake: 90,
+            escrowed_fees: 7,
+            escrowed_prize: 3,
+            ..Topic::default()
+        };
+        assert_eq!(topic.escrowed_total().unwrap(), 100);
+        topic.escrowed_prize = u64::MAX;
+        assert!(topic.escrowed_total().is_err());
+    }
+
+    #[test]
+    fn prediction_bounds_are_inclusive_and_optional() {
+        let mut options = TopicOptions::default();
+        assert!(options.within_bounds(i64::MIN + 1) && options.within_bounds(i64::MAX - 1));
+
+        options.min_prediction = Some(50);
+        assert!(!options.within_bounds(49) && options.within_bounds(50));
+        assert!(options.within_bounds(i64::MAX - 1));
+
+        options.max_prediction = Some(150);
+        assert!(options.within_bounds(150) && !options.within_bounds(151));
+    }
+
+    #[test]
+    fn commitment_hash_binds_every_input() {
+        let salt = [7u8; 32];
+        let participant = Pubkey::new_unique();
+        let hash = Commitment::compute_hash(100, &salt, &participant);
+        assert_eq!(hash, Commitment::compute_hash(100, &salt, &participant));
+        assert_ne!(hash, Commitment::compute_hash(101, &salt, &participant));
+        assert_ne!(
+            hash,
+            Commitment::compute_hash(100, &[8u8; 32], &participant)
+        );
+        assert_ne!(
+            hash,
+            Commitment::compute_hash(100, &salt, &Pubkey::new_unique())
+        );
+    }
+
+    #[test]
+    fn status_sits_at_the_documented_offset() {
+        let topic = Topic {
+            description: "a long description".to_string(),
+            symbol: "BTC-USD".to_string(),
+            status: TopicStatus::Finalized,
+            ..Topic::default()
+        };
+        let mut data = Vec::new();
+        topic.try_serialize(&mut data).unwrap();
+        assert_eq!(data[TOPIC_STATUS_OFFSET], TopicStatus::Finalized as u8);
+
+        let settled = Topic {
+            status: TopicStatus::Settled,
+            ..topic
+        };
+        let mut data = Vec::new();
+        settled.try_serialize(&mut data).unwrap();
+        assert_eq!(data[TOPIC_STATUS_OFFSET], TopicStatus::Settled as u8);
+    }
+
+    #[test]
+    fn first_reveal_turn_starts_with_the_reveal_window() {
+        let mut topic = topic_with_deadlines(1_000, 2_000);
+        assert_eq!(topic.reveal_turn_started(), 1_000);
+        topic.reveal_opened_at = 1_200;
+        assert_eq!(topic.reveal_turn_started(), 1_200);
+        topic.reveal_turn_started_at = 1_500;
+        assert_eq!(topic.reveal_turn_started(), 1_500);
+    }
+
+    #[test]
+    fn salt_prefix_differs_per_commitment() {
+        let (topic, participant) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let prefix = Commitment::salt_prefix(&topic, &participant, 0);
+        assert_eq!(prefix, Commitment::salt_prefix(&topic, &participant, 0));
+        assert_ne!(prefix, Commitment::salt_prefix(&topic, &participant, 1));
+        assert_ne!(
+            prefix,
+            Commitment::salt_prefix(&topic, &Pubkey::new_unique(), 0)
+        );
+        assert_ne!(
+            prefix,
+            Commitment::salt_prefix(&Pubkey::new_unique(), &participant, 0)
+        );
+    }
+
+    #[test]
+    fn truth_history_wraps_oldest_first() {
+        let record = |topic_id: u64| TruthRecord {
+            topic_id,
+            truth_value: topic_id as i64 * 10,
+            settled_at: 0,
+        };
+        let mut history = TruthHistory::default();
+        for id in 0..3 {
+            history.push(record(id));
+        }
+        let ids = |h: &TruthHistory| h.in_order().iter().map(|r| r.topic_id).collect::<Vec<_>>();
+        assert_eq!(ids(&history), vec![0, 1, 2]);
+
+        let capacity = TruthHistory::CAPACITY as u64;
+        for id in 3..capacity + 5 {
+            history.push(record(id));
+        }
+        assert_eq!(history.records.len(), TruthHistory::CAPACITY);
+        assert_eq!(history.total_recorded, capacity + 5);
+        assert_eq!(ids(&history), (5..capacity + 5).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn settlement_record_space_fits_its_outcomes() {
+        for n in [0, 1, 8] {
+            let record = SettlementRecord {
+                outcomes: vec![SettlementOutcome::default(); n],
+                ..SettlementRecord::default()
+            };
+            let mut data = Vec::new();
+            record.try_serialize(&mut data).unwrap();
+            assert_eq!(data.len(), SettlementRecord::space(n));
+        }
+    }
+
+    #[test]
+    fn leaderboard_accumulates_credits() {
+        let mut leaderboard = Leaderboard::default();
+        let outcome = |payout, score| SettlementOutcome {
+            payout,
+            score,
+            ..SettlementOutcome::default()
+        };
+        leaderboard.credit(7, &outcome(150, 40), 100);
+        leaderboard.credit(8, &outcome(60, 5), 100);
+        leaderboard.credit(9, &outcome(100, u128::MAX), 100);
+        assert_eq!(leaderboard.settled_count, 3);
+        assert_eq!(leaderboard.win_count, 1);
+        assert_eq!(leaderboard.cumulative_payout, 310);
+        assert_eq!(leaderboard.total_score, u128::MAX);
+        assert_eq!(leaderboard.last_topic_id, 9);
+
+        let mut data = Vec::new();
+        leaderboard.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), Leaderboard::MAX_SIZE);
+    }
+
+    #[test]
+    fn pre_finalize_invariants_catch_corrupt_counters() {
+        let topic = Topic {
+            commitment_count: 3,
+            reveal_count: 2,
+            min_stake: 10,
+            total_stake: 45,
+            escrowed_stake: 45,
+            ..Topic::default()
+        };
+        assert!(topic.check_pre_finalize_invariants().is_ok());
+
+        let corrupt = [
+            Topic {
+                reveal_count: 4,
+                ..topic.clone()
+            },
+            Topic {
+                total_stake: 29,
+                escrowed_stake: 29,
+                ..topic.clone()
+            },
+            Topic {
+                escrowed_stake: 40,
+                ..topic.clone()
+            },
+        ];
+        for topic in corrupt {
+            assert_eq!(
+                topic.check_pre_finalize_invariants().unwrap_err(),
+                crate::errors::WorthHubError::InvariantViolated.into()
+            );
+        }
+    }
+
+    #[test]
+    fn full_topic_config_fits_a_cpi_created_account() {
+        let config = TopicConfig {
+            allowed_values: (0..TopicConfig::MAX_ALLOWED_VALUES as i64).collect(),
+            ..TopicConfig::default()
+        };
+        let mut data = Vec::new();
+        config.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), TopicConfig::MAX_SIZE);
+        const {
+            assert!(
+                TopicConfig::MAX_SIZE
+                    <= anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE
+            )
+        };
+        assert!(config.allows(0) && config.allows(511) && !config.allows(512));
+        assert!(TopicConfig::default().allows(i64::MAX - 1));
+
+        // The hot account keeps its fixed bound whatever the config holds
+        let topic = Topic {
+            description: "d".repeat(256),
+            symbol: "S".repeat(32),
+            metadata_uri: "u".repeat(128),
+            config: Pubkey::new_unique(),
+            ..Topic::default()
+        };
+        let mut data = Vec::new();
+        topic.try_serialize(&mut data).unwrap();
+        assert!(data.len() <= Topic::MAX_SIZE);
+    }
+
+    #[test]
+    fn placeholder_range_proof_binds_aux_commitment_and_range() {
+        let aux = [4u8; 32];
+        let proof = Commitment::placeholder_range_proof(&aux, 90, 110);
+        assert_eq!(proof, Commitment::placeholder_range_proof(&aux, 90, 110));
+        assert_ne!(
+            proof,
+            Commitment::placeholder_range_proof(&[5u8; 32], 90, 110)
+        );
+        assert_ne!(proof, Commitment::placeholder_range_proof(&aux, 90, 111));
+        assert_ne!(proof, Commitment::placeholder_range_proof(&aux, 110, 90));
+    }
+
+    #[test]
+    fn commitment_address_is_rederived_with_the_canonical_bump() {
+        let mut commitment = Commitment {
+            nonce: 3,
+            ..test_commitment()
+        };
+        let (key, bump) = Pubkey::find_program_address(
+            &[
+                b"commitment",
+                commitment.topic.as_ref(),
+                commitment.participant.as_ref(),
+                3u32.to_le_bytes().as_ref(),
+            ],
+            &crate::ID,
+        );
+        commitment.bump = bump;
+        assert!(commitment.verify_address(&key).is_ok());
+        assert!(commitment.verify_address(&Pubkey::new_unique()).is_err());
+
+        let tampered = Commitment {
+            bump: bump.wrapping_sub(1),
+            ..commitment.clone()
+        };
+        assert_eq!(
+            tampered.verify_address(&key).unwrap_err(),
+            crate::errors::WorthHubError::InvalidCommitmentAccount.into()
+        );
+        let other_nonce = Commitment {
+            nonce: 4,
+            ..commitment
+        };
+        assert!(other_nonce.verify_address(&key).is_err());
+    }
+
+    #[test]
+    fn settled_flag_reads_and_writes_through_the_typed_layout() {
+        // Non-default neighbours, so a stray write around the flag shows up
+        let mut commitment = Commitment {
+            commitment_hash: [3; 32],
+            submit_order: 4,
+            prediction_value: 150,
+            revealed: true,
+            salt: [9; 32],
+            bump: 254,
+            encrypted_prediction: [7; 64],
+            interval_low: 100,
+            interval_high: 200,
+            nonce: 2,
+            extra_predictions: [-5, 6, 0],
+            ..test_commitment()
+        };
+        let mut data = vec![0u8; Commitment::MAX_SIZE];
+        commitment.try_serialize(&mut &mut data[..]).unwrap();
+        assert!(!Commitment::is_settled(&data).unwrap());
+
+        let marked = Commitment::mark_settled(&mut data).unwrap();
+        assert!(marked.settled);
+        assert!(Commitment::is_settled(&data).unwrap());
+        let reread = Commitment::try_deserialize(&mut &data[..]).unwrap();
+        assert!(reread.settled);
+
+        // Every other field is left as it was
+        commitment.settled = true;
+        let mut expected = vec![0u8; Commitment::MAX_SIZE];
+        commitment.try_serialize(&mut &mut expected[..]).unwrap();
+        assert_eq!(data, expected);
+
+        assert!(Commitment::is_settled(&[0u8; Commitment::MAX_SIZE]).is_err());
+        assert!(Commitment::mark_settled(&mut [0u8; Commitment::MAX_SIZE]).is_err());
+    }
+
+    #[test]
+    fn interval_midpoint_survives_extreme_bounds() {
+        assert_eq!(Commitment::interval_midpoint(100, 200), 150);
+        assert_eq!(Commitment::interval_midpoint(7, 7), 7);
+        assert_eq!(Commitment::interval_midpoint(-3, 4), 0);
+        assert_eq!(Commitment::interval_midpoint(-4, 3), -1);
+        assert_eq!(Commitment::interval_midpoint(-(1 << 62), (1 << 62) + 1), 0);
+        assert_eq!(Commitment::interval_midpoint(i64::MIN, i64::MAX), -1);
+        assert_eq!(
+            Commitment::interval_midpoint(i64::MAX - 1, i64::MAX),
+            i64::MAX - 1
+        );
+        assert_eq!(
+            Commitment::interval_midpoint(i64::MIN, i64::MIN + 1),
+            i64::MIN
+        );
+    }
+
+    #[test]
+    fn revealed_commitments_are_never_cancelable() {
+        use crate::errors::WorthHubError;
+        let mut commitment = test_commitment();
+        let mut topic = topic_with_deadlines(100, 200);
+        assert!(commitment.check_cancelable(&topic, 99).is_ok());
+        assert_eq!(
+            commitment.check_cancelable(&topic, 100).unwrap_err(),
+            WorthHubError::CommitPhaseEnded.into()
+        );
+
+        // Unreachable through today's phases, yet still locked mid-commit
+        commitment.revealed = true;
+        assert_eq!(
+            commitment.check_cancelable(&topic, 99).unwrap_err(),
+            WorthHubError::CannotCancelAfterReveal.into()
+        );
+        // And the lock is what a late cancel of a revealed commitment reports
+        topic.status = TopicStatus::Revealing;
+        assert_eq!(
+            commitment.check_cancelable(&topic, 150).unwrap_err(),
+            WorthHubError::CannotCancelAfterReveal.into()
+        );
+        commitment.revealed = false;
+        assert_eq!(
+            commitment.check_cancelable(&topic, 150).unwrap_err(),
+            WorthHubError::TopicNotOpen.into()
+        );
+    }
 }
@@ -12,7 +12,6 @@ import yahooFinance from "yahoo-finance2";
 import {
   WorthHubClient,
   findTopicPDA,
-  findCommitmentPDA,
 } from "./client";
 import {
   TopicInfo,
@@ -107,16 +106,22 @@ export class Oracle {
   // ─── On-chain Interactions ────────────────────────────────────────
 
   /**
-   * Submit truth value to finalize a topic
+   * Submit truth value to finalize a topic, optionally with a confidence
+   * band [low, high] inside which predictions count as fully accurate
    */
-  async submitTruth(topicId: number, truthValue: number): Promise<string> {
+  async submitTruth(
+    topicId: number,
+    truthValue: number,
+    band?: [number, number]
+  ): Promise<string> {
     const fixedValue = toFixedPoint(truthValue);
 
     try {
       const tx = await this.client.finalize(
         this.keypair,
         topicId,
-        fixedValue
+        fixedValue,
+        band && [toFixedPoint(band[0]), toFixedPoint(band[1])]
       );
       console.log(
         `[Oracle] Finalized topic ${topicId}: truth=${truthValue} (fixed=${fixedValue}), tx=${tx}`
@@ -139,10 +144,12 @@ export class Oracle {
     participants: PublicKey[]
   ): Promise<string> {
     const [topicPDA] = findTopicPDA(topicId);
-    const pairs: [PublicKey, PublicKey][] = participants.map((p) => [
-      findCommitmentPDA(topicPDA, p)[0],
-      p,
-    ]);
+    const pairs: [PublicKey, PublicKey][] = [];
+    for (const p of participants) {
+      for (const commitmentPDA of await this.client.commitmentPDAs(topicPDA, p)) {
+        pairs.push([commitmentPDA, p]);
+      }
+    }
 
     try {
       const tx = await this.client.settle(this.keypair, topicId, pairs);
@@ -14,7 +14,13 @@ import {
 } from "@solana/web3.js";
 import jsSha3 from "js-sha3";
 const { keccak_256 } = jsSha3;
-import { TopicInfo, CommitmentInfo, TopicStatus } from "./protocol";
+import {
+  TopicInfo,
+  CommitmentInfo,
+  TopicStatus,
+  DEFAULT_REFUND_TIMEOUT,
+  DEFAULT_SELF_REFUND_TIMEOUT,
+} from "./protocol";
 
 // IDL will be generated by `anchor build` — we use a type-safe approach
 // For now, we define the program ID and use dynamic IDL loading
@@ -23,6 +29,12 @@ export const PROGRAM_ID = new PublicKey(
   "8qXNZGRTwYeAw3fdPsaqJ3cq5ieyZWtxrXTZizmuZFeQ"
 );
 
+/**
+ * Byte offset of the status field in topic account data, for memcmp filters
+ * (mirrors the program's TOPIC_STATUS_OFFSET)
+ */
+export const TOPIC_STATUS_OFFSET = 8 + 32 * 3 + 8;
+
 /**
  * Derive the PDA for a topic account
  */
@@ -50,18 +62,79 @@ export function findVaultPDA(topicPubkey: PublicKey): [PublicKey, number] {
  */
 export function findCommitmentPDA(
   topicPubkey: PublicKey,
-  participant: PublicKey
+  participant: PublicKey,
+  nonce = 0
 ): [PublicKey, number] {
+  const nonceBuf = Buffer.alloc(4);
+  nonceBuf.writeUInt32LE(nonce);
   return PublicKey.findProgramAddressSync(
     [
       Buffer.from("commitment"),
       topicPubkey.toBuffer(),
       participant.toBuffer(),
+      nonceBuf,
+    ],
+    PROGRAM_ID
+  );
+}
+
+/**
+ * Derive the per-participant state PDA holding the commit nonce
+ */
+export function findParticipantStatePDA(
+  topicPubkey: PublicKey,
+  participant: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("participant"),
+      topicPubkey.toBuffer(),
+      participant.toBuffer(),
     ],
     PROGRAM_ID
   );
 }
 
+/**
+ * Derive the PDA holding a symbol's recent settled truths
+ */
+export function findTruthHistoryPDA(symbol: string): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("truth_history"), Buffer.from(symbol)],
+    PROGRAM_ID
+  );
+}
+
+/**
+ * Derive the PDA of the record a topic's settle writes
+ */
+export function findSettlementRecordPDA(topicPDA: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("settlement"), topicPDA.toBuffer()],
+    PROGRAM_ID
+  );
+}
+
+/**
+ * Derive a participant's leaderboard entry PDA
+ */
+export function findLeaderboardPDA(participant: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("leaderboard"), participant.toBuffer()],
+    PROGRAM_ID
+  );
+}
+
+/**
+ * Derive a topic's config PDA, holding its allowed prediction grid
+ */
+export function findTopicConfigPDA(topicPDA: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("topic_config"), topicPDA.toBuffer()],
+    PROGRAM_ID
+  );
+}
+
 /**
  * Compute the commitment hash: keccak256(prediction_value || salt || participant_address)
  */
@@ -78,6 +151,42 @@ export function computeCommitmentHash(
   return Buffer.from(hash);
 }
 
+/**
+ * Compute a multi-dimension commitment hash:
+ * keccak256(prediction_1 || ... || prediction_n || salt || participant_address)
+ */
+export function computeVectorHash(
+  predictions: number[],
+  salt: Buffer,
+  participant: PublicKey
+): Buffer {
+  const offset = 8 * predictions.length;
+  const buf = Buffer.alloc(offset + 32 + 32);
+  predictions.forEach((p, i) => buf.writeBigInt64LE(BigInt(p), 8 * i));
+  salt.copy(buf, offset);
+  participant.toBuffer().copy(buf, offset + 32);
+  const hash = keccak_256.arrayBuffer(buf);
+  return Buffer.from(hash);
+}
+
+/**
+ * Compute an interval commitment hash: keccak256(low || high || salt || participant_address)
+ */
+export function computeIntervalHash(
+  low: number,
+  high: number,
+  salt: Buffer,
+  participant: PublicKey
+): Buffer {
+  const buf = Buffer.alloc(8 + 8 + 32 + 32);
+  buf.writeBigInt64LE(BigInt(low), 0);
+  buf.writeBigInt64LE(BigInt(high), 8);
+  salt.copy(buf, 16);
+  participant.toBuffer().copy(buf, 48);
+  const hash = keccak_256.arrayBuffer(buf);
+  return Buffer.from(hash);
+}
+
 /**
  * Generate a random 32-byte salt
  */
@@ -85,6 +194,147 @@ export function generateSalt(): Buffer {
   return Buffer.from(Keypair.generate().secretKey.slice(0, 32));
 }
 
+/**
+ * Prefix `salt` with the bytes a `bindSalt` topic requires of the commitment
+ * under `nonce`: keccak256(topic || participant || nonce_le)[..8]
+ */
+export function bindSalt(
+  topicPDA: PublicKey,
+  participant: PublicKey,
+  nonce: number,
+  salt: Buffer = generateSalt()
+): Buffer {
+  const buf = Buffer.alloc(32 + 32 + 4);
+  topicPDA.toBuffer().copy(buf, 0);
+  participant.toBuffer().copy(buf, 32);
+  buf.writeUInt32LE(nonce, 64);
+  const bound = Buffer.from(salt);
+  Buffer.from(keccak_256.arrayBuffer(buf)).copy(bound, 0, 0, 8);
+  return bound;
+}
+
+/**
+ * Per-topic options passed to `create_topic` (mirrors the on-chain TopicOptions)
+ */
+export interface TopicOptions {
+  refundTimeout: anchor.BN;
+  manualRevealGate: boolean;
+  rewardMode:
+    | { proportional: {} }
+    | { winnerTakeAll: {} }
+    | { underdogBoost: {} }
+    | { intervalScoring: {} }
+    | { diversityBonus: {} }
+    | { closestWins: {} };
+  tiebreak: { earliestOrder: {} } | { latestOrder: {} } | { random: {} };
+  revealRebateBps: number;
+  keeperRewardBps: number;
+  publicSettle: boolean;
+  /** Score exponents in basis points; 10_000 is neutral */
+  accuracyWeightBps: number;
+  boldnessWeightBps: number;
+  /** Per-participant cap on the score-weighted pool in basis points; 0 is uncapped */
+  maxPayoutBps: number;
+  /** Revealed share of commitments, in basis points, allowing an early finalize; 0 disables */
+  earlyFinalizeThresholdBps: number;
+  /** If false, every commit gets the same time-decay factor regardless of order */
+  timeDecayEnabled: boolean;
+  /** If true, revealed participants get at least their stake back whenever the vault covers it */
+  guaranteeStakeReturn: boolean;
+  /** Inclusive bounds on predictions and on the finalized truth; null leaves a side open */
+  minPrediction: anchor.BN | null;
+  maxPrediction: anchor.BN | null;
+  /** Lamports each reveal posts to the settle fund, returned at settle less the keeper's share */
+  revealBond: anchor.BN;
+  /** Share of each reveal bond, in basis points, paid to the settler */
+  revealBondKeeperBps: number;
+  /** Smallest |consensus| percentage deviations are normalized by; 0 disables */
+  consensusFloor: anchor.BN;
+  /** Share of a wrong-direction revealer's stake, in basis points, forfeited to the loser pool */
+  wrongDirectionPenaltyBps: number;
+  /** If true, escrow left after payouts and the keeper goes back to participants instead of the authority */
+  leftoverToParticipants: boolean;
+  /** If true, only the revealed fraction of the loser pool is distributed; the rest returns pro-rata */
+  poolReleaseByRevealRatio: boolean;
+  /** Number of truth dimensions predicted and scored, 1 to 4 */
+  dimensions: number;
+  /** If true, commitments must be revealed in commit order */
+  orderedReveal: boolean;
+  /** Seconds an ordered-reveal turn lasts before anyone may skip it */
+  revealTurnSeconds: number;
+  /** If true, each salt must carry its commitment's `bindSalt` prefix */
+  bindSalt: boolean;
+  /** If true, non-revealers forfeit a share of their stake that grows with their commit order */
+  forfeitByOrder: boolean;
+  /** Distance from the truth within which a prediction counts as perfectly accurate */
+  accuracyTolerance: anchor.BN;
+  /** Largest truth distance from the topic's price feed, in basis points of the price */
+  priceToleranceBps: number;
+  /** Pyth feed id the topic's price feed account must hold */
+  priceFeedId: number[];
+  /** Oldest, in seconds before finalize, the feed's price may be (required with a price feed) */
+  priceMaxAge: number;
+  /** If true, an early finalize is a no-op returning the seconds until the reveal deadline */
+  softFinalize: boolean;
+  /** Reveal window required per SOL of minimum stake, in seconds (0 disables) */
+  revealSecondsPerSol: number;
+  /** If false, settle returns every stake and only records scores (pure prediction mode) */
+  rewardEnabled: boolean;
+  /** Loser pool handling when every revealed prediction equals the consensus */
+  unanimousPolicy: { refund: {} } | { sweep: {} };
+  /** Seconds after finalization before a revealed participant may self-refund an unsettled topic */
+  selfRefundTimeout: anchor.BN;
+  /** Lamports paid to each revealer at settle out of the protocol fee */
+  revealRebate: anchor.BN;
+  /** Fewest distinct participants needed to finalize (0 disables) */
+  minParticipants: number;
+}
+
+/**
+ * Default per-topic options
+ */
+export function defaultTopicOptions(): TopicOptions {
+  return {
+    refundTimeout: new anchor.BN(DEFAULT_REFUND_TIMEOUT),
+    manualRevealGate: false,
+    rewardMode: { proportional: {} },
+    tiebreak: { earliestOrder: {} },
+    revealRebateBps: 0,
+    keeperRewardBps: 0,
+    publicSettle: false,
+    accuracyWeightBps: 10_000,
+    boldnessWeightBps: 10_000,
+    maxPayoutBps: 0,
+    earlyFinalizeThresholdBps: 0,
+    timeDecayEnabled: true,
+    guaranteeStakeReturn: false,
+    minPrediction: null,
+    maxPrediction: null,
+    revealBond: new anchor.BN(0),
+    revealBondKeeperBps: 0,
+    consensusFloor: new anchor.BN(0),
+    wrongDirectionPenaltyBps: 0,
+    leftoverToParticipants: false,
+    poolReleaseByRevealRatio: false,
+    dimensions: 1,
+    orderedReveal: false,
+    revealTurnSeconds: 0,
+    bindSalt: false,
+    forfeitByOrder: false,
+    accuracyTolerance: new anchor.BN(0),
+    priceToleranceBps: 0,
+    priceFeedId: Array(32).fill(0),
+    priceMaxAge: 0,
+    softFinalize: false,
+    revealSecondsPerSol: 0,
+    rewardEnabled: true,
+    unanimousPolicy: { refund: {} },
+    selfRefundTimeout: new anchor.BN(DEFAULT_SELF_REFUND_TIMEOUT),
+    revealRebate: new anchor.BN(0),
+    minParticipants: 0,
+  };
+}
+
 /**
  * High-level client for interacting with the WorthHub program
  */
@@ -101,7 +351,13 @@ export class WorthHubClient {
   }
 
   /**
-   * Create a new prediction topic
+   * Create a new prediction topic. `metadataUri` (at most 128 bytes) points
+   * at off-chain JSON with the full rules; `description` stays a summary.
+   * A `cosigner` must co-sign every commit on the topic. With a `priceFeed`
+   * every truth must match that price account within
+   * `options.priceToleranceBps`, and its price must be for
+   * `options.priceFeedId`, fully verified and at most `options.priceMaxAge`
+   * seconds old.
    */
   async createTopic(
     authority: Keypair,
@@ -111,7 +367,11 @@ export class WorthHubClient {
     symbol: string,
     commitDeadline: number,
     revealDeadline: number,
-    minStake: number
+    minStake: number,
+    options: TopicOptions = defaultTopicOptions(),
+    metadataUri?: string,
+    cosigner?: PublicKey,
+    priceFeed?: PublicKey
   ): Promise<string> {
     const [topicPDA] = findTopicPDA(topicId);
     const [vaultPDA] = findVaultPDA(topicPDA);
@@ -123,11 +383,15 @@ export class WorthHubClient {
         symbol,
         new anchor.BN(commitDeadline),
         new anchor.BN(revealDeadline),
-        new anchor.BN(minStake)
+        new anchor.BN(minStake),
+        options,
+        metadataUri ?? null
       )
       .accounts({
         authority: authority.publicKey,
         oracleAuthority: oracleAuthority,
+        cosigner: cosigner ?? null,
+        priceFeed: priceFeed ?? null,
         topic: topicPDA,
         vault: vaultPDA,
         systemProgram: SystemProgram.programId,
@@ -139,31 +403,144 @@ export class WorthHubClient {
   }
 
   /**
-   * Submit a commitment
+   * Create `count` topics from one template with ids `baseTopicId` onwards,
+   * each topic's deadlines `period` seconds after the previous one's
+   */
+  async createRecurringTopics(
+    authority: Keypair,
+    oracleAuthority: PublicKey,
+    baseTopicId: number,
+    count: number,
+    period: number,
+    description: string,
+    symbol: string,
+    firstCommitDeadline: number,
+    firstRevealDeadline: number,
+    minStake: number,
+    options: TopicOptions = defaultTopicOptions(),
+    metadataUri?: string,
+    cosigner?: PublicKey,
+    priceFeed?: PublicKey
+  ): Promise<string> {
+    const topics = Array.from({ length: count }, (_, i) => ({
+      pubkey: findTopicPDA(baseTopicId + i)[0],
+      isSigner: false,
+      isWritable: true,
+    }));
+
+    const tx = await this.program.methods
+      .createRecurringTopics(
+        new anchor.BN(baseTopicId),
+        count,
+        new anchor.BN(period),
+        description,
+        symbol,
+        new anchor.BN(firstCommitDeadline),
+        new anchor.BN(firstRevealDeadline),
+        new anchor.BN(minStake),
+        options,
+        metadataUri ?? null
+      )
+      .accounts({
+        authority: authority.publicKey,
+        oracleAuthority: oracleAuthority,
+        cosigner: cosigner ?? null,
+        priceFeed: priceFeed ?? null,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(topics)
+      .signers([authority])
+      .rpc();
+
+    return tx;
+  }
+
+  /**
+   * Submit a commitment. `weight` overrides the scoring weight, which
+   * otherwise defaults to `stakeAmount`. `encryptedPrediction` is an optional
+   * 64-byte blob (encrypted to an auditor key) stored for later audit.
+   * `cosigner` is required on topics created with one. A `rentPayer`
+   * (e.g. a relayer) funds the new accounts' rent, and the stake too when
+   * `stakeFromRentPayer` is set; the participant still owns the position.
    */
   async commit(
     participant: Keypair,
     topicId: number,
     commitmentHash: Buffer,
-    stakeAmount: number
+    stakeAmount: number,
+    weight?: number,
+    encryptedPrediction?: Buffer,
+    cosigner?: Keypair,
+    rentPayer?: Keypair,
+    stakeFromRentPayer = false,
+    auxCommitment?: Buffer
   ): Promise<string> {
     const [topicPDA] = findTopicPDA(topicId);
     const [vaultPDA] = findVaultPDA(topicPDA);
-    const [commitmentPDA] = findCommitmentPDA(
+    const [participantStatePDA] = findParticipantStatePDA(
       topicPDA,
       participant.publicKey
     );
+    const [commitmentPDA] = findCommitmentPDA(
+      topicPDA,
+      participant.publicKey,
+      await this.commitNonce(topicPDA, participant.publicKey)
+    );
 
     const tx = await this.program.methods
       .commit(
         Array.from(commitmentHash) as number[],
-        new anchor.BN(stakeAmount)
+        new anchor.BN(stakeAmount),
+        weight === undefined ? null : new anchor.BN(weight),
+        encryptedPrediction === undefined ? null : (Array.from(encryptedPrediction) as number[]),
+        stakeFromRentPayer,
+        auxCommitment === undefined ? null : (Array.from(auxCommitment) as number[])
       )
       .accounts({
         participant: participant.publicKey,
+        rentPayer: (rentPayer ?? participant).publicKey,
         topic: topicPDA,
+        participantState: participantStatePDA,
         commitment: commitmentPDA,
         vault: vaultPDA,
+        cosigner: cosigner?.publicKey ?? null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([participant, ...(cosigner ? [cosigner] : []), ...(rentPayer ? [rentPayer] : [])])
+      .rpc();
+
+    return tx;
+  }
+
+  /**
+   * Withdraw a commitment and its stake before the commit deadline.
+   * Defaults to the participant's latest commitment.
+   */
+  async cancelCommitment(
+    participant: Keypair,
+    topicId: number,
+    nonce?: number
+  ): Promise<string> {
+    const [topicPDA] = findTopicPDA(topicId);
+    const [vaultPDA] = findVaultPDA(topicPDA);
+    const [commitmentPDA] = findCommitmentPDA(
+      topicPDA,
+      participant.publicKey,
+      nonce ?? (await this.latestNonce(topicPDA, participant.publicKey))
+    );
+    // Rent and stake go back to whoever paid them, possibly a sponsor
+    const commitment = await this.program.account.commitment.fetch(commitmentPDA);
+
+    const tx = await this.program.methods
+      .cancelCommitment()
+      .accounts({
+        participant: participant.publicKey,
+        topic: topicPDA,
+        commitment: commitmentPDA,
+        rentPayer: commitment.rentPayer,
+        stakePayer: commitment.stakePayer,
+        participantState: findParticipantStatePDA(topicPDA, participant.publicKey)[0],
+        vault: vaultPDA,
         systemProgram: SystemProgram.programId,
       })
       .signers([participant])
@@ -173,7 +550,46 @@ export class WorthHubClient {
   }
 
   /**
-   * Reveal a commitment
+   * Nonce the participant's next commit on a topic will use, which is also
+   * the number of commitments they have made (0 before their first commit)
+   */
+  async commitNonce(topicPDA: PublicKey, participant: PublicKey): Promise<number> {
+    const [participantStatePDA] = findParticipantStatePDA(topicPDA, participant);
+    const state = await this.program.account.participantState.fetchNullable(participantStatePDA);
+    return state ? (state.commitNonce as number) : 0;
+  }
+
+  /**
+   * Nonce of the participant's most recent commitment on a topic
+   */
+  async latestNonce(topicPDA: PublicKey, participant: PublicKey): Promise<number> {
+    return Math.max((await this.commitNonce(topicPDA, participant)) - 1, 0);
+  }
+
+  /**
+   * Addresses of a participant's open commitments on a topic, skipping
+   * canceled ones
+   */
+  async commitmentPDAs(topicPDA: PublicKey, participant: PublicKey): Promise<PublicKey[]> {
+    const count = await this.commitNonce(topicPDA, participant);
+    const pdas = Array.from({ length: count }, (_, nonce) =>
+      findCommitmentPDA(topicPDA, participant, nonce)[0]
+    );
+    const infos = await this.program.provider.connection.getMultipleAccountsInfo(pdas);
+    return pdas.filter((_, i) => infos[i] !== null);
+  }
+
+  /**
+   * The config PDA reveals on the topic must pass, or null if it has none
+   */
+  private async topicConfigOf(topicPDA: PublicKey): Promise<PublicKey | null> {
+    const topic = await this.program.account.topic.fetch(topicPDA);
+    return (topic.config as PublicKey).equals(PublicKey.default) ? null : (topic.config as PublicKey);
+  }
+
+  /**
+   * Reveal a commitment. Topics with an allowed-value grid are passed their
+   * config automatically.
    */
   async reveal(
     participant: Keypair,
@@ -184,7 +600,8 @@ export class WorthHubClient {
     const [topicPDA] = findTopicPDA(topicId);
     const [commitmentPDA] = findCommitmentPDA(
       topicPDA,
-      participant.publicKey
+      participant.publicKey,
+      await this.latestNonce(topicPDA, participant.publicKey)
     );
 
     const tx = await this.program.methods
@@ -196,6 +613,79 @@ export class WorthHubClient {
         participant: participant.publicKey,
         topic: topicPDA,
         commitment: commitmentPDA,
+        vault: findVaultPDA(topicPDA)[0],
+        topicConfig: await this.topicConfigOf(topicPDA),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([participant])
+      .rpc();
+
+    return tx;
+  }
+
+  /**
+   * Reveal one prediction per dimension of a multi-dimension topic
+   */
+  async revealVector(
+    participant: Keypair,
+    topicId: number,
+    predictions: number[],
+    salt: Buffer
+  ): Promise<string> {
+    const [topicPDA] = findTopicPDA(topicId);
+    const [commitmentPDA] = findCommitmentPDA(
+      topicPDA,
+      participant.publicKey,
+      await this.latestNonce(topicPDA, participant.publicKey)
+    );
+
+    const tx = await this.program.methods
+      .revealVector(
+        predictions.map((p) => new anchor.BN(p)),
+        Array.from(salt) as number[]
+      )
+      .accounts({
+        participant: participant.publicKey,
+        topic: topicPDA,
+        commitment: commitmentPDA,
+        vault: findVaultPDA(topicPDA)[0],
+        topicConfig: await this.topicConfigOf(topicPDA),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([participant])
+      .rpc();
+
+    return tx;
+  }
+
+  /**
+   * Prove a commitment but opt out of scoring; the stake is returned at settle
+   */
+  async abstain(
+    participant: Keypair,
+    topicId: number,
+    predictionValue: number,
+    salt: Buffer
+  ): Promise<string> {
+    const [topicPDA] = findTopicPDA(topicId);
+    const [commitmentPDA] = findCommitmentPDA(
+      topicPDA,
+      participant.publicKey,
+      await this.latestNonce(topicPDA, participant.publicKey)
+    );
+
+    const tx = await this.program.methods
+      .abstain(
+        new anchor.BN(predictionValue),
+        Array.from(salt) as number[]
+      )
+      .accounts({
+        participant: participant.publicKey,
+        topic: topicPDA,
+        commitment: commitmentPDA,
+        vault: findVaultPDA(topicPDA)[0],
+        topicConfig: await this.topicConfigOf(topicPDA),
+        systemProgram: SystemProgram.programId,
       })
       .signers([participant])
       .rpc();
@@ -204,17 +694,136 @@ export class WorthHubClient {
   }
 
   /**
-   * Oracle finalizes the topic with the true value
+   * Reveal several of the participant's commitments in one transaction,
+   * each identified by its commit nonce (at most 8)
+   */
+  async revealMany(
+    participant: Keypair,
+    topicId: number,
+    reveals: { nonce: number; predictionValue: number; salt: Buffer }[]
+  ): Promise<string> {
+    const [topicPDA] = findTopicPDA(topicId);
+
+    const tx = await this.program.methods
+      .revealMany(
+        reveals.map((r) => ({
+          commitmentIndex: r.nonce,
+          predictionValue: new anchor.BN(r.predictionValue),
+          salt: Array.from(r.salt) as number[],
+        }))
+      )
+      .accounts({
+        participant: participant.publicKey,
+        topic: topicPDA,
+        vault: findVaultPDA(topicPDA)[0],
+        topicConfig: await this.topicConfigOf(topicPDA),
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(
+        reveals.map((r) => ({
+          pubkey: findCommitmentPDA(topicPDA, participant.publicKey, r.nonce)[0],
+          isSigner: false,
+          isWritable: true,
+        }))
+      )
+      .signers([participant])
+      .rpc();
+
+    return tx;
+  }
+
+  /**
+   * Reveal an interval commitment made with computeIntervalHash
+   */
+  async revealInterval(
+    participant: Keypair,
+    topicId: number,
+    low: number,
+    high: number,
+    salt: Buffer
+  ): Promise<string> {
+    const [topicPDA] = findTopicPDA(topicId);
+    const [commitmentPDA] = findCommitmentPDA(
+      topicPDA,
+      participant.publicKey,
+      await this.latestNonce(topicPDA, participant.publicKey)
+    );
+
+    const tx = await this.program.methods
+      .revealInterval(
+        new anchor.BN(low),
+        new anchor.BN(high),
+        Array.from(salt) as number[]
+      )
+      .accounts({
+        participant: participant.publicKey,
+        topic: topicPDA,
+        commitment: commitmentPDA,
+        vault: findVaultPDA(topicPDA)[0],
+        topicConfig: await this.topicConfigOf(topicPDA),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([participant])
+      .rpc();
+
+    return tx;
+  }
+
+  /**
+   * Skip an ordered-reveal turn that has expired (permissionless)
+   */
+  async skipRevealTurn(topicId: number): Promise<string> {
+    const [topicPDA] = findTopicPDA(topicId);
+    return await this.program.methods
+      .skipRevealTurn()
+      .accounts({ topic: topicPDA })
+      .rpc();
+  }
+
+  /**
+   * Oracle finalizes the topic with the true value and an optional
+   * confidence band [low, high] around it. Topics created with a price
+   * feed are passed it automatically.
    */
   async finalize(
     oracle: Keypair,
     topicId: number,
-    truthValue: number
+    truthValue: number,
+    band?: [number, number]
   ): Promise<string> {
     const [topicPDA] = findTopicPDA(topicId);
+    const topic = await this.program.account.topic.fetch(topicPDA);
+    const priceFeed = topic.priceFeed as PublicKey;
 
     const tx = await this.program.methods
-      .finalize(new anchor.BN(truthValue))
+      .finalize(
+        new anchor.BN(truthValue),
+        band ? new anchor.BN(band[0]) : null,
+        band ? new anchor.BN(band[1]) : null
+      )
+      .accounts({
+        oracleAuthority: oracle.publicKey,
+        topic: topicPDA,
+        priceFeed: priceFeed.equals(PublicKey.default) ? null : priceFeed,
+      })
+      .signers([oracle])
+      .rpc();
+
+    return tx;
+  }
+
+  /**
+   * Oracle finalizes a multi-dimension topic with one true value per dimension
+   */
+  async finalizeVector(
+    oracle: Keypair,
+    topicId: number,
+    truths: number[]
+  ): Promise<string> {
+    const [topicPDA] = findTopicPDA(topicId);
+
+    const tx = await this.program.methods
+      .finalizeVector(truths.map((t) => new anchor.BN(t)))
       .accounts({
         oracleAuthority: oracle.publicKey,
         topic: topicPDA,
@@ -228,12 +837,14 @@ export class WorthHubClient {
   /**
    * Settle the topic and distribute rewards
    *
-   * @param commitmentParticipantPairs - Array of [commitmentPDA, participantPubkey]
+   * @param commitmentParticipantPairs - [commitmentPDA, participantPubkey] for every commitment of the topic
+   * @param leaderboardParticipants - Participants whose leaderboard entries to credit
    */
   async settle(
     authority: Keypair,
     topicId: number,
-    commitmentParticipantPairs: [PublicKey, PublicKey][]
+    commitmentParticipantPairs: [PublicKey, PublicKey][],
+    leaderboardParticipants: PublicKey[] = []
   ): Promise<string> {
     const [topicPDA] = findTopicPDA(topicId);
     const [vaultPDA] = findVaultPDA(topicPDA);
@@ -252,13 +863,25 @@ export class WorthHubClient {
         isWritable: true,
       });
     }
+    for (const participant of leaderboardParticipants) {
+      remainingAccounts.push({
+        pubkey: findLeaderboardPDA(participant)[0],
+        isSigner: false,
+        isWritable: true,
+      });
+    }
+
+    const topic = await this.program.account.topic.fetch(topicPDA);
 
     const tx = await this.program.methods
       .settle()
       .accounts({
         authority: authority.publicKey,
         topic: topicPDA,
+        topicAuthority: topic.authority as PublicKey,
         vault: vaultPDA,
+        truthHistory: findTruthHistoryPDA(topic.symbol as string)[0],
+        settlementRecord: findSettlementRecordPDA(topicPDA)[0],
         systemProgram: SystemProgram.programId,
       })
       .remainingAccounts(remainingAccounts)
@@ -268,6 +891,93 @@ export class WorthHubClient {
     return tx;
   }
 
+  /**
+   * Lamports `settle` would pay `commitment` once the topic is finalized,
+   * given the same commitment/participant pairs settle would receive
+   */
+  async projectedPayout(
+    topicId: number,
+    commitmentParticipantPairs: [PublicKey, PublicKey][],
+    commitment: PublicKey
+  ): Promise<number> {
+    const [topicPDA] = findTopicPDA(topicId);
+    const [vaultPDA] = findVaultPDA(topicPDA);
+
+    const remainingAccounts: anchor.web3.AccountMeta[] = commitmentParticipantPairs.flatMap(
+      ([commitmentPDA, participant]) => [
+        { pubkey: commitmentPDA, isSigner: false, isWritable: false },
+        { pubkey: participant, isSigner: false, isWritable: false },
+      ]
+    );
+
+    const payout = await this.program.methods
+      .projectedPayout()
+      .accounts({ topic: topicPDA, vault: vaultPDA, commitment })
+      .remainingAccounts(remainingAccounts)
+      .view();
+
+    return (payout as anchor.BN).toNumber();
+  }
+
+  /**
+   * The loser pool settle would distribute, given every commitment on the
+   * topic: the unrevealed stake so far during the reveal phase, settle's
+   * exact figure once finalized
+   */
+  async loserPoolPreview(topicId: number, commitments: PublicKey[]): Promise<number> {
+    const [topicPDA] = findTopicPDA(topicId);
+    const pool = await this.program.methods
+      .loserPoolPreview()
+      .accounts({ topic: topicPDA })
+      .remainingAccounts(commitments.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })))
+      .view();
+
+    return (pool as anchor.BN).toNumber();
+  }
+
+  /**
+   * Whether the participant's commitment under `nonce` exists, is revealed,
+   * abstained or settled, and its stake, without fetching the account
+   */
+  async participantStatus(
+    topicId: number,
+    participant: PublicKey,
+    nonce = 0
+  ): Promise<{ exists: boolean; revealed: boolean; abstained: boolean; settled: boolean; stakeAmount: anchor.BN }> {
+    const [topicPDA] = findTopicPDA(topicId);
+    return await this.program.methods
+      .participantStatus(participant, nonce)
+      .accounts({
+        topic: topicPDA,
+        commitment: findCommitmentPDA(topicPDA, participant, nonce)[0],
+      })
+      .view();
+  }
+
+  /**
+   * The program's full LN_TABLE (ln(N + e) × precision for small N), read a
+   * page at a time, with the precision and percentage cap it is used with
+   */
+  async getDecayTable(): Promise<{ lnTable: anchor.BN[]; precision: anchor.BN; maxPct: anchor.BN }> {
+    const lnTable: anchor.BN[] = [];
+    for (let page = 0; ; page++) {
+      const data = await this.program.methods.getDecayTable(page).accounts({}).view();
+      lnTable.push(...data.entries);
+      if (lnTable.length >= data.tableLen || data.entries.length === 0) {
+        return { lnTable, precision: data.precision, maxPct: data.maxPct };
+      }
+    }
+  }
+
+  /**
+   * Whether `topicId` is free: `createTopic` with a taken id fails with
+   * `TopicIdAlreadyExists`
+   */
+  async isTopicIdAvailable(topicId: number): Promise<boolean> {
+    const [topicPDA] = findTopicPDA(topicId);
+    return (await this.connection.getAccountInfo(topicPDA)) === null;
+  }
+
   /**
    * Fetch a topic's on-chain data
    */
@@ -289,6 +999,7 @@ export class WorthHubClient {
         commitment_count: account.commitmentCount as number,
         reveal_count: account.revealCount as number,
         min_stake: (account.minStake as anchor.BN).toNumber(),
+        metadata_uri: account.metadataUri as string,
       };
     } catch {
       return null;
@@ -303,8 +1014,12 @@ export class WorthHubClient {
     participant: PublicKey
   ): Promise<CommitmentInfo | null> {
     const [topicPDA] = findTopicPDA(topicId);
-    const [commitmentPDA] = findCommitmentPDA(topicPDA, participant);
     try {
+      const [commitmentPDA] = findCommitmentPDA(
+        topicPDA,
+        participant,
+        await this.latestNonce(topicPDA, participant)
+      );
       const account = await this.program.account.commitment.fetch(commitmentPDA);
       return {
         topic: (account.topic as PublicKey).toBase58(),
@@ -322,10 +1037,147 @@ export class WorthHubClient {
   }
 
   /**
-   * Fetch all topics (using getProgramAccounts)
+   * Recent settled truths for `symbol`, oldest first (empty before its
+   * first settle)
    */
-  async fetchAllTopics(): Promise<TopicInfo[]> {
-    const accounts = await this.program.account.topic.all();
+  async fetchTruthHistory(
+    symbol: string
+  ): Promise<{ topicId: number; truthValue: number; settledAt: number }[]> {
+    const [historyPDA] = findTruthHistoryPDA(symbol);
+    const history = await this.program.account.truthHistory.fetchNullable(historyPDA);
+    if (!history) return [];
+    const records = history.records as any[];
+    const head = history.head as number;
+    return [...records.slice(head), ...records.slice(0, head)].map((r) => ({
+      topicId: (r.topicId as anchor.BN).toNumber(),
+      truthValue: (r.truthValue as anchor.BN).toNumber(),
+      settledAt: (r.settledAt as anchor.BN).toNumber(),
+    }));
+  }
+
+  /**
+   * Every settled commitment's prediction, score and payout, as recorded
+   * by settle (null before the topic is settled)
+   */
+  async fetchSettlementRecord(topicId: number): Promise<
+    | {
+        participant: PublicKey;
+        prediction: number;
+        score: bigint;
+        payout: number;
+        revealed: boolean;
+        abstained: boolean;
+      }[]
+    | null
+  > {
+    const [topicPDA] = findTopicPDA(topicId);
+    const [recordPDA] = findSettlementRecordPDA(topicPDA);
+    const record = await this.program.account.settlementRecord.fetchNullable(recordPDA);
+    if (!record || (record.settledAt as anchor.BN).isZero()) return null;
+    return (record.outcomes as any[]).map((o) => ({
+      participant: o.participant as PublicKey,
+      prediction: (o.prediction as anchor.BN).toNumber(),
+      score: BigInt((o.score as anchor.BN).toString()),
+      payout: (o.payout as anchor.BN).toNumber(),
+      revealed: o.revealed as boolean,
+      abstained: o.abstained as boolean,
+    }));
+  }
+
+  /**
+   * Take back the participant's own stake from a finalized topic that has
+   * gone unsettled past its self-refund timeout (revealed commitments only)
+   */
+  async selfRefund(participant: Keypair, topicId: number, nonce = 0): Promise<string> {
+    const [topicPDA] = findTopicPDA(topicId);
+    const [commitmentPDA] = findCommitmentPDA(topicPDA, participant.publicKey, nonce);
+    const commitment = await this.program.account.commitment.fetch(commitmentPDA);
+    return await this.program.methods
+      .selfRefund()
+      .accounts({
+        participant: participant.publicKey,
+        topic: topicPDA,
+        commitment: commitmentPDA,
+        stakePayer: commitment.stakePayer,
+        vault: findVaultPDA(topicPDA)[0],
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([participant])
+      .rpc();
+  }
+
+  /**
+   * Append to a topic's allowed prediction grid (at most 512 values in
+   * total), before anyone commits. Long grids take several calls.
+   */
+  async addAllowedValues(authority: Keypair, topicId: number, values: number[]): Promise<string> {
+    const [topicPDA] = findTopicPDA(topicId);
+    return await this.program.methods
+      .addAllowedValues(values.map((v) => new anchor.BN(v)))
+      .accounts({
+        authority: authority.publicKey,
+        topic: topicPDA,
+        topicConfig: findTopicConfigPDA(topicPDA)[0],
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  /**
+   * Create the participant's leaderboard entry, which `settle` credits
+   * when passed it
+   */
+  async initLeaderboard(participant: Keypair): Promise<string> {
+    return await this.program.methods
+      .initLeaderboard()
+      .accounts({
+        participant: participant.publicKey,
+        leaderboard: findLeaderboardPDA(participant.publicKey)[0],
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([participant])
+      .rpc();
+  }
+
+  /**
+   * A participant's cumulative settle results (null without an entry)
+   */
+  async fetchLeaderboard(participant: PublicKey): Promise<{
+    settledCount: number;
+    winCount: number;
+    cumulativePayout: number;
+    totalScore: bigint;
+    lastTopicId: number;
+  } | null> {
+    const entry = await this.program.account.leaderboard.fetchNullable(findLeaderboardPDA(participant)[0]);
+    if (!entry) return null;
+    return {
+      settledCount: entry.settledCount as number,
+      winCount: entry.winCount as number,
+      cumulativePayout: (entry.cumulativePayout as anchor.BN).toNumber(),
+      totalScore: BigInt((entry.totalScore as anchor.BN).toString()),
+      lastTopicId: (entry.lastTopicId as anchor.BN).toNumber(),
+    };
+  }
+
+  /**
+   * Fetch all topics (using getProgramAccounts), optionally only those in
+   * `status`
+   */
+  async fetchAllTopics(status?: TopicStatus): Promise<TopicInfo[]> {
+    const filters =
+      status === undefined
+        ? []
+        : [
+            {
+              memcmp: {
+                offset: TOPIC_STATUS_OFFSET,
+                bytes: anchor.utils.bytes.bs58.encode(Buffer.from([status])),
+              },
+            },
+          ];
+    const accounts = await this.program.account.topic.all(filters);
     return accounts.map((a) => ({
       topic_id: (a.account.topicId as anchor.BN).toNumber(),
       authority: (a.account.authority as PublicKey).toBase58(),
@@ -340,9 +1192,17 @@ export class WorthHubClient {
       commitment_count: a.account.commitmentCount as number,
       reveal_count: a.account.revealCount as number,
       min_stake: (a.account.minStake as anchor.BN).toNumber(),
+      metadata_uri: a.account.metadataUri as string,
     }));
   }
 
+  /**
+   * Fetch finalized topics still waiting for settle, for keepers
+   */
+  async fetchTopicsToSettle(): Promise<TopicInfo[]> {
+    return this.fetchAllTopics(TopicStatus.Finalized);
+  }
+
   private mapStatus(status: any): TopicStatus {
     if (status.open) return TopicStatus.Open;
     if (status.revealing) return TopicStatus.Revealing;
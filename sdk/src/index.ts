@@ -7,6 +7,8 @@ export {
   PROTOCOL_VERSION,
   VALUE_PRECISION,
   DEFAULT_MIN_STAKE,
+  DEFAULT_REFUND_TIMEOUT,
+  MIN_REFUND_TIMEOUT,
   TopicStatus,
   toFixedPoint,
   fromFixedPoint,
@@ -28,13 +30,22 @@ export type {
 // Anchor client
 export {
   PROGRAM_ID,
+  TOPIC_STATUS_OFFSET,
   WorthHubClient,
   findTopicPDA,
   findVaultPDA,
   findCommitmentPDA,
+  findParticipantStatePDA,
+  findTruthHistoryPDA,
+  findSettlementRecordPDA,
   computeCommitmentHash,
+  computeIntervalHash,
+  computeVectorHash,
   generateSalt,
+  bindSalt,
+  defaultTopicOptions,
 } from "./client";
+export type { TopicOptions } from "./client";
 
 // Worker Agent
 export { WorkerAgent } from "./WorkerAgent";
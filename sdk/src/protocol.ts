@@ -13,6 +13,15 @@ export const VALUE_PRECISION = 1_000_000;
 // Minimum stake in lamports (0.01 SOL)
 export const DEFAULT_MIN_STAKE = 10_000_000;
 
+// Seconds after finalization before the authority may refund a participant (7 days)
+export const DEFAULT_REFUND_TIMEOUT = 7 * 24 * 60 * 60;
+
+// Shortest refund timeout a topic may be created with (1 minute)
+export const MIN_REFUND_TIMEOUT = 60;
+
+// Seconds after finalization before a revealed participant may self-refund (30 days)
+export const DEFAULT_SELF_REFUND_TIMEOUT = 30 * 24 * 60 * 60;
+
 // ─── Topic Status ───────────────────────────────────────────────────
 
 export enum TopicStatus {
@@ -111,6 +120,8 @@ export interface TopicInfo {
   commitment_count: number;
   reveal_count: number;
   min_stake: number;
+  /** Off-chain rules URI; empty if unset */
+  metadata_uri: string;
 }
 
 export interface CommitmentInfo {
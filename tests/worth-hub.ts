@@ -5,9 +5,11 @@ import {
   PublicKey,
   SystemProgram,
   LAMPORTS_PER_SOL,
+  Transaction,
 } from "@solana/web3.js";
 import { expect } from "chai";
 import { keccak_256 } from "js-sha3";
+import * as fs from "fs";
 
 // We'll reference the IDL type once generated; for now use `any`
 type WorthHub = any;
@@ -17,7 +19,7 @@ type WorthHub = any;
  * keccak256(prediction_value_le_bytes || salt || participant_pubkey)
  */
 function computeHash(
-  predictionValue: number,
+  predictionValue: number | string,
   salt: Buffer,
   participant: PublicKey
 ): number[] {
@@ -29,10 +31,129 @@ function computeHash(
   return Array.from(new Uint8Array(hash));
 }
 
+/**
+ * Helper: interval commitment hash
+ * keccak256(low_le_bytes || high_le_bytes || salt || participant_pubkey)
+ */
+function computeIntervalHash(
+  low: number,
+  high: number,
+  salt: Buffer,
+  participant: PublicKey
+): number[] {
+  const buf = Buffer.alloc(8 + 8 + 32 + 32);
+  buf.writeBigInt64LE(BigInt(low), 0);
+  buf.writeBigInt64LE(BigInt(high), 8);
+  salt.copy(buf, 16);
+  participant.toBuffer().copy(buf, 48);
+  const hash = keccak_256.arrayBuffer(buf);
+  return Array.from(new Uint8Array(hash));
+}
+
+/**
+ * Helper: multi-dimension commitment hash
+ * keccak256(prediction_1_le_bytes || ... || prediction_n_le_bytes || salt || participant_pubkey)
+ */
+function computeVectorHash(
+  predictions: number[],
+  salt: Buffer,
+  participant: PublicKey
+): number[] {
+  const offset = 8 * predictions.length;
+  const buf = Buffer.alloc(offset + 32 + 32);
+  predictions.forEach((p, i) => buf.writeBigInt64LE(BigInt(p), 8 * i));
+  salt.copy(buf, offset);
+  participant.toBuffer().copy(buf, offset + 32);
+  const hash = keccak_256.arrayBuffer(buf);
+  return Array.from(new Uint8Array(hash));
+}
+
+/**
+ * Helper: the proof the placeholder range-proof verifier accepts
+ * keccak256(aux_commitment || low_le_bytes || high_le_bytes)
+ */
+function placeholderRangeProof(auxCommitment: number[], low: number, high: number): number[] {
+  const buf = Buffer.alloc(32 + 8 + 8);
+  Buffer.from(auxCommitment).copy(buf, 0);
+  buf.writeBigInt64LE(BigInt(low), 32);
+  buf.writeBigInt64LE(BigInt(high), 40);
+  const hash = keccak_256.arrayBuffer(buf);
+  return Array.from(new Uint8Array(hash));
+}
+
+/** Shortest refund timeout a topic accepts (TopicOptions::MIN_REFUND_TIMEOUT) */
+const MIN_REFUND_TIMEOUT = 60;
+
+/**
+ * Helper: default per-topic options, with `overrides` applied on top
+ */
+function topicOptions(overrides: Record<string, any> = {}) {
+  return {
+    refundTimeout: new BN(7 * 24 * 60 * 60),
+    manualRevealGate: false,
+    rewardMode: { proportional: {} },
+    tiebreak: { earliestOrder: {} },
+    revealRebateBps: 0,
+    keeperRewardBps: 0,
+    publicSettle: false,
+    accuracyWeightBps: 10_000,
+    boldnessWeightBps: 10_000,
+    maxPayoutBps: 0,
+    earlyFinalizeThresholdBps: 0,
+    timeDecayEnabled: true,
+    guaranteeStakeReturn: false,
+    minPrediction: null,
+    maxPrediction: null,
+    revealBond: new BN(0),
+    revealBondKeeperBps: 0,
+    consensusFloor: new BN(0),
+    wrongDirectionPenaltyBps: 0,
+    leftoverToParticipants: false,
+    poolReleaseByRevealRatio: false,
+    dimensions: 1,
+    orderedReveal: false,
+    revealTurnSeconds: 0,
+    bindSalt: false,
+    forfeitByOrder: false,
+    accuracyTolerance: new BN(0),
+    priceToleranceBps: 0,
+    priceFeedId: Array(32).fill(0),
+    priceMaxAge: 0,
+    softFinalize: false,
+    revealSecondsPerSol: 0,
+    rewardEnabled: true,
+    unanimousPolicy: { refund: {} },
+    selfRefundTimeout: new BN(30 * 24 * 60 * 60),
+    revealRebate: new BN(0),
+    minParticipants: 0,
+    ...overrides,
+  };
+}
+
+/**
+ * Helper: u32 little-endian commit nonce, as used in commitment PDA seeds
+ */
+function nonceSeed(nonce: number): Buffer {
+  const buf = Buffer.alloc(4);
+  buf.writeUInt32LE(nonce);
+  return buf;
+}
+
 function randomSalt(): Buffer {
   return Buffer.from(Keypair.generate().secretKey.slice(0, 32));
 }
 
+/**
+ * Helper: random salt carrying the prefix a `bindSalt` topic requires
+ * keccak256(topic || participant || nonce_le)[..8]
+ */
+function boundSalt(topic: PublicKey, participant: PublicKey, nonce: number): Buffer {
+  const buf = Buffer.concat([topic.toBuffer(), participant.toBuffer(), nonceSeed(nonce)]);
+  const salt = randomSalt();
+  Buffer.from(keccak_256.arrayBuffer(buf)).copy(salt, 0, 0, 8);
+  return salt;
+}
+
 describe("WorthHub", () => {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
@@ -62,6 +183,269 @@ describe("WorthHub", () => {
     await provider.connection.confirmTransaction(sig);
   }
 
+  // ─── Lifecycle helpers ───────────────────────────────────────────
+
+  function deriveTopic(id: number): [PublicKey, PublicKey] {
+    const idBuf = Buffer.alloc(8);
+    idBuf.writeBigUInt64LE(BigInt(id));
+    const [topic] = PublicKey.findProgramAddressSync(
+      [Buffer.from("topic"), idBuf],
+      program.programId
+    );
+    const [vault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), topic.toBuffer()],
+      program.programId
+    );
+    return [topic, vault];
+  }
+
+  function deriveCommitment(topic: PublicKey, participant: PublicKey, nonce = 0): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("commitment"), topic.toBuffer(), participant.toBuffer(), nonceSeed(nonce)],
+      program.programId
+    )[0];
+  }
+
+  function deriveParticipantState(topic: PublicKey, participant: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("participant"), topic.toBuffer(), participant.toBuffer()],
+      program.programId
+    )[0];
+  }
+
+  function deriveTruthHistory(symbol: string): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("truth_history"), Buffer.from(symbol)],
+      program.programId
+    )[0];
+  }
+
+  function deriveTopicConfig(topic: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("topic_config"), topic.toBuffer()],
+      program.programId
+    )[0];
+  }
+
+  /** The config PDA reveals on `topic` must pass, or null if it has none */
+  async function topicConfigOf(topic: PublicKey): Promise<PublicKey | null> {
+    const { config } = await program.account.topic.fetch(topic);
+    return config.equals(PublicKey.default) ? null : config;
+  }
+
+  async function addAllowedValues(topic: PublicKey, values: number[]): Promise<string> {
+    return await program.methods
+      .addAllowedValues(values.map((v) => new BN(v)))
+      .accounts({
+        authority: authority.publicKey,
+        topic,
+        topicConfig: deriveTopicConfig(topic),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  }
+
+  /** The truth history PDA of `topic`'s symbol */
+  async function truthHistoryOf(topic: PublicKey): Promise<PublicKey> {
+    return deriveTruthHistory((await program.account.topic.fetch(topic)).symbol);
+  }
+
+  /** Decode the events a confirmed transaction emitted, by name */
+  async function eventsOf(signature: string): Promise<Record<string, any>> {
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+    const events: Record<string, any> = {};
+    for (const event of parser.parseLogs(tx?.meta?.logMessages ?? [])) {
+      events[event.name] = event.data;
+    }
+    return events;
+  }
+
+  /** Assert the finalize `submit` sends fails with `reason`, leaving the topic unfinalized */
+  async function expectFinalizeRejected(topic: PublicKey, submit: () => Promise<string>, reason: string) {
+    try {
+      await submit();
+      expect.fail("Should have thrown");
+    } catch (err: any) {
+      expect(err.toString()).to.include(reason);
+    }
+    expect((await program.account.topic.fetch(topic)).status).to.not.have.property("finalized");
+  }
+
+  async function sleepUntil(unixTs: number) {
+    const waitMs = (unixTs - Math.floor(Date.now() / 1000) + 1) * 1000;
+    if (waitMs > 0) {
+      await new Promise((r) => setTimeout(r, waitMs));
+    }
+  }
+
+  type TestTopicOpts = {
+    commitIn?: number;
+    revealIn?: number;
+    settleAuthority?: PublicKey;
+    cosigner?: PublicKey;
+    priceFeed?: PublicKey;
+    symbol?: string;
+    minStake?: number;
+    options?: Record<string, any>;
+    metadataUri?: string;
+  };
+
+  async function createTestTopic(
+    id: number,
+    opts: TestTopicOpts = {}
+  ): Promise<[PublicKey, PublicKey]> {
+    const [topic, vault] = deriveTopic(id);
+    const now = Math.floor(Date.now() / 1000);
+    await program.methods
+      .createTopic(
+        new BN(id),
+        `Test topic ${id}`,
+        opts.symbol ?? "TEST",
+        new BN(now + (opts.commitIn ?? 5)),
+        new BN(now + (opts.revealIn ?? 10)),
+        new BN(opts.minStake ?? 10_000_000),
+        topicOptions(opts.options),
+        opts.metadataUri ?? null
+      )
+      .accounts({
+        authority: authority.publicKey,
+        oracleAuthority: oracleAuthority.publicKey,
+        settleAuthority: opts.settleAuthority ?? null,
+        cosigner: opts.cosigner ?? null,
+        priceFeed: opts.priceFeed ?? null,
+        topic,
+        vault,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+    return [topic, vault];
+  }
+
+  async function commitTo(
+    topic: PublicKey,
+    vault: PublicKey,
+    agent: Keypair,
+    prediction: number | string,
+    salt: Buffer,
+    stake: number,
+    weight: number | null = null,
+    encryptedPrediction: number[] | null = null,
+    auxCommitment: number[] | null = null
+  ) {
+    await program.methods
+      .commit(
+        computeHash(prediction, salt, agent.publicKey),
+        new BN(stake),
+        weight === null ? null : new BN(weight),
+        encryptedPrediction,
+        false,
+        auxCommitment
+      )
+      .accounts({
+        participant: agent.publicKey,
+        rentPayer: agent.publicKey,
+        topic,
+        commitment: deriveCommitment(topic, agent.publicKey),
+        participantState: deriveParticipantState(topic, agent.publicKey),
+        vault,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+  }
+
+  async function revealTo(topic: PublicKey, agent: Keypair, prediction: number, salt: Buffer) {
+    await program.methods
+      .reveal(new BN(prediction), Array.from(salt))
+      .accounts({
+        participant: agent.publicKey,
+        topic,
+        commitment: deriveCommitment(topic, agent.publicKey),
+        topicConfig: await topicConfigOf(topic),
+      })
+      .signers([agent])
+      .rpc();
+  }
+
+  async function finalizeAs(
+    topic: PublicKey,
+    truth: number,
+    oracle: Keypair = oracleAuthority,
+    priceFeed: PublicKey | null = null
+  ): Promise<string> {
+    return await program.methods
+      .finalize(new BN(truth), null, null)
+      .accounts({ oracleAuthority: oracle.publicKey, topic, priceFeed })
+      .signers([oracle])
+      .rpc();
+  }
+
+  async function reportRejectedTruth(topic: PublicKey, truth: number, oracle: Keypair = oracleAuthority): Promise<string> {
+    return await program.methods
+      .reportRejectedTruth(new BN(truth), null, null, [])
+      .accounts({ oracleAuthority: oracle.publicKey, topic, priceFeed: null })
+      .signers([oracle])
+      .rpc();
+  }
+
+  async function settleAs(
+    topic: PublicKey,
+    vault: PublicKey,
+    signer: Keypair,
+    agents: Keypair[]
+  ): Promise<string> {
+    return await program.methods
+      .settle()
+      .accounts({
+        authority: signer.publicKey,
+        topic,
+        topicAuthority: authority.publicKey,
+        vault,
+        truthHistory: await truthHistoryOf(topic),
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(
+        agents.flatMap((a) => [
+          { pubkey: deriveCommitment(topic, a.publicKey), isSigner: false, isWritable: true },
+          { pubkey: a.publicKey, isSigner: false, isWritable: true },
+        ])
+      )
+      .signers([signer])
+      .rpc();
+  }
+
+  /**
+   * Create a topic, have each agent commit and reveal the given prediction,
+   * then finalize with `truth`. Returns once the topic is Finalized.
+   */
+  async function runToFinalized(
+    id: number,
+    agents: Keypair[],
+    predictions: number[],
+    truth: number,
+    opts: TestTopicOpts = {}
+  ): Promise<[PublicKey, PublicKey]> {
+    const [topic, vault] = await createTestTopic(id, opts);
+    const salts = agents.map(() => randomSalt());
+    for (let i = 0; i < agents.length; i++) {
+      await commitTo(topic, vault, agents[i], predictions[i], salts[i], 50_000_000);
+    }
+    const created = await program.account.topic.fetch(topic);
+    await sleepUntil(created.commitDeadline.toNumber());
+    for (let i = 0; i < agents.length; i++) {
+      await revealTo(topic, agents[i], predictions[i], salts[i]);
+    }
+    await sleepUntil(created.revealDeadline.toNumber());
+    await finalizeAs(topic, truth);
+    return [topic, vault];
+  }
+
   before(async () => {
     // Derive PDAs
     const topicIdBuf = Buffer.alloc(8);
@@ -101,7 +485,9 @@ describe("WorthHub", () => {
           "AAPL",
           new BN(commitDeadline),
           new BN(revealDeadline),
-          new BN(minStake)
+          new BN(minStake),
+          topicOptions(),
+          null
         )
         .accounts({
           authority: authority.publicKey,
@@ -146,7 +532,9 @@ describe("WorthHub", () => {
             "TEST",
             new BN(now + 60),
             new BN(now + 120),
-            new BN(10_000_000)
+            new BN(10_000_000),
+            topicOptions(),
+            null
           )
           .accounts({
             authority: authority.publicKey,
@@ -162,6 +550,37 @@ describe("WorthHub", () => {
         expect(err.toString()).to.include("DescriptionTooLong");
       }
     });
+
+    it("should reject the default pubkey as oracle", async () => {
+      const [topic, vault] = deriveTopic(400);
+      const now = Math.floor(Date.now() / 1000);
+
+      try {
+        await program.methods
+          .createTopic(
+            new BN(400),
+            "Topic nobody can finalize",
+            "TEST",
+            new BN(now + 60),
+            new BN(now + 120),
+            new BN(10_000_000),
+            topicOptions(),
+            null
+          )
+          .accounts({
+            authority: authority.publicKey,
+            oracleAuthority: PublicKey.default,
+            topic,
+            vault,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([authority])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidOracle");
+      }
+    });
   });
 
   // ─── Test 2: Commit Phase ────────────────────────────────────────
@@ -181,16 +600,18 @@ describe("WorthHub", () => {
     it("agent1 should commit successfully", async () => {
       const hash = computeHash(prediction1, salt1, agent1.publicKey);
       const [commitPDA] = PublicKey.findProgramAddressSync(
-        [Buffer.from("commitment"), topicPDA.toBuffer(), agent1.publicKey.toBuffer()],
+        [Buffer.from("commitment"), topicPDA.toBuffer(), agent1.publicKey.toBuffer(), nonceSeed(0)],
         program.programId
       );
 
       await program.methods
-        .commit(hash, new BN(stakeAmount))
+        .commit(hash, new BN(stakeAmount), null, null, false, null)
         .accounts({
           participant: agent1.publicKey,
+          rentPayer: agent1.publicKey,
           topic: topicPDA,
           commitment: commitPDA,
+          participantState: deriveParticipantState(topicPDA, agent1.publicKey),
           vault: vaultPDA,
           systemProgram: SystemProgram.programId,
         })
@@ -211,16 +632,18 @@ describe("WorthHub", () => {
     it("agent2 should commit successfully", async () => {
       const hash = computeHash(prediction2, salt2, agent2.publicKey);
       const [commitPDA] = PublicKey.findProgramAddressSync(
-        [Buffer.from("commitment"), topicPDA.toBuffer(), agent2.publicKey.toBuffer()],
+        [Buffer.from("commitment"), topicPDA.toBuffer(), agent2.publicKey.toBuffer(), nonceSeed(0)],
         program.programId
       );
 
       await program.methods
-        .commit(hash, new BN(stakeAmount))
+        .commit(hash, new BN(stakeAmount), null, null, false, null)
         .accounts({
           participant: agent2.publicKey,
+          rentPayer: agent2.publicKey,
           topic: topicPDA,
           commitment: commitPDA,
+          participantState: deriveParticipantState(topicPDA, agent2.publicKey),
           vault: vaultPDA,
           systemProgram: SystemProgram.programId,
         })
@@ -237,16 +660,18 @@ describe("WorthHub", () => {
     it("agent3 should commit successfully", async () => {
       const hash = computeHash(prediction3, salt3, agent3.publicKey);
       const [commitPDA] = PublicKey.findProgramAddressSync(
-        [Buffer.from("commitment"), topicPDA.toBuffer(), agent3.publicKey.toBuffer()],
+        [Buffer.from("commitment"), topicPDA.toBuffer(), agent3.publicKey.toBuffer(), nonceSeed(0)],
         program.programId
       );
 
       await program.methods
-        .commit(hash, new BN(stakeAmount))
+        .commit(hash, new BN(stakeAmount), null, null, false, null)
         .accounts({
           participant: agent3.publicKey,
+          rentPayer: agent3.publicKey,
           topic: topicPDA,
           commitment: commitPDA,
+          participantState: deriveParticipantState(topicPDA, agent3.publicKey),
           vault: vaultPDA,
           systemProgram: SystemProgram.programId,
         })
@@ -264,17 +689,19 @@ describe("WorthHub", () => {
 
       const hash = computeHash(100_000_000, randomSalt(), tmpAgent.publicKey);
       const [commitPDA] = PublicKey.findProgramAddressSync(
-        [Buffer.from("commitment"), topicPDA.toBuffer(), tmpAgent.publicKey.toBuffer()],
+        [Buffer.from("commitment"), topicPDA.toBuffer(), tmpAgent.publicKey.toBuffer(), nonceSeed(0)],
         program.programId
       );
 
       try {
         await program.methods
-          .commit(hash, new BN(0))
+          .commit(hash, new BN(0), null, null, false, null)
           .accounts({
             participant: tmpAgent.publicKey,
+            rentPayer: tmpAgent.publicKey,
             topic: topicPDA,
             commitment: commitPDA,
+            participantState: deriveParticipantState(topicPDA, tmpAgent.publicKey),
             vault: vaultPDA,
             systemProgram: SystemProgram.programId,
           })
@@ -303,7 +730,7 @@ describe("WorthHub", () => {
       const { salt1 } = (global as any).__test_salts;
 
       const [commitPDA] = PublicKey.findProgramAddressSync(
-        [Buffer.from("commitment"), topicPDA.toBuffer(), agent1.publicKey.toBuffer()],
+        [Buffer.from("commitment"), topicPDA.toBuffer(), agent1.publicKey.toBuffer(), nonceSeed(0)],
         program.programId
       );
 
@@ -341,7 +768,7 @@ describe("WorthHub", () => {
 
       // Agent 1 reveals
       const [commitPDA1] = PublicKey.findProgramAddressSync(
-        [Buffer.from("commitment"), topicPDA.toBuffer(), agent1.publicKey.toBuffer()],
+        [Buffer.from("commitment"), topicPDA.toBuffer(), agent1.publicKey.toBuffer(), nonceSeed(0)],
         program.programId
       );
 
@@ -361,7 +788,7 @@ describe("WorthHub", () => {
 
       // Agent 2 reveals
       const [commitPDA2] = PublicKey.findProgramAddressSync(
-        [Buffer.from("commitment"), topicPDA.toBuffer(), agent2.publicKey.toBuffer()],
+        [Buffer.from("commitment"), topicPDA.toBuffer(), agent2.publicKey.toBuffer(), nonceSeed(0)],
         program.programId
       );
 
@@ -390,7 +817,7 @@ describe("WorthHub", () => {
       const wrongSalt = randomSalt();
 
       const [commitPDA3] = PublicKey.findProgramAddressSync(
-        [Buffer.from("commitment"), topicPDA.toBuffer(), agent3.publicKey.toBuffer()],
+        [Buffer.from("commitment"), topicPDA.toBuffer(), agent3.publicKey.toBuffer(), nonceSeed(0)],
         program.programId
       );
 
@@ -431,7 +858,7 @@ describe("WorthHub", () => {
 
       try {
         await program.methods
-          .finalize(new BN(truthValue))
+          .finalize(new BN(truthValue), null, null)
           .accounts({
             oracleAuthority: agent1.publicKey, // wrong authority
             topic: topicPDA,
@@ -448,7 +875,7 @@ describe("WorthHub", () => {
       const truthValue = 151_000_000; // $151.00
 
       await program.methods
-        .finalize(new BN(truthValue))
+        .finalize(new BN(truthValue), null, null)
         .accounts({
           oracleAuthority: oracleAuthority.publicKey,
           topic: topicPDA,
@@ -473,15 +900,15 @@ describe("WorthHub", () => {
 
       // Build remaining accounts
       const [commitPDA1] = PublicKey.findProgramAddressSync(
-        [Buffer.from("commitment"), topicPDA.toBuffer(), agent1.publicKey.toBuffer()],
+        [Buffer.from("commitment"), topicPDA.toBuffer(), agent1.publicKey.toBuffer(), nonceSeed(0)],
         program.programId
       );
       const [commitPDA2] = PublicKey.findProgramAddressSync(
-        [Buffer.from("commitment"), topicPDA.toBuffer(), agent2.publicKey.toBuffer()],
+        [Buffer.from("commitment"), topicPDA.toBuffer(), agent2.publicKey.toBuffer(), nonceSeed(0)],
         program.programId
       );
       const [commitPDA3] = PublicKey.findProgramAddressSync(
-        [Buffer.from("commitment"), topicPDA.toBuffer(), agent3.publicKey.toBuffer()],
+        [Buffer.from("commitment"), topicPDA.toBuffer(), agent3.publicKey.toBuffer(), nonceSeed(0)],
         program.programId
       );
 
@@ -490,7 +917,9 @@ describe("WorthHub", () => {
         .accounts({
           authority: authority.publicKey,
           topic: topicPDA,
+          topicAuthority: authority.publicKey,
           vault: vaultPDA,
+          truthHistory: await truthHistoryOf(topicPDA),
           systemProgram: SystemProgram.programId,
         })
         .remainingAccounts([
@@ -569,7 +998,9 @@ describe("WorthHub", () => {
           "TEST",
           new BN(now + 5), // 5 seconds
           new BN(now + 10), // 10 seconds
-          new BN(10_000_000)
+          new BN(10_000_000),
+          topicOptions(),
+          null
         )
         .accounts({
           authority: authority.publicKey,
@@ -586,17 +1017,19 @@ describe("WorthHub", () => {
       const salt = randomSalt();
       const hash = computeHash(prediction, salt, agent1.publicKey);
       const [commitPDA] = PublicKey.findProgramAddressSync(
-        [Buffer.from("commitment"), singleTopicPDA.toBuffer(), agent1.publicKey.toBuffer()],
+        [Buffer.from("commitment"), singleTopicPDA.toBuffer(), agent1.publicKey.toBuffer(), nonceSeed(0)],
         program.programId
       );
       const stakeAmount = 50_000_000;
 
       await program.methods
-        .commit(hash, new BN(stakeAmount))
+        .commit(hash, new BN(stakeAmount), null, null, false, null)
         .accounts({
           participant: agent1.publicKey,
+          rentPayer: agent1.publicKey,
           topic: singleTopicPDA,
           commitment: commitPDA,
+          participantState: deriveParticipantState(singleTopicPDA, agent1.publicKey),
           vault: singleVaultPDA,
           systemProgram: SystemProgram.programId,
         })
@@ -622,7 +1055,7 @@ describe("WorthHub", () => {
 
       // Finalize
       await program.methods
-        .finalize(new BN(100_000_000))
+        .finalize(new BN(100_000_000), null, null)
         .accounts({
           oracleAuthority: oracleAuthority.publicKey,
           topic: singleTopicPDA,
@@ -638,7 +1071,9 @@ describe("WorthHub", () => {
         .accounts({
           authority: authority.publicKey,
           topic: singleTopicPDA,
+          topicAuthority: authority.publicKey,
           vault: singleVaultPDA,
+          truthHistory: await truthHistoryOf(singleTopicPDA),
           systemProgram: SystemProgram.programId,
         })
         .remainingAccounts([
@@ -658,4 +1093,3975 @@ describe("WorthHub", () => {
       console.log(`    Single participant gets back: ${gain / LAMPORTS_PER_SOL} SOL (rent reserved: ${rentExempt / LAMPORTS_PER_SOL} SOL)`);
     });
   });
+
+  // ─── Test 7: Settle Authority ────────────────────────────────────
+
+  describe("settle_authority", () => {
+    const keeper = Keypair.generate();
+    const stranger = Keypair.generate();
+    let topics: [PublicKey, PublicKey][];
+
+    before(async function () {
+      this.timeout(60000);
+      await Promise.all([airdrop(keeper.publicKey, 1), airdrop(stranger.publicKey, 1)]);
+      topics = await Promise.all(
+        [10, 11, 12].map((id) =>
+          runToFinalized(id, [agent1, agent2], [100_000_000, 110_000_000], 104_000_000, {
+            settleAuthority: keeper.publicKey,
+          })
+        )
+      );
+    });
+
+    it("stores the settle authority at creation", async () => {
+      const topic = await program.account.topic.fetch(topics[0][0]);
+      expect(topic.settleAuthority.toBase58()).to.equal(keeper.publicKey.toBase58());
+    });
+
+    it("rejects an unrelated signer", async () => {
+      try {
+        await settleAs(topics[0][0], topics[0][1], stranger, [agent1, agent2]);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("UnauthorizedSettler");
+      }
+    });
+
+    it("accepts the keeper, the topic authority, and the oracle", async () => {
+      const settlers = [keeper, authority, oracleAuthority];
+      for (let i = 0; i < settlers.length; i++) {
+        await settleAs(topics[i][0], topics[i][1], settlers[i], [agent1, agent2]);
+        const topic = await program.account.topic.fetch(topics[i][0]);
+        expect(topic.status).to.have.property("settled");
+      }
+    });
+
+    it("defaults to no settle authority when omitted", async () => {
+      const [topic] = await createTestTopic(13);
+      const data = await program.account.topic.fetch(topic);
+      expect(data.settleAuthority.toBase58()).to.equal(PublicKey.default.toBase58());
+    });
+  });
+
+  // ─── Test 8: Reveal Accounting ───────────────────────────────────
+
+  describe("reveal accounting", () => {
+    it("reveal_count reaches commitment_count and double reveals are rejected", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(20);
+      const agents = [agent1, agent2, agent3];
+      const salts = agents.map(() => randomSalt());
+      for (let i = 0; i < agents.length; i++) {
+        await commitTo(topic, vault, agents[i], 100_000_000 + i, salts[i], 20_000_000);
+      }
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      for (let i = 0; i < agents.length; i++) {
+        await revealTo(topic, agents[i], 100_000_000 + i, salts[i]);
+      }
+
+      let data = await program.account.topic.fetch(topic);
+      expect(data.revealCount).to.equal(data.commitmentCount);
+      expect(data.revealCount).to.equal(3);
+
+      try {
+        await revealTo(topic, agent1, 100_000_000, salts[0]);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("AlreadyRevealed");
+      }
+      data = await program.account.topic.fetch(topic);
+      expect(data.revealCount).to.equal(3);
+    });
+  });
+
+  // ─── Test 9: Scoring Weight ──────────────────────────────────────
+
+  describe("scoring weight", () => {
+    it("defaults weight to the stake amount", async () => {
+      const commitment = await program.account.commitment.fetch(
+        deriveCommitment(topicPDA, agent1.publicKey)
+      );
+      expect(commitment.weight.toNumber()).to.equal(commitment.stakeAmount.toNumber());
+    });
+
+    it("rejects a zero weight", async () => {
+      const [topic, vault] = await createTestTopic(30);
+      try {
+        await commitTo(topic, vault, agent1, 100_000_000, randomSalt(), 20_000_000, 0);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidWeight");
+      }
+    });
+
+    it("uses weight rather than stake for consensus", async function () {
+      this.timeout(30000);
+      // Equal stakes put the stake-weighted consensus exactly on the truth
+      // (105), leaving nobody with a score. With agent2 weighted 1000x the
+      // consensus sits near 110, so agent1's low call is on the right side.
+      const [topic, vault] = await createTestTopic(31);
+      const stake = 50_000_000;
+      const salts = [randomSalt(), randomSalt(), randomSalt()];
+      await commitTo(topic, vault, agent1, 100_000_000, salts[0], stake, 1);
+      await commitTo(topic, vault, agent2, 110_000_000, salts[1], stake, 1_000);
+      await commitTo(topic, vault, agent3, 90_000_000, salts[2], stake); // never reveals
+
+      const stored = await program.account.commitment.fetch(deriveCommitment(topic, agent2.publicKey));
+      expect(stored.weight.toNumber()).to.equal(1_000);
+      expect(stored.stakeAmount.toNumber()).to.equal(stake);
+
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      await revealTo(topic, agent1, 100_000_000, salts[0]);
+      await revealTo(topic, agent2, 110_000_000, salts[1]);
+      await sleepUntil(created.revealDeadline.toNumber());
+      await finalizeAs(topic, 105_000_000);
+
+      const before1 = await provider.connection.getBalance(agent1.publicKey);
+      const before2 = await provider.connection.getBalance(agent2.publicKey);
+      await settleAs(topic, vault, authority, [agent1, agent2, agent3]);
+      const gain1 = (await provider.connection.getBalance(agent1.publicKey)) - before1;
+      const gain2 = (await provider.connection.getBalance(agent2.publicKey)) - before2;
+
+      // Payouts are scaled down slightly to keep the vault rent-exempt
+      expect(gain1).to.be.greaterThan(stake * 1.5);
+      expect(gain2).to.be.closeTo(stake, 1_000_000);
+    });
+  });
+
+  // ─── Test 10: Refund Participant ─────────────────────────────────
+
+  describe("refund_participant", () => {
+    async function refund(topic: PublicKey, vault: PublicKey, agent: Keypair) {
+      await program.methods
+        .refundParticipant()
+        .accounts({
+          authority: authority.publicKey,
+          topic,
+          commitment: deriveCommitment(topic, agent.publicKey),
+          participant: agent.publicKey,
+          stakePayer: agent.publicKey,
+          vault,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+    }
+
+    it("rejects a refund before the timeout", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await runToFinalized(40, [agent1], [100_000_000], 100_000_000);
+      try {
+        await refund(topic, vault, agent1);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("RefundTimeoutNotReached");
+      }
+    });
+
+    it("rejects a refund timeout under the minimum", async () => {
+      try {
+        await createTestTopic(857, { options: { refundTimeout: new BN(MIN_REFUND_TIMEOUT - 1) } });
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidDeadlines");
+      }
+    });
+
+    it("never refunds a non-revealer, whose stake is forfeit", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(858, { options: { refundTimeout: new BN(MIN_REFUND_TIMEOUT) } });
+      const salts = [randomSalt(), randomSalt()];
+      await commitTo(topic, vault, agent1, 100_000_000, salts[0], 50_000_000);
+      await commitTo(topic, vault, agent2, 110_000_000, salts[1], 50_000_000);
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      // agent2 never reveals
+      await revealTo(topic, agent1, 100_000_000, salts[0]);
+      await sleepUntil(created.revealDeadline.toNumber());
+      await finalizeAs(topic, 104_000_000);
+
+      try {
+        await refund(topic, vault, agent2);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("NotRevealed");
+      }
+    });
+
+    it("refunds the stake once and never double-pays", async function () {
+      this.timeout(30000 + MIN_REFUND_TIMEOUT * 1000);
+      const stake = 50_000_000;
+      const [topic, vault] = await runToFinalized(
+        41,
+        [agent1, agent2],
+        [100_000_000, 110_000_000],
+        104_000_000,
+        { options: { refundTimeout: new BN(MIN_REFUND_TIMEOUT) } }
+      );
+      const { finalizedAt } = await program.account.topic.fetch(topic);
+      await sleepUntil(finalizedAt.toNumber() + MIN_REFUND_TIMEOUT);
+
+      let before = await provider.connection.getBalance(agent1.publicKey);
+      await refund(topic, vault, agent1);
+      expect((await provider.connection.getBalance(agent1.publicKey)) - before).to.equal(stake);
+      const commitment = await program.account.commitment.fetch(deriveCommitment(topic, agent1.publicKey));
+      expect(commitment.settled).to.be.true;
+
+      try {
+        await refund(topic, vault, agent1);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("CommitmentAlreadySettled");
+      }
+
+      // A later settle over the full set skips the refunded commitment
+      before = await provider.connection.getBalance(agent1.publicKey);
+      await settleAs(topic, vault, authority, [agent1, agent2]);
+      expect(await provider.connection.getBalance(agent1.publicKey)).to.equal(before);
+    });
+  });
+
+  // ─── Test 11: Verify Reveal ──────────────────────────────────────
+
+  describe("verify_reveal", () => {
+    const prediction = 123_000_000;
+    const salt = randomSalt();
+    let commitment: PublicKey;
+
+    before(async () => {
+      const [topic, vault] = await createTestTopic(50);
+      await commitTo(topic, vault, agent1, prediction, salt, 20_000_000);
+      commitment = deriveCommitment(topic, agent1.publicKey);
+    });
+
+    it("reports a matching pre-image", async () => {
+      const matches = await program.methods
+        .verifyReveal(new BN(prediction), Array.from(salt), agent1.publicKey)
+        .accounts({ commitment })
+        .view();
+      expect(matches).to.be.true;
+    });
+
+    it("reports a non-matching pre-image", async () => {
+      const matches = await program.methods
+        .verifyReveal(new BN(prediction + 1), Array.from(salt), agent1.publicKey)
+        .accounts({ commitment })
+        .view();
+      expect(matches).to.be.false;
+    });
+  });
+
+  // ─── Test 12: Allowed Values ─────────────────────────────────────
+
+  describe("allowed_values", () => {
+    it("accepts an allowed category and rejects an off-grid value", async function () {
+      this.timeout(30000);
+      const categories = Array.from({ length: 10 }, (_, i) => i);
+      const [topic, vault] = await createTestTopic(60);
+      await addAllowedValues(topic, categories);
+      const salt1 = randomSalt();
+      const salt2 = randomSalt();
+      await commitTo(topic, vault, agent1, 3, salt1, 20_000_000);
+      await commitTo(topic, vault, agent2, 42, salt2, 20_000_000);
+
+      const created = await program.account.topic.fetch(topic);
+      expect(created.config.toBase58()).to.equal(deriveTopicConfig(topic).toBase58());
+      const config = await program.account.topicConfig.fetch(created.config);
+      expect(config.allowedValues.map((v: BN) => v.toNumber())).to.deep.equal(categories);
+      await sleepUntil(created.commitDeadline.toNumber());
+
+      await revealTo(topic, agent1, 3, salt1);
+      try {
+        await revealTo(topic, agent2, 42, salt2);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidPredictionValue");
+      }
+    });
+
+    it("rejects new values once the topic has commitments", async () => {
+      const [topic, vault] = await createTestTopic(61);
+      await addAllowedValues(topic, [1, 2]);
+      await commitTo(topic, vault, agent1, 1, randomSalt(), 20_000_000);
+      try {
+        await addAllowedValues(topic, [3]);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("TopicHasCommitments");
+      }
+    });
+  });
+
+  // ─── Test 13: Manual Reveal Gate ─────────────────────────────────
+
+  describe("open_reveal", () => {
+    it("holds reveals until the oracle opens the phase", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(70, {
+        options: { manualRevealGate: true },
+      });
+      const salt = randomSalt();
+      await commitTo(topic, vault, agent1, 100_000_000, salt, 20_000_000);
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+
+      try {
+        await revealTo(topic, agent1, 100_000_000, salt);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("RevealNotOpened");
+      }
+
+      try {
+        await program.methods
+          .openReveal()
+          .accounts({ oracleAuthority: agent2.publicKey, topic })
+          .signers([agent2])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("UnauthorizedOracle");
+      }
+
+      await program.methods
+        .openReveal()
+        .accounts({ oracleAuthority: oracleAuthority.publicKey, topic })
+        .signers([oracleAuthority])
+        .rpc();
+      let data = await program.account.topic.fetch(topic);
+      expect(data.status).to.have.property("revealing");
+      expect(data.revealOpenedAt.toNumber()).to.be.greaterThan(0);
+
+      await revealTo(topic, agent1, 100_000_000, salt);
+      data = await program.account.topic.fetch(topic);
+      expect(data.revealCount).to.equal(1);
+    });
+  });
+
+  // ─── Test 14: Void Topic ─────────────────────────────────────────
+
+  describe("void_topic", () => {
+    it("refunds three committers and closes the topic", async () => {
+      const [topic, vault] = await createTestTopic(80);
+      const agents = [agent1, agent2, agent3];
+      const stake = 30_000_000;
+      for (const agent of agents) {
+        await commitTo(topic, vault, agent, 100_000_000, randomSalt(), stake);
+      }
+
+      const before = await Promise.all(
+        agents.map((a) => provider.connection.getBalance(a.publicKey))
+      );
+      const rents = await Promise.all(
+        agents.map(async (a) =>
+          (await provider.connection.getBalance(deriveCommitment(topic, a.publicKey))) +
+          (await provider.connection.getBalance(deriveParticipantState(topic, a.publicKey)))
+        )
+      );
+
+      await program.methods
+        .voidTopic()
+        .accounts({
+          authority: authority.publicKey,
+          topic,
+          vault,
+          topicConfig: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts([
+          ...agents.flatMap((a) => [
+            { pubkey: deriveCommitment(topic, a.publicKey), isSigner: false, isWritable: true },
+            { pubkey: a.publicKey, isSigner: false, isWritable: true },
+            { pubkey: a.publicKey, isSigner: false, isWritable: true },
+            { pubkey: a.publicKey, isSigner: false, isWritable: true },
+          ]),
+          ...agents.flatMap((a) => [
+            { pubkey: deriveParticipantState(topic, a.publicKey), isSigner: false, isWritable: true },
+            { pubkey: a.publicKey, isSigner: false, isWritable: true },
+          ]),
+        ])
+        .signers([authority])
+        .rpc();
+
+      for (let i = 0; i < agents.length; i++) {
+        const after = await provider.connection.getBalance(agents[i].publicKey);
+        expect(after - before[i]).to.equal(stake + rents[i]);
+        expect(
+          await provider.connection.getAccountInfo(deriveCommitment(topic, agents[i].publicKey))
+        ).to.be.null;
+        expect(
+          await provider.connection.getAccountInfo(deriveParticipantState(topic, agents[i].publicKey))
+        ).to.be.null;
+      }
+      expect(await program.account.topic.fetchNullable(topic)).to.be.null;
+      expect(await provider.connection.getBalance(vault)).to.equal(0);
+    });
+
+    it("rejects voiding without every commitment", async () => {
+      const [topic, vault] = await createTestTopic(81);
+      await commitTo(topic, vault, agent1, 100_000_000, randomSalt(), 20_000_000);
+      await commitTo(topic, vault, agent2, 100_000_000, randomSalt(), 20_000_000);
+      try {
+        await program.methods
+          .voidTopic()
+          .accounts({
+            authority: authority.publicKey,
+            topic,
+            vault,
+            topicConfig: null,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts([
+            { pubkey: deriveCommitment(topic, agent1.publicKey), isSigner: false, isWritable: true },
+            { pubkey: agent1.publicKey, isSigner: false, isWritable: true },
+            { pubkey: agent1.publicKey, isSigner: false, isWritable: true },
+            { pubkey: agent1.publicKey, isSigner: false, isWritable: true },
+          ])
+          .signers([authority])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("MissingCommitments");
+      }
+    });
+  });
+
+  // ─── Test 15: Deadline Helpers ───────────────────────────────────
+
+  describe("time_until deadlines", () => {
+    async function remaining(topic: PublicKey): Promise<[number, number]> {
+      const commit = await program.methods.timeUntilCommitDeadline().accounts({ topic }).view();
+      const reveal = await program.methods.timeUntilRevealDeadline().accounts({ topic }).view();
+      return [commit.toNumber(), reveal.toNumber()];
+    }
+
+    it("counts down through each phase and floors at zero", async function () {
+      this.timeout(30000);
+      const [topic] = await createTestTopic(90, { commitIn: 4, revealIn: 8 });
+      const data = await program.account.topic.fetch(topic);
+
+      let [commitLeft, revealLeft] = await remaining(topic);
+      expect(commitLeft).to.be.within(1, 4);
+      expect(revealLeft).to.be.within(commitLeft + 3, 8);
+
+      await sleepUntil(data.commitDeadline.toNumber());
+      [commitLeft, revealLeft] = await remaining(topic);
+      expect(commitLeft).to.equal(0);
+      expect(revealLeft).to.be.within(1, 4);
+
+      await sleepUntil(data.revealDeadline.toNumber());
+      [commitLeft, revealLeft] = await remaining(topic);
+      expect(commitLeft).to.equal(0);
+      expect(revealLeft).to.equal(0);
+    });
+  });
+
+  // ─── Test 16: Encrypted Prediction ───────────────────────────────
+
+  describe("encrypted_prediction", () => {
+    it("round-trips the audit blob through commit and settle without affecting payouts", async function () {
+      this.timeout(30000);
+      const blob = Array.from({ length: 64 }, (_, i) => (i * 7 + 3) % 256);
+      const [topic, vault] = await createTestTopic(100);
+      const salts = [randomSalt(), randomSalt()];
+      const stake = 40_000_000;
+      await commitTo(topic, vault, agent1, 100_000_000, salts[0], stake, null, blob);
+      await commitTo(topic, vault, agent2, 100_000_000, salts[1], stake);
+
+      const c1 = deriveCommitment(topic, agent1.publicKey);
+      const c2 = deriveCommitment(topic, agent2.publicKey);
+      expect((await program.account.commitment.fetch(c1)).encryptedPrediction).to.deep.equal(blob);
+      expect((await program.account.commitment.fetch(c2)).encryptedPrediction).to.deep.equal(
+        new Array(64).fill(0)
+      );
+
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      await revealTo(topic, agent1, 100_000_000, salts[0]);
+      await revealTo(topic, agent2, 100_000_000, salts[1]);
+      await sleepUntil(created.revealDeadline.toNumber());
+      await finalizeAs(topic, 100_000_000);
+
+      const before = await Promise.all([agent1, agent2].map((a) => provider.connection.getBalance(a.publicKey)));
+      await settleAs(topic, vault, authority, [agent1, agent2]);
+      const gain1 = (await provider.connection.getBalance(agent1.publicKey)) - before[0];
+      const gain2 = (await provider.connection.getBalance(agent2.publicKey)) - before[1];
+
+      // Same prediction and stake: the blob must not change the outcome
+      expect(gain1).to.be.closeTo(gain2, 1);
+      expect((await program.account.commitment.fetch(c1)).encryptedPrediction).to.deep.equal(blob);
+    });
+  });
+
+  // ─── Test 17: Reveal Window Cap ──────────────────────────────────
+
+  describe("max reveal window", () => {
+    const MAX_REVEAL_WINDOW = 30 * 24 * 60 * 60;
+
+    it("accepts a reveal window exactly at the cap", async () => {
+      const [topic] = await createTestTopic(110, {
+        commitIn: 60,
+        revealIn: 60 + MAX_REVEAL_WINDOW,
+      });
+      const data = await program.account.topic.fetch(topic);
+      expect(data.revealDeadline.sub(data.commitDeadline).toNumber()).to.equal(MAX_REVEAL_WINDOW);
+    });
+
+    it("rejects a reveal window one second over the cap", async () => {
+      try {
+        await createTestTopic(111, { commitIn: 60, revealIn: 61 + MAX_REVEAL_WINDOW });
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidDeadlines");
+      }
+    });
+  });
+
+  // ─── Test 18: Settled Payout Accounting ──────────────────────────
+
+  describe("total_settled_payout", () => {
+    it("equals the sum of participant balance increases", async function () {
+      this.timeout(30000);
+      const agents = [agent1, agent2, agent3];
+      const [topic, vault] = await runToFinalized(
+        120,
+        agents,
+        [100_000_000, 104_000_000, 110_000_000],
+        103_000_000
+      );
+      const before = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+      await settleAs(topic, vault, authority, agents);
+      const after = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+      const observed = after.reduce((sum, bal, i) => sum + (bal - before[i]), 0);
+
+      const data = await program.account.topic.fetch(topic);
+      expect(data.totalSettledPayout.toNumber()).to.equal(observed);
+      expect(observed).to.be.greaterThan(0);
+    });
+  });
+
+  // ─── Test 19: Reveal After Finalize ──────────────────────────────
+
+  describe("reveal after finalize", () => {
+    it("rejects with RevealAfterFinalize", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(130);
+      const salt = randomSalt();
+      await commitTo(topic, vault, agent1, 100_000_000, salt, 20_000_000);
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.revealDeadline.toNumber());
+      await finalizeAs(topic, 100_000_000);
+
+      try {
+        await revealTo(topic, agent1, 100_000_000, salt);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("RevealAfterFinalize");
+      }
+    });
+  });
+
+  // ─── Test 20: Reveal Without Commitment ──────────────────────────
+
+  describe("reveal without commitment", () => {
+    it("rejects with CommitmentNotFound", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(140);
+      await commitTo(topic, vault, agent1, 100_000_000, randomSalt(), 20_000_000);
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+
+      try {
+        await revealTo(topic, agent2, 100_000_000, randomSalt());
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("CommitmentNotFound");
+      }
+    });
+  });
+
+  // ─── Test 21: Interval Scoring ───────────────────────────────────
+
+  describe("interval scoring", () => {
+    it("pays a tight correct interval more than a wide one", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(150, {
+        options: { rewardMode: { intervalScoring: {} } },
+      });
+      const intervals: [Keypair, number, number][] = [
+        [agent1, 50_000_000, 150_000_000],
+        [agent2, 99_000_000, 101_000_000],
+      ];
+      const salts = intervals.map(() => randomSalt());
+      for (let i = 0; i < intervals.length; i++) {
+        const [agent, low, high] = intervals[i];
+        await program.methods
+          .commit(computeIntervalHash(low, high, salts[i], agent.publicKey), new BN(50_000_000), null, null, false, null)
+          .accounts({
+            participant: agent.publicKey,
+            rentPayer: agent.publicKey,
+            topic,
+            commitment: deriveCommitment(topic, agent.publicKey),
+            participantState: deriveParticipantState(topic, agent.publicKey),
+            vault,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([agent])
+          .rpc();
+      }
+      // agent3 forfeits, funding the pool
+      await commitTo(topic, vault, agent3, 100_000_000, randomSalt(), 50_000_000);
+
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      for (let i = 0; i < intervals.length; i++) {
+        const [agent, low, high] = intervals[i];
+        await program.methods
+          .revealInterval(new BN(low), new BN(high), Array.from(salts[i]))
+          .accounts({
+            participant: agent.publicKey,
+            topic,
+            commitment: deriveCommitment(topic, agent.publicKey),
+          })
+          .signers([agent])
+          .rpc();
+      }
+      const tight = await program.account.commitment.fetch(deriveCommitment(topic, agent2.publicKey));
+      expect(tight.intervalLow.toNumber()).to.equal(99_000_000);
+      expect(tight.intervalHigh.toNumber()).to.equal(101_000_000);
+      expect(tight.predictionValue.toNumber()).to.equal(100_000_000);
+
+      await sleepUntil(created.revealDeadline.toNumber());
+      await finalizeAs(topic, 100_000_000);
+
+      const before = await Promise.all([agent1, agent2].map((a) => provider.connection.getBalance(a.publicKey)));
+      await settleAs(topic, vault, authority, [agent1, agent2, agent3]);
+      const after = await Promise.all([agent1, agent2].map((a) => provider.connection.getBalance(a.publicKey)));
+      expect(after[1] - before[1]).to.be.greaterThan(after[0] - before[0]);
+    });
+  });
+
+  // ─── Test 22: Reveal Rebate ──────────────────────────────────────
+
+  describe("reveal rebate", () => {
+    it("rejects a rebate above 10000 bps", async () => {
+      try {
+        await createTestTopic(160, { options: { revealRebateBps: 10_001 } });
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidBasisPoints");
+      }
+    });
+
+    it("pays every revealer a share of the loser pool even when nobody scores", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(161, { options: { revealRebateBps: 5_000 } });
+      const salts = [randomSalt(), randomSalt(), randomSalt()];
+      await commitTo(topic, vault, agent1, 100_000_000, salts[0], 50_000_000);
+      await commitTo(topic, vault, agent2, 100_000_000, salts[1], 50_000_000);
+      await commitTo(topic, vault, agent3, 100_000_000, salts[2], 50_000_000);
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      await revealTo(topic, agent1, 100_000_000, salts[0]);
+      await revealTo(topic, agent2, 100_000_000, salts[1]);
+      await sleepUntil(created.revealDeadline.toNumber());
+      // Truth equals consensus, so every score is zero
+      await finalizeAs(topic, 100_000_000);
+
+      const before = await provider.connection.getBalance(agent1.publicKey);
+      await settleAs(topic, vault, authority, [agent1, agent2, agent3]);
+      const after = await provider.connection.getBalance(agent1.publicKey);
+      expect(after - before).to.be.greaterThan(50_000_000);
+    });
+  });
+
+  // ─── Test 23: Symbol Validation ──────────────────────────────────
+
+  describe("symbol validation", () => {
+    it("accepts an uppercase ASCII symbol with digits and dashes", async () => {
+      const [topic] = await createTestTopic(170, { symbol: "BTC-USD2" });
+      const data = await program.account.topic.fetch(topic);
+      expect(data.symbol).to.equal("BTC-USD2");
+    });
+
+    it("rejects a fullwidth look-alike symbol", async () => {
+      try {
+        await createTestTopic(171, { symbol: "\uFF22\uFF34\uFF23-USD" });
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidSymbol");
+      }
+    });
+
+    it("rejects lowercase symbols", async () => {
+      try {
+        await createTestTopic(172, { symbol: "btc-usd" });
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidSymbol");
+      }
+    });
+  });
+
+  // ─── Test 24: Topic Snapshot ─────────────────────────────────────
+
+  describe("topic_snapshot", () => {
+    it("matches the raw topic account", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(180);
+      await commitTo(topic, vault, agent1, 100_000_000, randomSalt(), 20_000_000);
+      await commitTo(topic, vault, agent2, 101_000_000, randomSalt(), 30_000_000);
+
+      const snapshot = await program.methods.topicSnapshot().accounts({ topic }).view();
+      const data = await program.account.topic.fetch(topic);
+
+      expect(snapshot.status).to.deep.equal(data.status);
+      expect(snapshot.commitDeadline.toNumber()).to.equal(data.commitDeadline.toNumber());
+      expect(snapshot.revealDeadline.toNumber()).to.equal(data.revealDeadline.toNumber());
+      expect(snapshot.commitmentCount).to.equal(data.commitmentCount);
+      expect(snapshot.revealCount).to.equal(data.revealCount);
+      expect(snapshot.totalStake.toNumber()).to.equal(data.totalStake.toNumber());
+      expect(snapshot.totalStake.toNumber()).to.equal(50_000_000);
+      expect(snapshot.truthValue.toNumber()).to.equal(data.truthValue.toNumber());
+      expect(Math.abs(snapshot.now.toNumber() - Math.floor(Date.now() / 1000))).to.be.lessThan(30);
+    });
+  });
+
+  // ─── Test 25: Extreme Values ─────────────────────────────────────
+
+  describe("i64 extremes", () => {
+    const I64_MIN = "-9223372036854775808";
+
+    it("rejects i64::MIN as a prediction and as a truth value", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(190);
+      const salt = randomSalt();
+      await commitTo(topic, vault, agent1, I64_MIN, salt, 20_000_000);
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+
+      try {
+        await program.methods
+          .reveal(new BN(I64_MIN), Array.from(salt))
+          .accounts({
+            participant: agent1.publicKey,
+            topic,
+            commitment: deriveCommitment(topic, agent1.publicKey),
+          })
+          .signers([agent1])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ValueOutOfRange");
+      }
+
+      await sleepUntil(created.revealDeadline.toNumber());
+      await expectFinalizeRejected(
+        topic,
+        () =>
+          program.methods
+            .finalize(new BN(I64_MIN), null, null)
+            .accounts({ oracleAuthority: oracleAuthority.publicKey, topic })
+            .signers([oracleAuthority])
+            .rpc(),
+        "ValueOutOfRange"
+      );
+    });
+  });
+
+  // ─── Test 26: Keeper Reward ──────────────────────────────────────
+
+  describe("keeper reward", () => {
+    it("pays the settle signer its share of the loser pool", async function () {
+      this.timeout(30000);
+      const keeper = Keypair.generate();
+      await airdrop(keeper.publicKey, 1);
+      // A lone revealer sits on the consensus, so keep the unscored pool
+      // released rather than refunded
+      const [topic, vault] = await createTestTopic(200, {
+        settleAuthority: keeper.publicKey,
+        options: { keeperRewardBps: 1_000, unanimousPolicy: { sweep: {} } },
+      });
+      const salt = randomSalt();
+      await commitTo(topic, vault, agent1, 100_000_000, salt, 50_000_000);
+      // agent2 never reveals: its 50M stake is the loser pool
+      await commitTo(topic, vault, agent2, 100_000_000, randomSalt(), 50_000_000);
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      await revealTo(topic, agent1, 100_000_000, salt);
+      await sleepUntil(created.revealDeadline.toNumber());
+      await finalizeAs(topic, 100_000_000);
+
+      const before = await provider.connection.getBalance(keeper.publicKey);
+      const sig = await settleAs(topic, vault, keeper, [agent1, agent2]);
+      const after = await provider.connection.getBalance(keeper.publicKey);
+
+      // 10% of the 50M pool
+      const settled = (await eventsOf(sig)).topicSettled;
+      expect(settled.keeperReward.toNumber()).to.equal(5_000_000);
+      expect(after - before).to.be.greaterThan(5_000_000 - 10_000);
+    });
+  });
+
+  // ─── Test 27: Public Settle ──────────────────────────────────────
+
+  describe("public settle", () => {
+    it("lets an unrelated signer settle only when public_settle is set", async function () {
+      this.timeout(60000);
+      const stranger = Keypair.generate();
+      await airdrop(stranger.publicKey, 1);
+      const [open, openVault] = await runToFinalized(210, [agent1], [100_000_000], 100_000_000, {
+        options: { publicSettle: true },
+      });
+      const [closed, closedVault] = await runToFinalized(211, [agent1], [100_000_000], 100_000_000);
+
+      try {
+        await settleAs(closed, closedVault, stranger, [agent1]);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("UnauthorizedSettler");
+      }
+
+      await settleAs(open, openVault, stranger, [agent1]);
+      const data = await program.account.topic.fetch(open);
+      expect(data.status).to.have.property("settled");
+    });
+  });
+
+  // ─── Test 28: Cancel and Re-commit ───────────────────────────────
+
+  describe("cancel_commitment", () => {
+    async function cancelAs(topic: PublicKey, vault: PublicKey, agent: Keypair, nonce: number) {
+      await program.methods
+        .cancelCommitment()
+        .accounts({
+          participant: agent.publicKey,
+          topic,
+          commitment: deriveCommitment(topic, agent.publicKey, nonce),
+          rentPayer: agent.publicKey,
+          stakePayer: agent.publicKey,
+          participantState: deriveParticipantState(topic, agent.publicKey),
+          vault,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([agent])
+        .rpc();
+    }
+
+    it("refunds the stake and re-commits under the next nonce", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(220, { commitIn: 8, revealIn: 14 });
+      await commitTo(topic, vault, agent1, 100_000_000, randomSalt(), 20_000_000);
+
+      const before = await provider.connection.getBalance(agent1.publicKey);
+      await cancelAs(topic, vault, agent1, 0);
+      const after = await provider.connection.getBalance(agent1.publicKey);
+      // Stake plus the closed commitment's rent, less the transaction fee
+      expect(after - before).to.be.greaterThan(20_000_000);
+
+      let data = await program.account.topic.fetch(topic);
+      expect(data.commitmentCount).to.equal(0);
+      expect(data.totalStake.toNumber()).to.equal(0);
+      expect(await provider.connection.getAccountInfo(deriveCommitment(topic, agent1.publicKey, 0))).to.be.null;
+      const state = await program.account.participantState.fetch(deriveParticipantState(topic, agent1.publicKey));
+      expect(state.commitNonce).to.equal(1);
+
+      // The old address cannot be re-initialized: the next commit must use nonce 1
+      try {
+        await commitTo(topic, vault, agent1, 105_000_000, randomSalt(), 20_000_000);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ConstraintSeeds");
+      }
+
+      const salt = randomSalt();
+      await program.methods
+        .commit(computeHash(105_000_000, salt, agent1.publicKey), new BN(30_000_000), null, null, false, null)
+        .accounts({
+          participant: agent1.publicKey,
+          rentPayer: agent1.publicKey,
+          topic,
+          participantState: deriveParticipantState(topic, agent1.publicKey),
+          commitment: deriveCommitment(topic, agent1.publicKey, 1),
+          vault,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([agent1])
+        .rpc();
+
+      const recommitted = await program.account.commitment.fetch(deriveCommitment(topic, agent1.publicKey, 1));
+      expect(recommitted.nonce).to.equal(1);
+      expect(recommitted.stakeAmount.toNumber()).to.equal(30_000_000);
+      data = await program.account.topic.fetch(topic);
+      expect(data.commitmentCount).to.equal(1);
+      expect(data.totalStake.toNumber()).to.equal(30_000_000);
+    });
+  });
+
+  // ─── Test 29: Reopen Empty Topic ─────────────────────────────────
+
+  describe("reopen_topic", () => {
+    async function reopen(topic: PublicKey, commitIn: number, revealIn: number) {
+      const now = Math.floor(Date.now() / 1000);
+      await program.methods
+        .reopenTopic(new BN(now + commitIn), new BN(now + revealIn))
+        .accounts({ authority: authority.publicKey, topic })
+        .signers([authority])
+        .rpc();
+    }
+
+    it("gives an expired topic with no commitments a new commit phase", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(230, { commitIn: 2, revealIn: 4 });
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+
+      try {
+        await commitTo(topic, vault, agent1, 100_000_000, randomSalt(), 20_000_000);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("CommitPhaseEnded");
+      }
+
+      await reopen(topic, 30, 60);
+      const reopened = await program.account.topic.fetch(topic);
+      expect(reopened.status).to.have.property("open");
+      expect(reopened.commitDeadline.toNumber()).to.be.greaterThan(created.commitDeadline.toNumber());
+
+      await commitTo(topic, vault, agent1, 100_000_000, randomSalt(), 20_000_000);
+      const data = await program.account.topic.fetch(topic);
+      expect(data.commitmentCount).to.equal(1);
+    });
+
+    it("rejects topics that already have commitments", async () => {
+      const [topic, vault] = await createTestTopic(231, { commitIn: 30, revealIn: 60 });
+      await commitTo(topic, vault, agent1, 100_000_000, randomSalt(), 20_000_000);
+      try {
+        await reopen(topic, 60, 120);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("TopicHasCommitments");
+      }
+    });
+
+    it("applies the usual deadline rules", async () => {
+      const [topic] = await createTestTopic(232, { commitIn: 30, revealIn: 60 });
+      try {
+        await reopen(topic, 60, 30);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidDeadlines");
+      }
+    });
+  });
+
+  // ─── Test 30: Settle Dry Run ─────────────────────────────────────
+
+  describe("settle_dry_run", () => {
+    it("previews exactly the payouts a real settle makes", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(240);
+      const agents = [agent1, agent2, agent3];
+      const predictions = [100_000_000, 108_000_000, 120_000_000];
+      const salts = agents.map(() => randomSalt());
+      for (let i = 0; i < agents.length; i++) {
+        await commitTo(topic, vault, agents[i], predictions[i], salts[i], 50_000_000);
+      }
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      // agent3 forfeits
+      await revealTo(topic, agent1, predictions[0], salts[0]);
+      await revealTo(topic, agent2, predictions[1], salts[1]);
+      await sleepUntil(created.revealDeadline.toNumber());
+      await finalizeAs(topic, 110_000_000);
+
+      const preview = await program.methods
+        .settleDryRun()
+        .accounts({ authority: authority.publicKey, topic, vault })
+        .remainingAccounts(
+          agents.flatMap((a) => [
+            { pubkey: deriveCommitment(topic, a.publicKey), isSigner: false, isWritable: true },
+            { pubkey: a.publicKey, isSigner: false, isWritable: true },
+          ])
+        )
+        .signers([authority])
+        .view();
+
+      let data = await program.account.topic.fetch(topic);
+      expect(data.status).to.have.property("finalized");
+      const [recordPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("settlement"), topic.toBuffer()],
+        program.programId
+      );
+      expect(await provider.connection.getAccountInfo(recordPDA)).to.be.null;
+
+      const before = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+      await settleAs(topic, vault, authority, agents);
+      const after = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+
+      expect(preview.payouts.map((p: BN) => p.toNumber())).to.deep.equal(after.map((bal, i) => bal - before[i]));
+      expect(preview.payouts[2].toNumber()).to.equal(0);
+      data = await program.account.topic.fetch(topic);
+      expect(data.totalSettledPayout.toNumber()).to.equal(
+        preview.payouts.reduce((sum: number, p: BN) => sum + p.toNumber(), 0)
+      );
+    });
+  });
+
+  // ─── Test 31: Truth Confidence Band ──────────────────────────────
+
+  describe("truth confidence band", () => {
+    it("stores the oracle's band and rejects malformed ones", async function () {
+      this.timeout(30000);
+      const [topic] = await createTestTopic(250);
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.revealDeadline.toNumber());
+
+      const finalizeWith = (low: BN | null, high: BN | null) =>
+        program.methods
+          .finalize(new BN(100_000_000), low, high)
+          .accounts({ oracleAuthority: oracleAuthority.publicKey, topic })
+          .signers([oracleAuthority])
+          .rpc();
+
+      for (const [low, high] of [
+        [new BN(99_000_000), null],
+        [new BN(101_000_000), new BN(102_000_000)],
+        [new BN(98_000_000), new BN(99_000_000)],
+      ]) {
+        await expectFinalizeRejected(topic, () => finalizeWith(low, high), "InvalidTruthBand");
+      }
+
+      await finalizeWith(new BN(98_000_000), new BN(103_000_000));
+      const data = await program.account.topic.fetch(topic);
+      expect(data.truthValue.toNumber()).to.equal(100_000_000);
+      expect(data.truthLow.toNumber()).to.equal(98_000_000);
+      expect(data.truthHigh.toNumber()).to.equal(103_000_000);
+    });
+
+    it("collapses to the point truth when no band is given", async function () {
+      this.timeout(30000);
+      const [topic] = await runToFinalized(251, [agent1], [100_000_000], 104_000_000);
+      const data = await program.account.topic.fetch(topic);
+      expect(data.truthLow.toNumber()).to.equal(104_000_000);
+      expect(data.truthHigh.toNumber()).to.equal(104_000_000);
+    });
+  });
+
+  // ─── Test 32: Max Payout Cap ─────────────────────────────────────
+
+  describe("max payout cap", () => {
+    it("rejects a cap above 10000 bps", async () => {
+      try {
+        await createTestTopic(260, { options: { maxPayoutBps: 10_001 } });
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidBasisPoints");
+      }
+    });
+
+    it("limits a lone scorer to its share of the loser pool", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(261, { options: { maxPayoutBps: 4_000 } });
+      const salts = [randomSalt(), randomSalt()];
+      await commitTo(topic, vault, agent1, 100_000_000, salts[0], 50_000_000);
+      await commitTo(topic, vault, agent2, 108_000_000, salts[1], 50_000_000);
+      // agent3 never reveals: its 50M stake is the loser pool
+      await commitTo(topic, vault, agent3, 100_000_000, randomSalt(), 50_000_000);
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      await revealTo(topic, agent1, 100_000_000, salts[0]);
+      await revealTo(topic, agent2, 108_000_000, salts[1]);
+      await sleepUntil(created.revealDeadline.toNumber());
+      // Only agent2 leans toward the truth from the 104M consensus
+      await finalizeAs(topic, 110_000_000);
+
+      const before = await provider.connection.getBalance(agent2.publicKey);
+      const sig = await settleAs(topic, vault, authority, [agent1, agent2, agent3]);
+      const after = await provider.connection.getBalance(agent2.publicKey);
+
+      // Stake back plus 40% of the 50M pool; the rest goes to the topic authority
+      expect(after - before).to.equal(50_000_000 + 20_000_000);
+      const settled = (await eventsOf(sig)).topicSettled;
+      expect(settled.totalSettledPayout.toNumber()).to.equal(120_000_000);
+    });
+  });
+
+  // ─── Test 33: Projected Payout ───────────────────────────────────
+
+  describe("projected_payout", () => {
+    it("returns what settle then pays each commitment", async function () {
+      this.timeout(30000);
+      const agents = [agent1, agent2, agent3];
+      const predictions = [100_000_000, 108_000_000, 120_000_000];
+      const salts = agents.map(() => randomSalt());
+      const [topic, vault] = await createTestTopic(270);
+      for (let i = 0; i < agents.length; i++) {
+        await commitTo(topic, vault, agents[i], predictions[i], salts[i], 50_000_000);
+      }
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      // agent3 forfeits
+      await revealTo(topic, agent1, predictions[0], salts[0]);
+      await revealTo(topic, agent2, predictions[1], salts[1]);
+      await sleepUntil(created.revealDeadline.toNumber());
+
+      const pairs = agents.flatMap((a) => [
+        { pubkey: deriveCommitment(topic, a.publicKey), isSigner: false, isWritable: false },
+        { pubkey: a.publicKey, isSigner: false, isWritable: false },
+      ]);
+      const project = (agent: Keypair) =>
+        program.methods
+          .projectedPayout()
+          .accounts({ topic, vault, commitment: deriveCommitment(topic, agent.publicKey) })
+          .remainingAccounts(pairs)
+          .view();
+
+      try {
+        await project(agent1);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("TopicNotFinalized");
+      }
+
+      await finalizeAs(topic, 110_000_000);
+      const projected = await Promise.all(agents.map(async (a) => (await project(a)).toNumber()));
+
+      const before = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+      await settleAs(topic, vault, authority, agents);
+      const after = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+
+      expect(projected).to.deep.equal(after.map((bal, i) => bal - before[i]));
+      expect(projected[2]).to.equal(0);
+    });
+  });
+
+  // ─── Test 34: Batched Reveals ────────────────────────────────────
+
+  describe("reveal_many", () => {
+    it("reveals several of one participant's commitments at once", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(280);
+      const predictions = [100_000_000, 105_000_000, 110_000_000];
+      const salts = predictions.map(() => randomSalt());
+      for (let nonce = 0; nonce < predictions.length; nonce++) {
+        await program.methods
+          .commit(computeHash(predictions[nonce], salts[nonce], agent1.publicKey), new BN(20_000_000), null, null, false, null)
+          .accounts({
+            participant: agent1.publicKey,
+            rentPayer: agent1.publicKey,
+            topic,
+            participantState: deriveParticipantState(topic, agent1.publicKey),
+            commitment: deriveCommitment(topic, agent1.publicKey, nonce),
+            vault,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([agent1])
+          .rpc();
+      }
+      const state = await program.account.participantState.fetch(deriveParticipantState(topic, agent1.publicKey));
+      expect(state.commitNonce).to.equal(3);
+
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+
+      const revealBatch = (entries: { nonce: number; prediction: number; salt: Buffer }[], accounts = entries) =>
+        program.methods
+          .revealMany(
+            entries.map((e) => ({
+              commitmentIndex: e.nonce,
+              predictionValue: new BN(e.prediction),
+              salt: Array.from(e.salt),
+            }))
+          )
+          .accounts({ participant: agent1.publicKey, topic })
+          .remainingAccounts(
+            accounts.map((e) => ({
+              pubkey: deriveCommitment(topic, agent1.publicKey, e.nonce),
+              isSigner: false,
+              isWritable: true,
+            }))
+          )
+          .signers([agent1])
+          .rpc();
+      const batch = predictions.map((prediction, nonce) => ({ nonce, prediction, salt: salts[nonce] }));
+
+      try {
+        await revealBatch(batch, batch.slice(0, 2));
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidRevealBatch");
+      }
+
+      // One bad salt fails the whole batch
+      try {
+        await revealBatch([batch[0], { ...batch[1], salt: randomSalt() }, batch[2]]);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("HashMismatch");
+      }
+      let data = await program.account.topic.fetch(topic);
+      expect(data.revealCount).to.equal(0);
+
+      await revealBatch(batch);
+      data = await program.account.topic.fetch(topic);
+      expect(data.revealCount).to.equal(3);
+      for (const { nonce, prediction } of batch) {
+        const c = await program.account.commitment.fetch(deriveCommitment(topic, agent1.publicKey, nonce));
+        expect(c.revealed).to.be.true;
+        expect(c.predictionValue.toNumber()).to.equal(prediction);
+      }
+    });
+  });
+
+  // ─── Test 35: Early Finalize ─────────────────────────────────────
+
+  describe("early finalize", () => {
+    it("lets the oracle finalize before reveal_deadline once the threshold is revealed", async function () {
+      this.timeout(30000);
+      const options = { earlyFinalizeThresholdBps: 10_000 };
+      const [full, fullVault] = await createTestTopic(290, { revealIn: 600, options });
+      const [partial, partialVault] = await createTestTopic(291, { revealIn: 600, options });
+      const salt = randomSalt();
+      await commitTo(full, fullVault, agent1, 100_000_000, salt, 20_000_000);
+      await commitTo(partial, partialVault, agent1, 100_000_000, salt, 20_000_000);
+      await commitTo(partial, partialVault, agent2, 100_000_000, randomSalt(), 20_000_000);
+      const created = await program.account.topic.fetch(full);
+      await sleepUntil(created.commitDeadline.toNumber());
+      await revealTo(full, agent1, 100_000_000, salt);
+      await revealTo(partial, agent1, 100_000_000, salt);
+
+      // Only half of partial's commitments are revealed
+      try {
+        await finalizeAs(partial, 100_000_000);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("RevealPhaseNotEnded");
+      }
+
+      await finalizeAs(full, 100_000_000);
+      const data = await program.account.topic.fetch(full);
+      expect(data.status).to.have.property("finalized");
+      expect(data.finalizedAt.toNumber()).to.be.lessThan(data.revealDeadline.toNumber());
+    });
+  });
+
+  // ─── Test 36: Consensus Snapshot ─────────────────────────────────
+
+  describe("snapshot_consensus", () => {
+    it("stores the stake-weighted consensus over every commitment", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(300);
+      const agents = [agent1, agent2, agent3];
+      const predictions = [100_000_000, 106_000_000, 130_000_000];
+      const stakes = [20_000_000, 60_000_000, 40_000_000];
+      const salts = agents.map(() => randomSalt());
+      for (let i = 0; i < agents.length; i++) {
+        await commitTo(topic, vault, agents[i], predictions[i], salts[i], stakes[i]);
+      }
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      // agent3 forfeits
+      await revealTo(topic, agent1, predictions[0], salts[0]);
+      await revealTo(topic, agent2, predictions[1], salts[1]);
+      await sleepUntil(created.revealDeadline.toNumber());
+      await finalizeAs(topic, 110_000_000);
+
+      const snapshot = (commitAgents: Keypair[]) =>
+        program.methods
+          .snapshotConsensus()
+          .accounts({ topic })
+          .remainingAccounts(
+            commitAgents.map((a) => ({
+              pubkey: deriveCommitment(topic, a.publicKey),
+              isSigner: false,
+              isWritable: false,
+            }))
+          )
+          .rpc();
+
+      for (const partial of [[agent1, agent2], [agent1, agent2, agent2]]) {
+        try {
+          await snapshot(partial);
+          expect.fail("Should have thrown");
+        } catch (err: any) {
+          expect(err.toString()).to.include("IncompleteCommitmentSet");
+        }
+      }
+
+      await snapshot(agents);
+      const data = await program.account.topic.fetch(topic);
+      const expected = Math.floor(
+        (predictions[0] * stakes[0] + predictions[1] * stakes[1]) / (stakes[0] + stakes[1])
+      );
+      expect(data.consensus.toNumber()).to.equal(expected);
+      expect(data.totalRevealedStake.toNumber()).to.equal(stakes[0] + stakes[1]);
+      expect(data.totalUnrevealedStake.toNumber()).to.equal(stakes[2]);
+      expect(data.consensusSnapshotAt.toNumber()).to.be.greaterThan(0);
+
+      try {
+        await snapshot(agents);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ConsensusAlreadySnapshotted");
+      }
+
+      // Settle reuses the stored consensus
+      const sig = await settleAs(topic, vault, authority, agents);
+      expect((await eventsOf(sig)).topicSettled.consensus.toNumber()).to.equal(expected);
+    });
+
+    it("drops the snapshot once a refund changes the stakes", async function () {
+      this.timeout(30000 + MIN_REFUND_TIMEOUT * 1000);
+      const agents = [agent1, agent2];
+      const [topic, vault] = await runToFinalized(856, agents, [100_000_000, 120_000_000], 110_000_000, {
+        options: { refundTimeout: new BN(MIN_REFUND_TIMEOUT) },
+      });
+      const snapshot = () =>
+        program.methods
+          .snapshotConsensus()
+          .accounts({ topic })
+          .remainingAccounts(
+            agents.map((a) => ({ pubkey: deriveCommitment(topic, a.publicKey), isSigner: false, isWritable: false }))
+          )
+          .rpc();
+
+      await snapshot();
+      const { consensus, finalizedAt } = await program.account.topic.fetch(topic);
+      expect(consensus.toNumber()).to.equal(110_000_000);
+
+      await sleepUntil(finalizedAt.toNumber() + MIN_REFUND_TIMEOUT);
+      await program.methods
+        .refundParticipant()
+        .accounts({
+          authority: authority.publicKey,
+          topic,
+          commitment: deriveCommitment(topic, agent1.publicKey),
+          participant: agent1.publicKey,
+          stakePayer: agent1.publicKey,
+          vault,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+      expect((await program.account.topic.fetch(topic)).consensusSnapshotAt.toNumber()).to.equal(0);
+
+      // Only agent2's stake is left to weigh
+      await snapshot();
+      expect((await program.account.topic.fetch(topic)).consensus.toNumber()).to.equal(120_000_000);
+    });
+  });
+
+  // ─── Test 37: Metadata URI ───────────────────────────────────────
+
+  describe("metadata uri", () => {
+    it("round-trips the URI, empty when unset", async () => {
+      const uri = "https://example.com/topics/310.json";
+      const [topic] = await createTestTopic(310, { metadataUri: uri });
+      const data = await program.account.topic.fetch(topic);
+      expect(data.metadataUri).to.equal(uri);
+
+      const [plain] = await createTestTopic(311);
+      expect((await program.account.topic.fetch(plain)).metadataUri).to.equal("");
+    });
+
+    it("rejects a URI over 128 bytes", async () => {
+      try {
+        await createTestTopic(312, { metadataUri: "https://example.com/" + "a".repeat(109) });
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("MetadataUriTooLong");
+      }
+    });
+  });
+
+  // ─── Test 38: Escrow Buckets ─────────────────────────────────────
+
+  describe("escrow buckets", () => {
+    it("track the vault through commit, cancel and settle", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(320, { commitIn: 8, revealIn: 14 });
+      const escrowOf = async () => {
+        const data = await program.account.topic.fetch(topic);
+        return data.escrowedStake.add(data.escrowedFees).add(data.escrowedPrize).toNumber();
+      };
+      expect(await escrowOf()).to.equal(0);
+
+      const salts = [randomSalt(), randomSalt()];
+      await commitTo(topic, vault, agent1, 100_000_000, salts[0], 20_000_000);
+      await commitTo(topic, vault, agent2, 108_000_000, salts[1], 30_000_000);
+      await commitTo(topic, vault, agent3, 100_000_000, randomSalt(), 40_000_000);
+      expect(await escrowOf()).to.equal(90_000_000);
+      expect(await provider.connection.getBalance(vault)).to.equal(90_000_000);
+
+      await program.methods
+        .cancelCommitment()
+        .accounts({
+          participant: agent3.publicKey,
+          topic,
+          commitment: deriveCommitment(topic, agent3.publicKey),
+          rentPayer: agent3.publicKey,
+          stakePayer: agent3.publicKey,
+          participantState: deriveParticipantState(topic, agent3.publicKey),
+          vault,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([agent3])
+        .rpc();
+      expect(await escrowOf()).to.equal(50_000_000);
+      expect(await provider.connection.getBalance(vault)).to.equal(50_000_000);
+
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      await revealTo(topic, agent1, 100_000_000, salts[0]);
+      await revealTo(topic, agent2, 108_000_000, salts[1]);
+      await sleepUntil(created.revealDeadline.toNumber());
+      await finalizeAs(topic, 110_000_000);
+      await settleAs(topic, vault, authority, [agent1, agent2]);
+
+      expect(await escrowOf()).to.equal(0);
+      const data = await program.account.topic.fetch(topic);
+      const vaultLeft = await provider.connection.getBalance(vault);
+      expect(data.totalSettledPayout.toNumber() + vaultLeft).to.be.at.most(50_000_000);
+    });
+  });
+
+  // ─── Test 39: Abstain ────────────────────────────────────────────
+
+  describe("abstain", () => {
+    it("returns the abstainer's stake without scoring it", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(330, { commitIn: 6, revealIn: 12 });
+      const salts = [randomSalt(), randomSalt(), randomSalt()];
+      await commitTo(topic, vault, agent1, 100_000_000, salts[0], 20_000_000);
+      await commitTo(topic, vault, agent2, 140_000_000, salts[1], 20_000_000);
+      await commitTo(topic, vault, agent3, 500_000_000, salts[2], 30_000_000);
+
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      await revealTo(topic, agent1, 100_000_000, salts[0]);
+      await revealTo(topic, agent2, 140_000_000, salts[1]);
+
+      const abstain = (salt: Buffer) =>
+        program.methods
+          .abstain(new BN(500_000_000), Array.from(salt))
+          .accounts({
+            participant: agent3.publicKey,
+            topic,
+            commitment: deriveCommitment(topic, agent3.publicKey),
+          })
+          .signers([agent3])
+          .rpc();
+
+      try {
+        await abstain(randomSalt());
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("HashMismatch");
+      }
+      await abstain(salts[2]);
+      expect((await program.account.topic.fetch(topic)).revealCount).to.equal(3);
+
+      try {
+        await revealTo(topic, agent3, 500_000_000, salts[2]);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("AlreadyAbstained");
+      }
+
+      await sleepUntil(created.revealDeadline.toNumber());
+      await finalizeAs(topic, 110_000_000);
+      const before = await provider.connection.getBalance(agent3.publicKey);
+      await settleAs(topic, vault, authority, [agent1, agent2, agent3]);
+      const after = await provider.connection.getBalance(agent3.publicKey);
+      expect(after - before).to.equal(30_000_000);
+    });
+  });
+
+  // ─── Test 40: Effective Phase ────────────────────────────────────
+
+  describe("effective phase", () => {
+    it("treats an Open topic past its commit deadline as revealing", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(340, { commitIn: 4, revealIn: 8 });
+      const salt = randomSalt();
+      await commitTo(topic, vault, agent1, 100_000_000, salt, 20_000_000);
+
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      // Nothing has moved the status on yet
+      expect((await program.account.topic.fetch(topic)).status).to.have.property("open");
+
+      try {
+        await commitTo(topic, vault, agent2, 100_000_000, randomSalt(), 20_000_000);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("CommitPhaseEnded");
+      }
+
+      await revealTo(topic, agent1, 100_000_000, salt);
+      expect((await program.account.topic.fetch(topic)).status).to.have.property("revealing");
+    });
+  });
+
+  // ─── Test 41: Co-signed Commits ──────────────────────────────────
+
+  describe("cosigner", () => {
+    it("requires the topic's cosigner to sign each commit", async function () {
+      this.timeout(30000);
+      const custodian = Keypair.generate();
+      const [topic, vault] = await createTestTopic(350, { cosigner: custodian.publicKey });
+      expect((await program.account.topic.fetch(topic)).cosigner.toBase58()).to.equal(
+        custodian.publicKey.toBase58()
+      );
+
+      const commitWith = (cosigner: Keypair | null) =>
+        program.methods
+          .commit(computeHash(100_000_000, randomSalt(), agent1.publicKey), new BN(20_000_000), null, null, false, null)
+          .accounts({
+            participant: agent1.publicKey,
+            rentPayer: agent1.publicKey,
+            topic,
+            commitment: deriveCommitment(topic, agent1.publicKey),
+            participantState: deriveParticipantState(topic, agent1.publicKey),
+            vault,
+            cosigner: cosigner?.publicKey ?? null,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers(cosigner ? [agent1, cosigner] : [agent1])
+          .rpc();
+
+      for (const cosigner of [null, Keypair.generate()]) {
+        try {
+          await commitWith(cosigner);
+          expect.fail("Should have thrown");
+        } catch (err: any) {
+          expect(err.toString()).to.include("InvalidCosigner");
+        }
+      }
+
+      await commitWith(custodian);
+      const data = await program.account.topic.fetch(topic);
+      expect(data.commitmentCount).to.equal(1);
+      expect(data.totalStake.toNumber()).to.equal(20_000_000);
+    });
+  });
+
+  // ─── Test 42: Set Min Stake ──────────────────────────────────────
+
+  describe("set_min_stake", () => {
+    async function setMinStake(topic: PublicKey, minStake: number) {
+      await program.methods
+        .setMinStake(new BN(minStake))
+        .accounts({ authority: authority.publicKey, topic })
+        .signers([authority])
+        .rpc();
+    }
+
+    it("adjusts the minimum stake before the first commit", async () => {
+      const [topic, vault] = await createTestTopic(360, { commitIn: 30, revealIn: 60 });
+      await setMinStake(topic, 50_000_000);
+      expect((await program.account.topic.fetch(topic)).minStake.toNumber()).to.equal(50_000_000);
+
+      try {
+        await commitTo(topic, vault, agent1, 100_000_000, randomSalt(), 20_000_000);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("StakeTooLow");
+      }
+
+      try {
+        await setMinStake(topic, 1);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("MinStakeBelowRentFloor");
+      }
+    });
+
+    it("rejects topics that already have commitments", async () => {
+      const [topic, vault] = await createTestTopic(361, { commitIn: 30, revealIn: 60 });
+      await commitTo(topic, vault, agent1, 100_000_000, randomSalt(), 20_000_000);
+      try {
+        await setMinStake(topic, 50_000_000);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("TopicHasCommitments");
+      }
+      expect((await program.account.topic.fetch(topic)).minStake.toNumber()).to.equal(10_000_000);
+    });
+  });
+
+  // ─── Test 43: Time Decay Disabled ────────────────────────────────
+
+  describe("time_decay_enabled", () => {
+    it("pays identical predictions the same whatever their commit order", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(370, {
+        options: { timeDecayEnabled: false },
+      });
+      const agents = [agent1, agent2, agent3];
+      const predictions = [112_000_000, 112_000_000, 90_000_000];
+      const salts = agents.map(() => randomSalt());
+      for (let i = 0; i < agents.length; i++) {
+        await commitTo(topic, vault, agents[i], predictions[i], salts[i], 30_000_000);
+      }
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      // agent3 forfeits
+      await revealTo(topic, agent1, predictions[0], salts[0]);
+      await revealTo(topic, agent2, predictions[1], salts[1]);
+      await sleepUntil(created.revealDeadline.toNumber());
+      await finalizeAs(topic, 115_000_000);
+
+      const before = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+      await settleAs(topic, vault, authority, agents);
+      const after = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+      const paid = after.map((bal, i) => bal - before[i]);
+
+      expect(paid[0]).to.be.greaterThan(30_000_000);
+      // Only the rounding dust, which the tie-break hands out, may differ
+      expect(Math.abs(paid[0] - paid[1])).to.be.at.most(1);
+      expect(paid[2]).to.equal(0);
+    });
+  });
+
+  // ─── Test 44: Reveal Window Ping ─────────────────────────────────
+
+  describe("ping_reveal_window", () => {
+    it("emits RevealWindowOpen only inside the reveal window", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(380, { commitIn: 4, revealIn: 8 });
+      await commitTo(topic, vault, agent1, 100_000_000, randomSalt(), 20_000_000);
+      const ping = () => program.methods.pingRevealWindow().accounts({ topic }).rpc();
+
+      try {
+        await ping();
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("CommitPhaseNotEnded");
+      }
+
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      const event = (await eventsOf(await ping())).revealWindowOpen;
+      expect(event.topicId.toNumber()).to.equal(380);
+      expect(event.revealDeadline.toNumber()).to.equal(created.revealDeadline.toNumber());
+      expect(event.revealCount).to.equal(0);
+      expect(event.commitmentCount).to.equal(1);
+
+      await sleepUntil(created.revealDeadline.toNumber());
+      try {
+        await ping();
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("RevealPhaseEnded");
+      }
+    });
+  });
+
+  // ─── Test 45: Settle Account Checks ──────────────────────────────
+
+  describe("settle account checks", () => {
+    it("rejects foreign-owned and undersized commitment accounts", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(390, { commitIn: 4, revealIn: 8 });
+      const salt = randomSalt();
+      await commitTo(topic, vault, agent1, 100_000_000, salt, 20_000_000);
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      await revealTo(topic, agent1, 100_000_000, salt);
+      await sleepUntil(created.revealDeadline.toNumber());
+      await finalizeAs(topic, 100_000_000);
+
+      const settleWith = async (commitment: PublicKey) =>
+        program.methods
+          .settle()
+          .accounts({
+            authority: authority.publicKey,
+            topic,
+            topicAuthority: authority.publicKey,
+            vault,
+            truthHistory: await truthHistoryOf(topic),
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts([
+            { pubkey: commitment, isSigner: false, isWritable: true },
+            { pubkey: agent1.publicKey, isSigner: false, isWritable: true },
+          ])
+          .signers([authority])
+          .rpc();
+
+      // A system-owned wallet, then a program-owned participant state
+      const cases: [PublicKey, string][] = [
+        [agent1.publicKey, "InvalidCommitmentAccount"],
+        [deriveParticipantState(topic, agent1.publicKey), "CommitmentAccountTooSmall"],
+      ];
+      for (const [account, error] of cases) {
+        try {
+          await settleWith(account);
+          expect.fail("Should have thrown");
+        } catch (err: any) {
+          expect(err.toString()).to.include(error);
+        }
+      }
+
+      await settleWith(deriveCommitment(topic, agent1.publicKey));
+      expect((await program.account.topic.fetch(topic)).status).to.have.property("settled");
+    });
+  });
+
+  // ─── Test 46: Finalize And Settle ────────────────────────────────
+
+  describe("finalize_and_settle", () => {
+    const finalizeAndSettle = async (topic: PublicKey, vault: PublicKey, truth: number, agents: Keypair[]) =>
+      program.methods
+        .finalizeAndSettle(new BN(truth), null, null)
+        .accounts({
+          oracleAuthority: oracleAuthority.publicKey,
+          topic,
+          topicAuthority: authority.publicKey,
+          vault,
+          truthHistory: await truthHistoryOf(topic),
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(
+          agents.flatMap((a) => [
+            { pubkey: deriveCommitment(topic, a.publicKey), isSigner: false, isWritable: true },
+            { pubkey: a.publicKey, isSigner: false, isWritable: true },
+          ])
+        )
+        .signers([oracleAuthority])
+        .rpc();
+
+    it("takes a small topic straight to Settled", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(410, { commitIn: 4, revealIn: 8 });
+      const agents = [agent1, agent2, agent3];
+      const predictions = [100_000_000, 108_000_000, 120_000_000];
+      const salts = agents.map(() => randomSalt());
+      for (let i = 0; i < agents.length; i++) {
+        await commitTo(topic, vault, agents[i], predictions[i], salts[i], 20_000_000);
+      }
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      // agent3 forfeits
+      await revealTo(topic, agent1, predictions[0], salts[0]);
+      await revealTo(topic, agent2, predictions[1], salts[1]);
+      await sleepUntil(created.revealDeadline.toNumber());
+
+      await finalizeAndSettle(topic, vault, 110_000_000, agents);
+      const data = await program.account.topic.fetch(topic);
+      expect(data.status).to.have.property("settled");
+      expect(data.truthValue.toNumber()).to.equal(110_000_000);
+      // Revealers share agent3's forfeit; the vault keeps its rent reserve
+      expect(data.totalSettledPayout.toNumber()).to.be.greaterThan(40_000_000);
+      expect(data.totalSettledPayout.toNumber()).to.be.at.most(60_000_000);
+    });
+
+    it("rejects topics with too many commitments", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(411, { commitIn: 30, revealIn: 60 });
+      for (let nonce = 0; nonce < 9; nonce++) {
+        await program.methods
+          .commit(computeHash(100_000_000, randomSalt(), agent1.publicKey), new BN(10_000_000), null, null, false, null)
+          .accounts({
+            participant: agent1.publicKey,
+            rentPayer: agent1.publicKey,
+            topic,
+            commitment: deriveCommitment(topic, agent1.publicKey, nonce),
+            participantState: deriveParticipantState(topic, agent1.publicKey),
+            vault,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([agent1])
+          .rpc();
+      }
+
+      try {
+        await finalizeAndSettle(topic, vault, 100_000_000, [agent1]);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("TooManyCommitmentsForAutoSettle");
+      }
+    });
+
+    it("fails an early or rejected finalize, creating no settlement record", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(412, {
+        commitIn: 3,
+        revealIn: 12,
+        options: { softFinalize: true, maxPrediction: new BN(150_000_000) },
+      });
+      const salt = randomSalt();
+      await commitTo(topic, vault, agent1, 100_000_000, salt, 20_000_000);
+      const created = await program.account.topic.fetch(topic);
+      const [recordPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("settlement"), topic.toBuffer()],
+        program.programId
+      );
+
+      try {
+        await finalizeAndSettle(topic, vault, 100_000_000, [agent1]);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("RevealPhaseNotEnded");
+      }
+      expect(await provider.connection.getAccountInfo(recordPDA)).to.be.null;
+
+      await sleepUntil(created.commitDeadline.toNumber());
+      await revealTo(topic, agent1, 100_000_000, salt);
+      await sleepUntil(created.revealDeadline.toNumber());
+      try {
+        await finalizeAndSettle(topic, vault, 200_000_000, [agent1]);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("TruthOutOfRange");
+      }
+      expect(await provider.connection.getAccountInfo(recordPDA)).to.be.null;
+
+      await finalizeAndSettle(topic, vault, 100_000_000, [agent1]);
+      expect((await program.account.topic.fetch(topic)).status).to.have.property("settled");
+      expect(await provider.connection.getAccountInfo(recordPDA)).to.not.be.null;
+    });
+  });
+
+  // ─── Test 47: Precise Wrong-State Errors ─────────────────────────
+
+  describe("wrong-state errors", () => {
+    it("names the state each instruction expected", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(420, { commitIn: 3, revealIn: 6 });
+      const salt = randomSalt();
+      await commitTo(topic, vault, agent1, 100_000_000, salt, 10_000_000);
+
+      try {
+        await settleAs(topic, vault, authority, [agent1]);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("TopicNotFinalized");
+      }
+
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      await revealTo(topic, agent1, 100_000_000, salt);
+      await sleepUntil(created.revealDeadline.toNumber());
+      await finalizeAs(topic, 100_000_000);
+
+      try {
+        await commitTo(topic, vault, agent2, 100_000_000, randomSalt(), 10_000_000);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("TopicNotOpen");
+      }
+
+      try {
+        await program.methods
+          .cancelCommitment()
+          .accounts({
+            participant: agent1.publicKey,
+            topic,
+            commitment: deriveCommitment(topic, agent1.publicKey),
+            rentPayer: agent1.publicKey,
+            stakePayer: agent1.publicKey,
+            participantState: deriveParticipantState(topic, agent1.publicKey),
+            vault,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([agent1])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("CannotCancelAfterReveal");
+      }
+
+      try {
+        await finalizeAs(topic, 100_000_000);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("AlreadyFinalized");
+      }
+    });
+  });
+
+  // ─── Test 48: Guaranteed Stake Return ────────────────────────────
+
+  describe("guarantee_stake_return", () => {
+    // agent2 reveals on the wrong side of consensus and earns no bonus;
+    // agent3 forfeits. The vault holds no rent reserve, so the clamp
+    // trims every payout unless stakes are guaranteed.
+    async function settledPayouts(id: number, guaranteeStakeReturn: boolean): Promise<number[]> {
+      const [topic, vault] = await createTestTopic(id, { options: { guaranteeStakeReturn } });
+      const agents = [agent1, agent2, agent3];
+      const predictions = [112_000_000, 90_000_000, 100_000_000];
+      const salts = agents.map(() => randomSalt());
+      for (let i = 0; i < agents.length; i++) {
+        await commitTo(topic, vault, agents[i], predictions[i], salts[i], 30_000_000);
+      }
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      await revealTo(topic, agent1, predictions[0], salts[0]);
+      await revealTo(topic, agent2, predictions[1], salts[1]);
+      await sleepUntil(created.revealDeadline.toNumber());
+      await finalizeAs(topic, 115_000_000);
+
+      const before = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+      await settleAs(topic, vault, authority, agents);
+      const after = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+      return after.map((bal, i) => bal - before[i]);
+    }
+
+    it("drops a bonus-less revealer below stake without the guarantee", async function () {
+      this.timeout(30000);
+      const paid = await settledPayouts(430, false);
+      expect(paid[0]).to.be.greaterThan(30_000_000);
+      expect(paid[1]).to.be.lessThan(30_000_000);
+    });
+
+    it("pays every revealer at least their stake with the guarantee", async function () {
+      this.timeout(30000);
+      const paid = await settledPayouts(431, true);
+      expect(paid[0]).to.be.greaterThan(30_000_000);
+      expect(paid[1]).to.equal(30_000_000);
+      expect(paid[2]).to.equal(0);
+    });
+  });
+
+  // ─── Test 49: Decay Table ────────────────────────────────────────
+
+  describe("get_decay_table", () => {
+    // LN_TABLE as written in the program source
+    const source = fs.readFileSync("programs/worth_hub/src/instructions/settle.rs", "utf8");
+    const body = source.slice(source.indexOf("const LN_TABLE"));
+    const lnTable = body
+      .slice(body.indexOf("= ") + 2, body.indexOf("];"))
+      .split("\n")
+      .map((line) => line.replace(/\/\/.*$/, "").replace(/_|,/g, "").trim())
+      .filter((v) => /^\d+$/.test(v));
+
+    it("pages through the table the program uses", async () => {
+      expect(lnTable).to.have.length(64);
+      const entries: string[] = [];
+      for (let page = 0; ; page++) {
+        const data = await program.methods.getDecayTable(page).accounts({}).view();
+        expect(data.tableLen).to.equal(64);
+        expect(data.precision.toString()).to.equal("1000000");
+        expect(data.maxPct.toString()).to.equal("100000000");
+        if (data.entries.length === 0) break;
+        expect(data.offset).to.equal(entries.length);
+        entries.push(...data.entries.map((e: BN) => e.toString()));
+      }
+      expect(entries).to.deep.equal(lnTable);
+    });
+  });
+
+  // ─── Test 50: Truth Within Prediction Bounds ─────────────────────
+
+  describe("prediction bounds", () => {
+    async function revealedTopic(id: number): Promise<PublicKey> {
+      const [topic, vault] = await createTestTopic(id, {
+        commitIn: 3,
+        revealIn: 6,
+        options: { minPrediction: new BN(50_000_000), maxPrediction: new BN(150_000_000) },
+      });
+      const salt = randomSalt();
+      await commitTo(topic, vault, agent1, 100_000_000, salt, 10_000_000);
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      await revealTo(topic, agent1, 100_000_000, salt);
+      await sleepUntil(created.revealDeadline.toNumber());
+      return topic;
+    }
+
+    it("accepts a truth on the bound", async function () {
+      this.timeout(30000);
+      const topic = await revealedTopic(440);
+      await finalizeAs(topic, 150_000_000);
+      expect((await program.account.topic.fetch(topic)).status).to.have.property("finalized");
+    });
+
+    it("rejects a truth outside the bounds", async function () {
+      this.timeout(30000);
+      const topic = await revealedTopic(441);
+      await expectFinalizeRejected(topic, () => finalizeAs(topic, 150_000_001), "TruthOutOfRange");
+    });
+  });
+
+  // ─── Test 51: Reveal Bond ────────────────────────────────────────
+
+  describe("reveal bond", () => {
+    it("collects bonds into the settle fund, pays the keeper and refunds revealers", async function () {
+      this.timeout(30000);
+      const keeper = Keypair.generate();
+      await airdrop(keeper.publicKey, 1);
+      const [topic, vault] = await createTestTopic(450, {
+        settleAuthority: keeper.publicKey,
+        options: {
+          revealBond: new BN(1_000_000),
+          revealBondKeeperBps: 2_000,
+          guaranteeStakeReturn: true,
+        },
+      });
+      const agents = [agent1, agent2, agent3];
+      const predictions = [112_000_000, 90_000_000, 100_000_000];
+      const salts = agents.map(() => randomSalt());
+      for (let i = 0; i < agents.length; i++) {
+        await commitTo(topic, vault, agents[i], predictions[i], salts[i], 20_000_000);
+      }
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+
+      const beforeReveal = await provider.connection.getBalance(agent1.publicKey);
+      await revealTo(topic, agent1, predictions[0], salts[0]);
+      expect(beforeReveal - (await provider.connection.getBalance(agent1.publicKey))).to.equal(1_000_000);
+      // agent3 forfeits and posts no bond
+      await revealTo(topic, agent2, predictions[1], salts[1]);
+      expect((await program.account.topic.fetch(topic)).settleFund.toNumber()).to.equal(2_000_000);
+
+      await sleepUntil(created.revealDeadline.toNumber());
+      await finalizeAs(topic, 115_000_000);
+
+      const before = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+      const sig = await settleAs(topic, vault, keeper, agents);
+      const after = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+      const paid = after.map((bal, i) => bal - before[i]);
+
+      // 20% of each 1M bond goes to the keeper, the rest back to its revealer
+      expect((await eventsOf(sig)).topicSettled.keeperReward.toNumber()).to.equal(400_000);
+      // agent2 earns no bonus: stake (guaranteed) plus the bond refund
+      expect(paid[1]).to.equal(20_800_000);
+      expect(paid[0]).to.be.greaterThan(20_800_000);
+      expect(paid[2]).to.equal(0);
+      expect((await program.account.topic.fetch(topic)).settleFund.toNumber()).to.equal(0);
+    });
+  });
+
+  // ─── Test 52: Participant Status ─────────────────────────────────
+
+  describe("participant_status", () => {
+    it("reports revealed, committed and never-committed participants", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(460, { commitIn: 3, revealIn: 6 });
+      const salt = randomSalt();
+      await commitTo(topic, vault, agent1, 100_000_000, salt, 10_000_000);
+      await commitTo(topic, vault, agent2, 100_000_000, randomSalt(), 20_000_000);
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      await revealTo(topic, agent1, 100_000_000, salt);
+
+      const statusOf = (agent: Keypair) =>
+        program.methods
+          .participantStatus(agent.publicKey, 0)
+          .accounts({ topic, commitment: deriveCommitment(topic, agent.publicKey) })
+          .view();
+
+      const revealed = await statusOf(agent1);
+      expect(revealed.exists).to.equal(true);
+      expect(revealed.revealed).to.equal(true);
+      expect(revealed.settled).to.equal(false);
+      expect(revealed.stakeAmount.toNumber()).to.equal(10_000_000);
+
+      const committed = await statusOf(agent2);
+      expect(committed.exists).to.equal(true);
+      expect(committed.revealed).to.equal(false);
+      expect(committed.stakeAmount.toNumber()).to.equal(20_000_000);
+
+      const missing = await statusOf(agent3);
+      expect(missing.exists).to.equal(false);
+      expect(missing.revealed).to.equal(false);
+      expect(missing.stakeAmount.toNumber()).to.equal(0);
+    });
+  });
+
+  // ─── Test 53: Wrong-Direction Penalty ────────────────────────────
+
+  describe("wrong_direction_penalty_bps", () => {
+    // agent2 reveals on the wrong side of consensus; agent3 forfeits
+    async function settledPayouts(id: number, wrongDirectionPenaltyBps: number): Promise<number[]> {
+      const [topic, vault] = await createTestTopic(id, { options: { wrongDirectionPenaltyBps } });
+      const agents = [agent1, agent2, agent3];
+      const predictions = [112_000_000, 90_000_000, 100_000_000];
+      const salts = agents.map(() => randomSalt());
+      for (let i = 0; i < agents.length; i++) {
+        await commitTo(topic, vault, agents[i], predictions[i], salts[i], 30_000_000);
+      }
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      await revealTo(topic, agent1, predictions[0], salts[0]);
+      await revealTo(topic, agent2, predictions[1], salts[1]);
+      await sleepUntil(created.revealDeadline.toNumber());
+      await finalizeAs(topic, 115_000_000);
+
+      const before = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+      await settleAs(topic, vault, authority, agents);
+      const after = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+      return after.map((bal, i) => bal - before[i]);
+    }
+
+    it("shifts half the wrong-direction stake to the right side", async function () {
+      this.timeout(60000);
+      const free = await settledPayouts(480, 0);
+      const penalized = await settledPayouts(481, 5_000);
+
+      // 15M of agent2's 30M moves to agent1, less the shared rent scaling
+      expect(free[1] - penalized[1]).to.be.greaterThan(14_000_000);
+      expect(penalized[0] - free[0]).to.be.greaterThan(14_000_000);
+      expect(penalized[0] + penalized[1]).to.be.closeTo(free[0] + free[1], 2);
+    });
+  });
+
+  // ─── Test 54: Leftover Sweep ─────────────────────────────────────
+
+  describe("leftover_to_participants", () => {
+    // agent1 and agent2 reveal, agent3 forfeits
+    async function settle(id: number, predictions: number[], truth: number, leftoverToParticipants: boolean) {
+      // The two revealers agree, so the pool is only released under the sweep policy
+      const [topic, vault] = await createTestTopic(id, {
+        options: { leftoverToParticipants, unanimousPolicy: { sweep: {} } },
+      });
+      const agents = [agent1, agent2, agent3];
+      const salts = agents.map(() => randomSalt());
+      for (let i = 0; i < agents.length; i++) {
+        await commitTo(topic, vault, agents[i], predictions[i], salts[i], 30_000_000);
+      }
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      await revealTo(topic, agent1, predictions[0], salts[0]);
+      await revealTo(topic, agent2, predictions[1], salts[1]);
+      await sleepUntil(created.revealDeadline.toNumber());
+      await finalizeAs(topic, truth);
+
+      const before = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+      const sig = await settleAs(topic, vault, authority, agents);
+      const after = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+      return { paid: after.map((bal, i) => bal - before[i]), settled: (await eventsOf(sig)).topicSettled };
+    }
+
+    it("never sweeps scaled-down payouts to the authority", async function () {
+      this.timeout(30000);
+      // Payouts exceed the vault less rent, so they are scaled
+      const { settled } = await settle(490, [112_000_000, 90_000_000, 100_000_000], 115_000_000, false);
+      expect(settled.protocolFee.toNumber()).to.equal(0);
+      expect(settled.totalSettledPayout.toNumber()).to.be.greaterThan(89_000_000);
+    });
+
+    it("returns an unscored pool to participants only when configured", async function () {
+      this.timeout(60000);
+      // Truth on consensus: nobody scores, stakes come back unscaled
+      const swept = await settle(491, [100_000_000, 100_000_000, 90_000_000], 100_000_000, false);
+      expect(swept.paid.slice(0, 2)).to.deep.equal([30_000_000, 30_000_000]);
+      expect(swept.settled.protocolFee.toNumber()).to.be.greaterThan(29_000_000);
+
+      const returned = await settle(492, [100_000_000, 100_000_000, 90_000_000], 100_000_000, true);
+      expect(returned.settled.protocolFee.toNumber()).to.equal(0);
+      expect(returned.paid[0]).to.be.greaterThan(44_000_000);
+      expect(returned.paid[0] + returned.paid[1]).to.equal(swept.paid[0] + swept.paid[1] + swept.settled.protocolFee.toNumber());
+    });
+  });
+
+  // ─── Test 55: Pool Release By Reveal Ratio ───────────────────────
+
+  describe("pool_release_by_reveal_ratio", () => {
+    it("returns the unreleased share of the pool to every committer", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(500, { options: { poolReleaseByRevealRatio: true } });
+      const agents = [agent1, agent2, agent3];
+      const predictions = [112_000_000, 90_000_000, 100_000_000];
+      const salts = agents.map(() => randomSalt());
+      for (let i = 0; i < agents.length; i++) {
+        await commitTo(topic, vault, agents[i], predictions[i], salts[i], 30_000_000);
+      }
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      // agent3 forfeits: 2 of 3 revealed, so a third of its 30M comes back
+      await revealTo(topic, agent1, predictions[0], salts[0]);
+      await revealTo(topic, agent2, predictions[1], salts[1]);
+      await sleepUntil(created.revealDeadline.toNumber());
+      await finalizeAs(topic, 115_000_000);
+
+      const before = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+      await settleAs(topic, vault, authority, agents);
+      const after = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+      const paid = after.map((bal, i) => bal - before[i]);
+
+      // Each committer gets a third of the 10M retained, less rent scaling
+      expect(paid[2]).to.be.within(3_200_000, 3_333_334);
+      expect(paid[1]).to.be.within(33_200_000, 33_333_334);
+      expect(paid[0]).to.be.greaterThan(50_000_000);
+    });
+  });
+
+  // ─── Test 56: Multi-Dimension Truths ─────────────────────────────
+
+  describe("multi-dimension truths", () => {
+    it("scores each dimension against its own truth and sums the scores", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(510, { options: { dimensions: 2 } });
+      const agents = [agent1, agent2, agent3];
+      // Same call on the first dimension; only agent2 reads the second right
+      const predictions = [
+        [112_000_000, 190_000_000],
+        [112_000_000, 212_000_000],
+        [95_000_000, 200_000_000],
+      ];
+      const salts = agents.map(() => randomSalt());
+      for (let i = 0; i < agents.length; i++) {
+        await program.methods
+          .commit(computeVectorHash(predictions[i], salts[i], agents[i].publicKey), new BN(10_000_000), null, null, false, null)
+          .accounts({
+            participant: agents[i].publicKey,
+            rentPayer: agents[i].publicKey,
+            topic,
+            commitment: deriveCommitment(topic, agents[i].publicKey),
+            participantState: deriveParticipantState(topic, agents[i].publicKey),
+            vault,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([agents[i]])
+          .rpc();
+      }
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+
+      // A single-value reveal doesn't match the topic's dimension count
+      try {
+        await revealTo(topic, agent1, predictions[0][0], salts[0]);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidDimensionCount");
+      }
+
+      for (let i = 0; i < agents.length; i++) {
+        await program.methods
+          .revealVector(predictions[i].map((p) => new BN(p)), Array.from(salts[i]))
+          .accounts({
+            participant: agents[i].publicKey,
+            topic,
+            commitment: deriveCommitment(topic, agents[i].publicKey),
+          })
+          .signers([agents[i]])
+          .rpc();
+      }
+      const commitment = await program.account.commitment.fetch(deriveCommitment(topic, agent2.publicKey));
+      expect(commitment.extraPredictions[0].toNumber()).to.equal(212_000_000);
+
+      await sleepUntil(created.revealDeadline.toNumber());
+      await program.methods
+        .finalizeVector([new BN(115_000_000), new BN(215_000_000)])
+        .accounts({ oracleAuthority: oracleAuthority.publicKey, topic })
+        .signers([oracleAuthority])
+        .rpc();
+      const finalized = await program.account.topic.fetch(topic);
+      expect(finalized.truthValue.toNumber()).to.equal(115_000_000);
+      expect(finalized.extraTruths[0].toNumber()).to.equal(215_000_000);
+
+      const before = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+      await settleAs(topic, vault, authority, agents);
+      const after = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+      const paid = after.map((bal, i) => bal - before[i]);
+      expect(paid[1]).to.be.greaterThan(paid[0]);
+    });
+  });
+
+  // ─── Test 57: Reveal Integrity ───────────────────────────────────
+
+  describe("reveal integrity", () => {
+    it("only accepts the exact committed pre-image from its committer", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(520);
+      const prediction = 105_000_000;
+      const salt = randomSalt();
+      await commitTo(topic, vault, agent1, prediction, salt, 10_000_000);
+
+      // agent2 copies agent1's hash into its own commitment
+      const copiedHash = computeHash(prediction, salt, agent1.publicKey);
+      await program.methods
+        .commit(copiedHash, new BN(10_000_000), null, null, false, null)
+        .accounts({
+          participant: agent2.publicKey,
+          rentPayer: agent2.publicKey,
+          topic,
+          commitment: deriveCommitment(topic, agent2.publicKey),
+          participantState: deriveParticipantState(topic, agent2.publicKey),
+          vault,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([agent2])
+        .rpc();
+
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+
+      const rejected: [Keypair, number, Buffer][] = [
+        [agent1, prediction + 1, salt],
+        [agent1, prediction, randomSalt()],
+        [agent2, prediction, salt],
+      ];
+      for (const [agent, value, s] of rejected) {
+        try {
+          await revealTo(topic, agent, value, s);
+          expect.fail("Should have thrown");
+        } catch (err: any) {
+          expect(err.toString()).to.include("HashMismatch");
+        }
+      }
+
+      await revealTo(topic, agent1, prediction, salt);
+      const commitment = await program.account.commitment.fetch(deriveCommitment(topic, agent1.publicKey));
+      expect(commitment.revealed).to.equal(true);
+      expect(commitment.predictionValue.toNumber()).to.equal(prediction);
+    });
+  });
+
+  // ─── Test 58: Topic Status Offset ────────────────────────────────
+
+  describe("topic status offset", () => {
+    it("lets keepers memcmp-filter finalized topics", async function () {
+      this.timeout(30000);
+      const TOPIC_STATUS_OFFSET = 8 + 32 * 3 + 8;
+      const [topic, vault] = await createTestTopic(530);
+      const salt = randomSalt();
+      await commitTo(topic, vault, agent1, 100_000_000, salt, 10_000_000);
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      await revealTo(topic, agent1, 100_000_000, salt);
+      await sleepUntil(created.revealDeadline.toNumber());
+      await finalizeAs(topic, 100_000_000);
+
+      const info = await provider.connection.getAccountInfo(topic);
+      expect(info!.data[TOPIC_STATUS_OFFSET]).to.equal(2);
+
+      const finalized = await program.account.topic.all([
+        { memcmp: { offset: TOPIC_STATUS_OFFSET, bytes: anchor.utils.bytes.bs58.encode(Buffer.from([2])) } },
+      ]);
+      const keys = finalized.map((t) => t.publicKey.toBase58());
+      expect(keys).to.include(topic.toBase58());
+      expect(finalized.every((t) => "finalized" in t.account.status)).to.equal(true);
+    });
+  });
+
+  // ─── Test 59: Ordered Reveal ─────────────────────────────────────
+
+  describe("ordered_reveal", () => {
+    it("reveals in commit order, skipping a turn once it expires", async function () {
+      this.timeout(40000);
+      const [topic, vault] = await createTestTopic(540, {
+        revealIn: 20,
+        options: { orderedReveal: true, revealTurnSeconds: 3 },
+      });
+      const agents = [agent1, agent2, agent3];
+      const salts = agents.map(() => randomSalt());
+      for (let i = 0; i < agents.length; i++) {
+        await commitTo(topic, vault, agents[i], 100_000_000 + i, salts[i], 10_000_000);
+      }
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+
+      // agent2 (order 1) can't go before agent1 (order 0)
+      try {
+        await revealTo(topic, agent2, 100_000_001, salts[1]);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("RevealOutOfOrder");
+      }
+
+      await revealTo(topic, agent1, 100_000_000, salts[0]);
+      await revealTo(topic, agent2, 100_000_001, salts[1]);
+
+      // agent3's turn has only just begun
+      const skip = () => program.methods.skipRevealTurn().accounts({ topic }).rpc();
+      try {
+        await skip();
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("RevealTurnNotExpired");
+      }
+
+      const turn = await program.account.topic.fetch(topic);
+      expect(turn.nextExpectedRevealOrder).to.equal(2);
+      await sleepUntil(turn.revealTurnStartedAt.toNumber() + 3);
+      await skip();
+      expect((await program.account.topic.fetch(topic)).nextExpectedRevealOrder).to.equal(3);
+      try {
+        await skip();
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("NoRevealTurnToSkip");
+      }
+
+      // A skipped committer may still reveal late
+      await revealTo(topic, agent3, 100_000_002, salts[2]);
+      expect((await program.account.topic.fetch(topic)).revealCount).to.equal(3);
+    });
+  });
+
+  // ─── Test 60: Sponsored Commit ───────────────────────────────────
+
+  describe("sponsored commit", () => {
+    it("lets a relayer pay rent, and optionally stake, for a participant's position", async function () {
+      this.timeout(30000);
+      const relayer = Keypair.generate();
+      await airdrop(relayer.publicKey, 2);
+      const [topic, vault] = await createTestTopic(550);
+
+      const commitFor = async (agent: Keypair, stakeFromRentPayer: boolean) => {
+        await program.methods
+          .commit(computeHash(100_000_000, randomSalt(), agent.publicKey), new BN(10_000_000), null, null, stakeFromRentPayer, null)
+          .accounts({
+            participant: agent.publicKey,
+            rentPayer: relayer.publicKey,
+            topic,
+            commitment: deriveCommitment(topic, agent.publicKey),
+            participantState: deriveParticipantState(topic, agent.publicKey),
+            vault,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([agent, relayer])
+          .rpc();
+      };
+
+      // Relayer pays rent only: the participant's balance drops by the stake
+      const before1 = await provider.connection.getBalance(agent1.publicKey);
+      await commitFor(agent1, false);
+      const after1 = await provider.connection.getBalance(agent1.publicKey);
+      expect(before1 - after1).to.equal(10_000_000);
+
+      // Relayer sponsors the stake too: the participant pays nothing
+      const before2 = await provider.connection.getBalance(agent2.publicKey);
+      await commitFor(agent2, true);
+      expect(await provider.connection.getBalance(agent2.publicKey)).to.equal(before2);
+
+      for (const agent of [agent1, agent2]) {
+        const commitment = await program.account.commitment.fetch(deriveCommitment(topic, agent.publicKey));
+        expect(commitment.participant.toBase58()).to.equal(agent.publicKey.toBase58());
+        expect(commitment.stakeAmount.toNumber()).to.equal(10_000_000);
+        expect(commitment.rentPayer.toBase58()).to.equal(relayer.publicKey.toBase58());
+      }
+      expect((await program.account.commitment.fetch(deriveCommitment(topic, agent1.publicKey))).stakePayer.toBase58())
+        .to.equal(agent1.publicKey.toBase58());
+      expect((await program.account.commitment.fetch(deriveCommitment(topic, agent2.publicKey))).stakePayer.toBase58())
+        .to.equal(relayer.publicKey.toBase58());
+    });
+
+    it("returns a sponsored stake and rent to the relayer on cancel", async function () {
+      this.timeout(30000);
+      const relayer = Keypair.generate();
+      await airdrop(relayer.publicKey, 2);
+      const [topic, vault] = await createTestTopic(850);
+      const commitment = deriveCommitment(topic, agent1.publicKey);
+      await program.methods
+        .commit(computeHash(100_000_000, randomSalt(), agent1.publicKey), new BN(10_000_000), null, null, true, null)
+        .accounts({
+          participant: agent1.publicKey,
+          rentPayer: relayer.publicKey,
+          topic,
+          commitment,
+          participantState: deriveParticipantState(topic, agent1.publicKey),
+          vault,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([agent1, relayer])
+        .rpc();
+      const rent = await provider.connection.getBalance(commitment);
+
+      const cancel = (stakePayer: PublicKey) =>
+        program.methods
+          .cancelCommitment()
+          .accounts({
+            participant: agent1.publicKey,
+            topic,
+            commitment,
+            rentPayer: relayer.publicKey,
+            stakePayer,
+            participantState: deriveParticipantState(topic, agent1.publicKey),
+            vault,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([agent1])
+          .rpc();
+
+      // The participant can't route the sponsor's stake to themselves
+      try {
+        await cancel(agent1.publicKey);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidRefundRecipient");
+      }
+
+      const relayerBefore = await provider.connection.getBalance(relayer.publicKey);
+      const agentBefore = await provider.connection.getBalance(agent1.publicKey);
+      await cancel(relayer.publicKey);
+      expect(await provider.connection.getBalance(relayer.publicKey)).to.equal(relayerBefore + 10_000_000 + rent);
+      // The participant only paid the transaction fee
+      expect(await provider.connection.getBalance(agent1.publicKey)).to.be.at.most(agentBefore);
+    });
+  });
+
+  // ─── Test 61: Salt Hygiene ───────────────────────────────────────
+
+  describe("salt hygiene", () => {
+    it("rejects an all-zero salt", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(560);
+      const zeroSalt = Buffer.alloc(32);
+      await commitTo(topic, vault, agent1, 100_000_000, zeroSalt, 10_000_000);
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      try {
+        await revealTo(topic, agent1, 100_000_000, zeroSalt);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidSalt");
+      }
+    });
+
+    it("rejects a salt reused across commitments when the topic binds salts", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(561, { options: { bindSalt: true } });
+      // Bound to nonce 0, then reused for the commitment under nonce 1
+      const salt = boundSalt(topic, agent1.publicKey, 0);
+      for (let nonce = 0; nonce < 2; nonce++) {
+        await program.methods
+          .commit(computeHash(100_000_000, salt, agent1.publicKey), new BN(10_000_000), null, null, false, null)
+          .accounts({
+            participant: agent1.publicKey,
+            rentPayer: agent1.publicKey,
+            topic,
+            participantState: deriveParticipantState(topic, agent1.publicKey),
+            commitment: deriveCommitment(topic, agent1.publicKey, nonce),
+            vault,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([agent1])
+          .rpc();
+      }
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+
+      const revealNonce = (nonce: number) =>
+        program.methods
+          .reveal(new BN(100_000_000), Array.from(salt))
+          .accounts({ participant: agent1.publicKey, topic, commitment: deriveCommitment(topic, agent1.publicKey, nonce) })
+          .signers([agent1])
+          .rpc();
+      await revealNonce(0);
+      try {
+        await revealNonce(1);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidSalt");
+      }
+    });
+  });
+
+  // ─── Test 62: Set Authority ──────────────────────────────────────
+
+  describe("set_authority", () => {
+    it("hands the topic to a new authority until it is settled", async function () {
+      this.timeout(30000);
+      const newAuthority = Keypair.generate();
+      const [topic, vault] = await runToFinalized(570, [agent1], [100_000_000], 100_000_000);
+      const setAuthority = (signer: Keypair, to: PublicKey) =>
+        program.methods
+          .setAuthority(to)
+          .accounts({ authority: signer.publicKey, topic })
+          .signers([signer])
+          .rpc();
+
+      const sig = await setAuthority(authority, newAuthority.publicKey);
+      const events = await eventsOf(sig);
+      expect(events.authorityTransferred.previousAuthority.toBase58()).to.equal(authority.publicKey.toBase58());
+      expect(events.authorityTransferred.newAuthority.toBase58()).to.equal(newAuthority.publicKey.toBase58());
+      expect((await program.account.topic.fetch(topic)).authority.toBase58()).to.equal(newAuthority.publicKey.toBase58());
+
+      // The previous authority has no say any more
+      try {
+        await setAuthority(authority, authority.publicKey);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("UnauthorizedAuthority");
+      }
+
+      // Settle sweeps the fee to the new authority; the topic is then frozen
+      await program.methods
+        .settle()
+        .accounts({
+          authority: oracleAuthority.publicKey,
+          topic,
+          topicAuthority: newAuthority.publicKey,
+          vault,
+          truthHistory: await truthHistoryOf(topic),
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts([
+          { pubkey: deriveCommitment(topic, agent1.publicKey), isSigner: false, isWritable: true },
+          { pubkey: agent1.publicKey, isSigner: false, isWritable: true },
+        ])
+        .signers([oracleAuthority])
+        .rpc();
+      try {
+        await setAuthority(newAuthority, authority.publicKey);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("AlreadySettled");
+      }
+    });
+  });
+
+  // ─── Test 63: Truth History ──────────────────────────────────────
+
+  describe("truth history", () => {
+    it("records each settled truth for the symbol, oldest first", async function () {
+      this.timeout(60000);
+      const symbol = "HIST-1";
+      const truths = [101_000_000, 102_000_000, 103_000_000];
+      for (let i = 0; i < truths.length; i++) {
+        const [topic, vault] = await runToFinalized(580 + i, [agent1], [100_000_000], truths[i], { symbol });
+        await settleAs(topic, vault, authority, [agent1]);
+      }
+
+      const history = await program.account.truthHistory.fetch(deriveTruthHistory(symbol));
+      expect(history.symbol).to.equal(symbol);
+      expect(history.totalRecorded.toNumber()).to.equal(3);
+      expect(history.records.map((r: any) => r.topicId.toNumber())).to.deep.equal([580, 581, 582]);
+      expect(history.records.map((r: any) => r.truthValue.toNumber())).to.deep.equal(truths);
+      const settledAt = history.records.map((r: any) => r.settledAt.toNumber());
+      expect(settledAt[0]).to.be.at.most(settledAt[1]);
+      expect(settledAt[1]).to.be.at.most(settledAt[2]);
+    });
+  });
+
+  // ─── Test 64: Vault Validation ───────────────────────────────────
+
+  describe("vault validation", () => {
+    it("records the vault on the topic and rejects a commit into another vault", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(590);
+      const [, otherVault] = await createTestTopic(591);
+
+      const topicAccount = await program.account.topic.fetch(topic);
+      expect(topicAccount.vault.toBase58()).to.equal(vault.toBase58());
+
+      try {
+        await program.methods
+          .commit(computeHash(100_000_000, randomSalt(), agent1.publicKey), new BN(10_000_000), null, null, false, null)
+          .accounts({
+            participant: agent1.publicKey,
+            rentPayer: agent1.publicKey,
+            topic,
+            commitment: deriveCommitment(topic, agent1.publicKey),
+            participantState: deriveParticipantState(topic, agent1.publicKey),
+            vault: otherVault,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([agent1])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.match(/VaultMismatch|ConstraintSeeds/);
+      }
+      expect(await provider.connection.getBalance(otherVault)).to.equal(0);
+    });
+  });
+
+  // ─── Test 65: Authority Replaces Oracle ──────────────────────────
+
+  describe("authority replace oracle", () => {
+    it("lets the authority swap out an oracle whose finalize was rejected", async function () {
+      this.timeout(30000);
+      const [topic] = await createTestTopic(610, { options: { maxPrediction: new BN(150_000_000) } });
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.revealDeadline.toNumber());
+      await expectFinalizeRejected(topic, () => finalizeAs(topic, 200_000_000), "TruthOutOfRange");
+
+      const { finalizeRejected } = await eventsOf(await reportRejectedTruth(topic, 200_000_000));
+      const code = (program.idl.errors ?? []).find((e) => e.name.toLowerCase() === "truthoutofrange")!.code;
+      expect(finalizeRejected.reason).to.equal(code);
+      expect((await program.account.topic.fetch(topic)).failedFinalizeCount).to.equal(1);
+
+      try {
+        await reportRejectedTruth(topic, 100_000_000);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("TruthPassesChecks");
+      }
+
+      const replaceOracle = (signer: Keypair, newOracle: PublicKey) =>
+        program.methods
+          .authorityReplaceOracle(newOracle)
+          .accounts({ authority: signer.publicKey, topic })
+          .signers([signer])
+          .rpc();
+
+      const newOracle = Keypair.generate();
+      try {
+        await replaceOracle(oracleAuthority, oracleAuthority.publicKey);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("UnauthorizedAuthority");
+      }
+
+      const { oracleReplaced } = await eventsOf(await replaceOracle(authority, newOracle.publicKey));
+      expect(oracleReplaced.previousOracle.toBase58()).to.equal(oracleAuthority.publicKey.toBase58());
+      expect(oracleReplaced.newOracle.toBase58()).to.equal(newOracle.publicKey.toBase58());
+      expect(oracleReplaced.failedFinalizeCount).to.equal(1);
+
+      try {
+        await finalizeAs(topic, 100_000_000);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("UnauthorizedOracle");
+      }
+      await finalizeAs(topic, 100_000_000, newOracle);
+      const data = await program.account.topic.fetch(topic);
+      expect(data.status).to.have.property("finalized");
+      expect(data.oracleAuthority.toBase58()).to.equal(newOracle.publicKey.toBase58());
+    });
+  });
+
+  // ─── Test 66: Forfeit By Order ───────────────────────────────────
+
+  describe("forfeit by order", () => {
+    it("makes an early non-revealer forfeit less than a late one", async function () {
+      this.timeout(30000);
+      const late = Keypair.generate();
+      await airdrop(late.publicKey, 1);
+      const [topic, vault] = await createTestTopic(620, { options: { forfeitByOrder: true } });
+
+      // Orders: agent1 0 (silent), agent2 1, agent3 2, late 3 (silent)
+      const salts = [randomSalt(), randomSalt(), randomSalt(), randomSalt()];
+      await commitTo(topic, vault, agent1, 100_000_000, salts[0], 50_000_000);
+      await commitTo(topic, vault, agent2, 90_000_000, salts[1], 50_000_000);
+      await commitTo(topic, vault, agent3, 110_000_000, salts[2], 50_000_000);
+      await commitTo(topic, vault, late, 100_000_000, salts[3], 50_000_000);
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      await revealTo(topic, agent2, 90_000_000, salts[1]);
+      await revealTo(topic, agent3, 110_000_000, salts[2]);
+      await sleepUntil(created.revealDeadline.toNumber());
+      await finalizeAs(topic, 110_000_000);
+
+      const silent = [agent1, late];
+      const before = await Promise.all(silent.map((a) => provider.connection.getBalance(a.publicKey)));
+      await settleAs(topic, vault, authority, [agent1, agent2, agent3, late]);
+      const after = await Promise.all(silent.map((a) => provider.connection.getBalance(a.publicKey)));
+
+      // Order 0 forfeits 50% of its stake, order 3 forfeits 80%
+      expect(after[0] - before[0]).to.equal(25_000_000);
+      expect(after[1] - before[1]).to.equal(10_000_000);
+    });
+  });
+
+  // ─── Test 67: Settlement Record ──────────────────────────────────
+
+  describe("settlement record", () => {
+    it("records every participant's prediction, score and payout at settle", async function () {
+      this.timeout(30000);
+      const agents = [agent1, agent2, agent3];
+      const predictions = [90_000_000, 110_000_000, 100_000_000];
+      const [topic, vault] = await runToFinalized(630, agents, predictions, 110_000_000);
+
+      const before = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+      await settleAs(topic, vault, authority, agents);
+      const after = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+
+      const [recordPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from("settlement"), topic.toBuffer()],
+        program.programId
+      );
+      const record = await program.account.settlementRecord.fetch(recordPDA);
+      expect(record.topic.toBase58()).to.equal(topic.toBase58());
+      expect(record.topicId.toNumber()).to.equal(630);
+      expect(record.truthValue.toNumber()).to.equal(110_000_000);
+      expect(record.consensus.toNumber()).to.equal(100_000_000);
+      expect(record.outcomes).to.have.length(3);
+
+      agents.forEach((agent, i) => {
+        const outcome = record.outcomes[i];
+        expect(outcome.participant.toBase58()).to.equal(agent.publicKey.toBase58());
+        expect(outcome.prediction.toNumber()).to.equal(predictions[i]);
+        expect(outcome.revealed).to.be.true;
+        expect(outcome.abstained).to.be.false;
+        expect(outcome.payout.toNumber()).to.equal(after[i] - before[i]);
+      });
+      // Only the right-direction prediction off consensus scores
+      expect(record.outcomes[1].score.gtn(0)).to.be.true;
+      expect(record.outcomes[0].score.isZero()).to.be.true;
+      expect(record.outcomes[2].score.isZero()).to.be.true;
+    });
+  });
+
+  // ─── Test 68: Accuracy Tolerance ─────────────────────────────────
+
+  describe("accuracy tolerance", () => {
+    it("scores predictions inside the deadband as perfect hits", async function () {
+      this.timeout(30000);
+      const silent = Keypair.generate();
+      await airdrop(silent.publicKey, 1);
+      const [topic, vault] = await createTestTopic(640, {
+        options: { accuracyTolerance: new BN(5_000_000), timeDecayEnabled: false },
+      });
+
+      // Consensus 105: 112 and 113 both sit inside the deadband around the
+      // truth of 110, so the bolder 113 earns more despite being further off
+      const agents = [agent1, agent2, agent3];
+      const predictions = [112_000_000, 113_000_000, 90_000_000];
+      const salts = agents.map(() => randomSalt());
+      for (let i = 0; i < agents.length; i++) {
+        await commitTo(topic, vault, agents[i], predictions[i], salts[i], 50_000_000);
+      }
+      await commitTo(topic, vault, silent, 100_000_000, randomSalt(), 50_000_000);
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      for (let i = 0; i < agents.length; i++) {
+        await revealTo(topic, agents[i], predictions[i], salts[i]);
+      }
+      await sleepUntil(created.revealDeadline.toNumber());
+      await finalizeAs(topic, 110_000_000);
+
+      const before = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+      await settleAs(topic, vault, authority, [...agents, silent]);
+      const after = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+      const gains = after.map((b, i) => b - before[i] - 50_000_000);
+
+      expect(gains[1]).to.be.greaterThan(gains[0]);
+      expect(gains[0]).to.be.greaterThan(0);
+      expect(gains[2]).to.equal(0);
+    });
+  });
+
+  // ─── Test 69: Recurring Topics ───────────────────────────────────
+
+  describe("recurring topics", () => {
+    const createSeries = (baseId: number, count: number, period: number, now: number) =>
+      program.methods
+        .createRecurringTopics(
+          new BN(baseId),
+          count,
+          new BN(period),
+          "Daily close",
+          "DAILY-1",
+          new BN(now + 60),
+          new BN(now + 120),
+          new BN(10_000_000),
+          topicOptions(),
+          null
+        )
+        .accounts({
+          authority: authority.publicKey,
+          oracleAuthority: oracleAuthority.publicKey,
+          settleAuthority: null,
+          cosigner: null,
+          priceFeed: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(
+          Array.from({ length: count }, (_, i) => ({
+            pubkey: deriveTopic(baseId + i)[0],
+            isSigner: false,
+            isWritable: true,
+          }))
+        )
+        .signers([authority])
+        .rpc();
+
+    it("creates a series with staggered deadlines and distinct PDAs", async function () {
+      this.timeout(30000);
+      const now = Math.floor(Date.now() / 1000);
+      await createSeries(650, 3, 86_400, now);
+
+      const keys = new Set<string>();
+      for (let i = 0; i < 3; i++) {
+        const [topic, vault] = deriveTopic(650 + i);
+        keys.add(topic.toBase58());
+        const data = await program.account.topic.fetch(topic);
+        expect(data.topicId.toNumber()).to.equal(650 + i);
+        expect(data.symbol).to.equal("DAILY-1");
+        expect(data.status).to.have.property("open");
+        expect(data.commitDeadline.toNumber()).to.equal(now + 60 + i * 86_400);
+        expect(data.revealDeadline.toNumber()).to.equal(now + 120 + i * 86_400);
+        expect(data.minStake.toNumber()).to.equal(10_000_000);
+        expect(data.oracleAuthority.toBase58()).to.equal(oracleAuthority.publicKey.toBase58());
+        expect(data.vault.toBase58()).to.equal(vault.toBase58());
+      }
+      expect(keys.size).to.equal(3);
+    });
+
+    it("creates a topic whose PDA someone pre-funded", async function () {
+      this.timeout(30000);
+      const [topic] = deriveTopic(855);
+      await provider.sendAndConfirm(
+        new Transaction().add(
+          SystemProgram.transfer({ fromPubkey: authority.publicKey, toPubkey: topic, lamports: 1 })
+        ),
+        [authority]
+      );
+      await createSeries(855, 1, 86_400, Math.floor(Date.now() / 1000));
+
+      const info = await provider.connection.getAccountInfo(topic);
+      expect(info!.owner.toBase58()).to.equal(program.programId.toBase58());
+      expect(info!.lamports).to.equal(await provider.connection.getMinimumBalanceForRentExemption(info!.data.length));
+      expect((await program.account.topic.fetch(topic)).topicId.toNumber()).to.equal(855);
+    });
+
+    it("rejects a series longer than the cap", async function () {
+      const now = Math.floor(Date.now() / 1000);
+      try {
+        await createSeries(660, 9, 86_400, now);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidRecurringCount");
+      }
+    });
+  });
+
+  // ─── Test 70: Price Feed Truth Check ─────────────────────────────
+
+  describe("price feed truth check", () => {
+    // tests/fixtures/price_feed.json: a fully verified Pyth PriceUpdateV2
+    // at 100.00 (1e10, expo -8), published at 1_700_000_000. The snapshot
+    // never updates, so topics here accept any price age.
+    const priceFeed = new PublicKey("EDQcxY2C75WRx73yAe4E5wzashCXzT2RqoXKR7esCjeH");
+    const priceFeedId = Array.from(
+      Buffer.from("47f6bc4f75c9918ae62da9dbb790eeaf9eab95b706520406e3a2473ec0845dde", "hex")
+    );
+    const anyAge = 0xffffffff;
+
+    it("rejects truths off the feed's price and accepts one within tolerance", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(670, {
+        priceFeed,
+        options: { priceToleranceBps: 50, priceFeedId, priceMaxAge: anyAge },
+      });
+      expect((await program.account.topic.fetch(topic)).priceFeed.toBase58()).to.equal(priceFeed.toBase58());
+
+      const salt = randomSalt();
+      await commitTo(topic, vault, agent1, 100_000_000, salt, 50_000_000);
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      await revealTo(topic, agent1, 100_000_000, salt);
+      await sleepUntil(created.revealDeadline.toNumber());
+
+      await expectFinalizeRejected(
+        topic,
+        () => finalizeAs(topic, 101_000_000, oracleAuthority, priceFeed),
+        "TruthOracleMismatch"
+      );
+
+      for (const wrongFeed of [null, authority.publicKey]) {
+        try {
+          await finalizeAs(topic, 100_000_000, oracleAuthority, wrongFeed);
+          expect.fail("Should have thrown");
+        } catch (err: any) {
+          expect(err.toString()).to.include("InvalidPriceFeed");
+        }
+      }
+
+      await finalizeAs(topic, 100_400_000, oracleAuthority, priceFeed);
+      const data = await program.account.topic.fetch(topic);
+      expect(data.status).to.have.property("finalized");
+      expect(data.truthValue.toNumber()).to.equal(100_400_000);
+    });
+
+    it("requires a price max age and checks the feed id and age at finalize", async function () {
+      this.timeout(30000);
+      try {
+        await createTestTopic(852, { priceFeed, options: { priceFeedId } });
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidPriceFeed");
+      }
+
+      for (const [id, options, error] of [
+        [853, { priceFeedId: Array(32).fill(1), priceMaxAge: anyAge }, "InvalidPriceFeed"],
+        [854, { priceFeedId, priceMaxAge: 60 }, "StalePrice"],
+      ] as const) {
+        const [topic, vault] = await createTestTopic(id, { priceFeed, options });
+        await commitTo(topic, vault, agent1, 100_000_000, randomSalt(), 10_000_000);
+        await sleepUntil((await program.account.topic.fetch(topic)).revealDeadline.toNumber());
+        try {
+          await finalizeAs(topic, 100_000_000, oracleAuthority, priceFeed);
+          expect.fail("Should have thrown");
+        } catch (err: any) {
+          expect(err.toString()).to.include(error);
+        }
+      }
+    });
+  });
+
+  // ─── Test 71: Reveal Locks Cancellation ──────────────────────────
+
+  describe("reveal locks cancellation", () => {
+    const cancelAs = (topic: PublicKey, vault: PublicKey, agent: Keypair) =>
+      program.methods
+        .cancelCommitment()
+        .accounts({
+          participant: agent.publicKey,
+          topic,
+          commitment: deriveCommitment(topic, agent.publicKey),
+          rentPayer: agent.publicKey,
+          stakePayer: agent.publicKey,
+          participantState: deriveParticipantState(topic, agent.publicKey),
+          vault,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([agent])
+        .rpc();
+
+    it("rejects canceling a revealed commitment, and any once reveals began", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(680);
+      const salt = randomSalt();
+      await commitTo(topic, vault, agent1, 100_000_000, salt, 20_000_000);
+      await commitTo(topic, vault, agent2, 90_000_000, randomSalt(), 20_000_000);
+
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      await revealTo(topic, agent1, 100_000_000, salt);
+
+      for (const [agent, reason] of [
+        [agent1, "CannotCancelAfterReveal"],
+        [agent2, "TopicNotOpen"],
+      ] as [Keypair, string][]) {
+        try {
+          await cancelAs(topic, vault, agent);
+          expect.fail("Should have thrown");
+        } catch (err: any) {
+          expect(err.toString()).to.include(reason);
+        }
+      }
+
+      const data = await program.account.topic.fetch(topic);
+      expect(data.commitmentCount).to.equal(2);
+      expect(data.totalStake.toNumber()).to.equal(40_000_000);
+      expect((await program.account.commitment.fetch(deriveCommitment(topic, agent1.publicKey))).revealed).to.be.true;
+    });
+  });
+
+  // ─── Test 72: Leaderboard ────────────────────────────────────────
+
+  describe("leaderboard", () => {
+    const deriveLeaderboard = (participant: PublicKey) =>
+      PublicKey.findProgramAddressSync([Buffer.from("leaderboard"), participant.toBuffer()], program.programId)[0];
+
+    const settleWithLeaderboards = (topic: PublicKey, vault: PublicKey, agents: Keypair[], leaderboards: PublicKey[]) =>
+      program.methods
+        .settle()
+        .accounts({
+          authority: authority.publicKey,
+          topic,
+          topicAuthority: authority.publicKey,
+          vault,
+          truthHistory: deriveTruthHistory("TEST"),
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts([
+          ...agents.flatMap((a) => [
+            { pubkey: deriveCommitment(topic, a.publicKey), isSigner: false, isWritable: true },
+            { pubkey: a.publicKey, isSigner: false, isWritable: true },
+          ]),
+          ...leaderboards.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })),
+        ])
+        .signers([authority])
+        .rpc();
+
+    it("accumulates a participant's results across two settled topics", async function () {
+      this.timeout(60000);
+      const [ranker, outsider] = [Keypair.generate(), Keypair.generate()];
+      for (const participant of [ranker, outsider]) {
+        await airdrop(participant.publicKey, 2);
+        await program.methods
+          .initLeaderboard()
+          .accounts({
+            participant: participant.publicKey,
+            leaderboard: deriveLeaderboard(participant.publicKey),
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([participant])
+          .rpc();
+      }
+      const leaderboard = deriveLeaderboard(ranker.publicKey);
+
+      // The ranker is exact on 690 and far off on 691
+      const [topicA, vaultA] = await runToFinalized(690, [ranker, agent1], [100_000_000, 130_000_000], 100_000_000);
+      const [topicB, vaultB] = await runToFinalized(691, [ranker, agent1], [160_000_000, 100_000_000], 100_000_000);
+
+      // A leaderboard whose participant is not settled on the topic is rejected
+      try {
+        await settleWithLeaderboards(topicA, vaultA, [ranker, agent1], [leaderboard, deriveLeaderboard(outsider.publicKey)]);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidLeaderboardAccount");
+      }
+
+      await settleWithLeaderboards(topicA, vaultA, [ranker, agent1], [leaderboard]);
+      await settleWithLeaderboards(topicB, vaultB, [ranker, agent1], [leaderboard]);
+
+      const outcomes = await Promise.all(
+        [topicA, topicB].map(async (topic) => {
+          const record = await program.account.settlementRecord.fetch(
+            PublicKey.findProgramAddressSync([Buffer.from("settlement"), topic.toBuffer()], program.programId)[0]
+          );
+          return record.outcomes.find((o: any) => o.participant.equals(ranker.publicKey))!;
+        })
+      );
+      const entry = await program.account.leaderboard.fetch(leaderboard);
+      expect(entry.participant.toBase58()).to.equal(ranker.publicKey.toBase58());
+      expect(entry.settledCount).to.equal(2);
+      expect(entry.winCount).to.equal(1);
+      expect(entry.cumulativePayout.toNumber()).to.equal(
+        outcomes[0].payout.toNumber() + outcomes[1].payout.toNumber()
+      );
+      expect(entry.totalScore.toString()).to.equal(outcomes[0].score.add(outcomes[1].score).toString());
+      expect(entry.lastTopicId.toNumber()).to.equal(691);
+    });
+  });
+
+  // ─── Test 73: Settle Requires Every Commitment ───────────────────
+
+  describe("settle requires every commitment", () => {
+    it("rejects a settle that omits a commitment", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await runToFinalized(
+        700,
+        [agent1, agent2, agent3],
+        [100_000_000, 104_000_000, 110_000_000],
+        100_000_000
+      );
+
+      for (const agents of [[agent1, agent2], [agent1, agent2, agent2]]) {
+        try {
+          await settleAs(topic, vault, authority, agents);
+          expect.fail("Should have thrown");
+        } catch (err: any) {
+          expect(err.toString()).to.include("MissingCommitments");
+        }
+      }
+      expect((await program.account.topic.fetch(topic)).status).to.have.property("finalized");
+
+      await settleAs(topic, vault, authority, [agent1, agent2, agent3]);
+      expect((await program.account.topic.fetch(topic)).status).to.have.property("settled");
+    });
+  });
+
+  // ─── Test 74: Soft Finalize ──────────────────────────────────────
+
+  describe("soft finalize", () => {
+    it("reports the seconds left on an early finalize instead of failing", async function () {
+      this.timeout(30000);
+      const [soft] = await createTestTopic(710, { commitIn: 5, revealIn: 20, options: { softFinalize: true } });
+      const [hard] = await createTestTopic(711, { commitIn: 5, revealIn: 20 });
+
+      const sig = await finalizeAs(soft, 100_000_000);
+      const tx = await provider.connection.getTransaction(sig, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const returned = Buffer.from(tx!.meta!.returnData!.data[0], "base64").readBigUInt64LE(0);
+      expect(Number(returned)).to.be.greaterThan(0).and.at.most(20);
+
+      const data = await program.account.topic.fetch(soft);
+      expect(data.status).to.have.property("open");
+      expect(data.failedFinalizeCount).to.equal(0);
+      expect(data.truthValue.toNumber()).to.equal(0);
+
+      try {
+        await finalizeAs(hard, 100_000_000);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("RevealPhaseNotEnded");
+      }
+
+      await sleepUntil(data.revealDeadline.toNumber());
+      await finalizeAs(soft, 100_000_000);
+      expect((await program.account.topic.fetch(soft)).status).to.have.property("finalized");
+    });
+  });
+
+  // ─── Test 75: Topic Id Collision ─────────────────────────────────
+
+  describe("topic id collision", () => {
+    it("rejects reusing a topic id with TopicIdAlreadyExists", async function () {
+      this.timeout(30000);
+      const [topic] = await createTestTopic(720, { commitIn: 60, revealIn: 120 });
+      const before = await program.account.topic.fetch(topic);
+
+      try {
+        await createTestTopic(720, { commitIn: 90, revealIn: 180, symbol: "OTHER" });
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("TopicIdAlreadyExists");
+      }
+
+      const after = await program.account.topic.fetch(topic);
+      expect(after.symbol).to.equal(before.symbol);
+      expect(after.commitDeadline.toNumber()).to.equal(before.commitDeadline.toNumber());
+
+      // The recurring path reports the same error for a taken id in its series
+      const now = Math.floor(Date.now() / 1000);
+      try {
+        await program.methods
+          .createRecurringTopics(
+            new BN(719),
+            2,
+            new BN(86_400),
+            "Series",
+            "TEST",
+            new BN(now + 60),
+            new BN(now + 120),
+            new BN(10_000_000),
+            topicOptions(),
+            null
+          )
+          .accounts({
+            authority: authority.publicKey,
+            oracleAuthority: oracleAuthority.publicKey,
+            settleAuthority: null,
+            cosigner: null,
+            priceFeed: null,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(
+            [719, 720].map((id) => ({ pubkey: deriveTopic(id)[0], isSigner: false, isWritable: true }))
+          )
+          .signers([authority])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("TopicIdAlreadyExists");
+      }
+      expect(await provider.connection.getAccountInfo(deriveTopic(719)[0])).to.be.null;
+    });
+  });
+
+  // ─── Test 76: Stake-Scaled Reveal Window ─────────────────────────
+
+  describe("stake-scaled reveal window", () => {
+    // One hour of reveal window per SOL of minimum stake
+    const options = { revealSecondsPerSol: 3_600 };
+
+    it("requires the window the minimum stake calls for, to the second", async function () {
+      this.timeout(30000);
+      const cases = [
+        // 0.01 SOL needs 36 seconds; 1 SOL needs an hour
+        { id: 730, minStake: 10_000_000, window: 36 },
+        { id: 732, minStake: LAMPORTS_PER_SOL, window: 3_600 },
+      ];
+      for (const { id, minStake, window } of cases) {
+        try {
+          await createTestTopic(id, { commitIn: 60, revealIn: 60 + window - 1, minStake, options });
+          expect.fail("Should have thrown");
+        } catch (err: any) {
+          expect(err.toString()).to.include("RevealWindowTooShort");
+        }
+
+        const [topic] = await createTestTopic(id + 1, { commitIn: 60, revealIn: 60 + window, minStake, options });
+        const data = await program.account.topic.fetch(topic);
+        expect(data.revealDeadline.sub(data.commitDeadline).toNumber()).to.equal(window);
+      }
+    });
+  });
+
+  // ─── Test 77: Pure Prediction Mode ───────────────────────────────
+
+  describe("pure prediction mode", () => {
+    it("returns every stake and still credits the leaderboard", async function () {
+      this.timeout(30000);
+      const ranker = Keypair.generate();
+      await airdrop(ranker.publicKey, 2);
+      const leaderboard = PublicKey.findProgramAddressSync(
+        [Buffer.from("leaderboard"), ranker.publicKey.toBuffer()],
+        program.programId
+      )[0];
+      await program.methods
+        .initLeaderboard()
+        .accounts({ participant: ranker.publicKey, leaderboard, systemProgram: SystemProgram.programId })
+        .signers([ranker])
+        .rpc();
+
+      const [topic, vault] = await createTestTopic(740, { options: { rewardEnabled: false } });
+      const stakes = [30_000_000, 40_000_000, 50_000_000];
+      const agents = [ranker, agent1, agent2];
+      const predictions = [100_000_000, 130_000_000, 90_000_000];
+      const salts = agents.map(() => randomSalt());
+      for (let i = 0; i < agents.length; i++) {
+        await commitTo(topic, vault, agents[i], predictions[i], salts[i], stakes[i]);
+      }
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      // agent2 never reveals
+      await revealTo(topic, ranker, predictions[0], salts[0]);
+      await revealTo(topic, agent1, predictions[1], salts[1]);
+      await sleepUntil(created.revealDeadline.toNumber());
+      await finalizeAs(topic, 100_000_000);
+
+      const before = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+      await program.methods
+        .settle()
+        .accounts({
+          authority: authority.publicKey,
+          topic,
+          topicAuthority: authority.publicKey,
+          vault,
+          truthHistory: deriveTruthHistory("TEST"),
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts([
+          ...agents.flatMap((a) => [
+            { pubkey: deriveCommitment(topic, a.publicKey), isSigner: false, isWritable: true },
+            { pubkey: a.publicKey, isSigner: false, isWritable: true },
+          ]),
+          { pubkey: leaderboard, isSigner: false, isWritable: true },
+        ])
+        .signers([authority])
+        .rpc();
+
+      for (let i = 0; i < agents.length; i++) {
+        expect((await provider.connection.getBalance(agents[i].publicKey)) - before[i]).to.equal(stakes[i]);
+      }
+      const entry = await program.account.leaderboard.fetch(leaderboard);
+      expect(entry.settledCount).to.equal(1);
+      expect(entry.winCount).to.equal(0);
+      expect(entry.cumulativePayout.toNumber()).to.equal(stakes[0]);
+      expect(entry.totalScore.gtn(0)).to.be.true;
+    });
+  });
+
+  // ─── Test 78: Topic Config ───────────────────────────────────────
+
+  describe("topic config", () => {
+    it("holds a 512-value grid in the side PDA without growing the topic", async function () {
+      this.timeout(60000);
+      const [plain] = await createTestTopic(750);
+      const [topic, vault] = await createTestTopic(751);
+      for (let start = 0; start < 512; start += 64) {
+        await addAllowedValues(topic, Array.from({ length: 64 }, (_, i) => (start + i) * 1_000_000));
+      }
+      try {
+        await addAllowedValues(topic, [512_000_000]);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("TooManyAllowedValues");
+      }
+
+      const [topicInfo, plainInfo, configInfo] = await provider.connection.getMultipleAccountsInfo([
+        topic,
+        plain,
+        deriveTopicConfig(topic),
+      ]);
+      expect(topicInfo!.data.length).to.equal(plainInfo!.data.length);
+      expect(configInfo!.data.length).to.be.at.most(10_240);
+      expect((await program.account.topicConfig.fetch(deriveTopicConfig(topic))).allowedValues).to.have.length(512);
+
+      const [onGrid, offGrid] = [randomSalt(), randomSalt()];
+      await commitTo(topic, vault, agent1, 511_000_000, onGrid, 20_000_000);
+      await commitTo(topic, vault, agent2, 511_500_000, offGrid, 20_000_000);
+      await sleepUntil((await program.account.topic.fetch(topic)).commitDeadline.toNumber());
+
+      // Leaving out the config of a topic that has one is rejected
+      try {
+        await program.methods
+          .reveal(new BN(511_000_000), Array.from(onGrid))
+          .accounts({ participant: agent1.publicKey, topic, commitment: deriveCommitment(topic, agent1.publicKey) })
+          .signers([agent1])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidTopicConfig");
+      }
+      await revealTo(topic, agent1, 511_000_000, onGrid);
+      try {
+        await revealTo(topic, agent2, 511_500_000, offGrid);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidPredictionValue");
+      }
+    });
+  });
+
+  // ─── Test 79: Aux Commitment And Range Proofs ────────────────────
+
+  describe("aux_commitment", () => {
+    it("stores the aux commitment and checks placeholder range proofs against it", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(755);
+      const aux = Array.from(randomSalt());
+      await commitTo(topic, vault, agent1, 100_000_000, randomSalt(), 10_000_000, null, null, aux);
+      await commitTo(topic, vault, agent2, 100_000_000, randomSalt(), 10_000_000);
+      const commitment = deriveCommitment(topic, agent1.publicKey);
+      expect((await program.account.commitment.fetch(commitment)).auxCommitment).to.deep.equal(aux);
+
+      const verify = (low: number, high: number, proof: number[], target = commitment) =>
+        program.methods
+          .verifyRangeProof(new BN(low), new BN(high), Buffer.from(proof))
+          .accounts({ commitment: target })
+          .view();
+      expect(await verify(90_000_000, 110_000_000, placeholderRangeProof(aux, 90_000_000, 110_000_000))).to.be.true;
+      expect(await verify(90_000_000, 120_000_000, placeholderRangeProof(aux, 90_000_000, 110_000_000))).to.be.false;
+
+      for (const [low, high, target, error] of [
+        [110_000_000, 90_000_000, commitment, "InvalidRangeProof"],
+        [90_000_000, 110_000_000, deriveCommitment(topic, agent2.publicKey), "MissingAuxCommitment"],
+      ] as [number, number, PublicKey, string][]) {
+        try {
+          await verify(low, high, placeholderRangeProof(aux, low, high), target);
+          expect.fail("Should have thrown");
+        } catch (err: any) {
+          expect(err.toString()).to.include(error);
+        }
+      }
+    });
+  });
+
+  // ─── Test 80: Unanimous Reveals ──────────────────────────────────
+
+  describe("unanimous reveals", () => {
+    // agent1 and agent2 reveal the same value, agent3 forfeits
+    async function settleUnanimous(id: number, unanimousPolicy: object) {
+      const [topic, vault] = await createTestTopic(id, { options: { unanimousPolicy } });
+      const agents = [agent1, agent2, agent3];
+      const salts = agents.map(() => randomSalt());
+      for (let i = 0; i < agents.length; i++) {
+        await commitTo(topic, vault, agents[i], 100_000_000, salts[i], 30_000_000);
+      }
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      await revealTo(topic, agent1, 100_000_000, salts[0]);
+      await revealTo(topic, agent2, 100_000_000, salts[1]);
+      await sleepUntil(created.revealDeadline.toNumber());
+      await finalizeAs(topic, 104_000_000);
+
+      const before = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+      const sig = await settleAs(topic, vault, authority, agents);
+      const after = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+      return { paid: after.map((bal, i) => bal - before[i]), settled: (await eventsOf(sig)).topicSettled };
+    }
+
+    it("refunds the pool pro-rata by default and sweeps it when configured", async function () {
+      this.timeout(60000);
+      // agent3's 30M goes back to all three committers, 10M each
+      const refunded = await settleUnanimous(760, { refund: {} });
+      const expected = [40_000_000, 40_000_000, 10_000_000];
+      for (let i = 0; i < expected.length; i++) {
+        expect(refunded.paid[i]).to.be.closeTo(expected[i], 1_000_000);
+      }
+      expect(refunded.settled.protocolFee.toNumber()).to.be.lessThan(1_000_000);
+
+      const swept = await settleUnanimous(761, { sweep: {} });
+      expect(swept.paid).to.deep.equal([30_000_000, 30_000_000, 0]);
+      expect(swept.settled.protocolFee.toNumber()).to.be.greaterThan(29_000_000);
+    });
+  });
+
+  // ─── Test 81: Loser Pool Preview ─────────────────────────────────
+
+  describe("loser pool preview", () => {
+    it("tracks the pool through the reveal phase and matches settle once finalized", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(770, { options: { wrongDirectionPenaltyBps: 5_000 } });
+      const agents = [agent1, agent2, agent3];
+      const predictions = [100_000_000, 90_000_000, 110_000_000];
+      const salts = agents.map(() => randomSalt());
+      for (let i = 0; i < agents.length; i++) {
+        await commitTo(topic, vault, agents[i], predictions[i], salts[i], 30_000_000);
+      }
+      const preview = async () =>
+        (
+          await program.methods
+            .loserPoolPreview()
+            .accounts({ topic })
+            .remainingAccounts(
+              agents.map((a) => ({ pubkey: deriveCommitment(topic, a.publicKey), isSigner: false, isWritable: false }))
+            )
+            .view()
+        ).toNumber();
+
+      expect(await preview()).to.equal(90_000_000);
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      await revealTo(topic, agent1, predictions[0], salts[0]);
+      expect(await preview()).to.equal(60_000_000);
+      // agent3 never reveals
+      await revealTo(topic, agent2, predictions[1], salts[1]);
+      expect(await preview()).to.equal(30_000_000);
+
+      await sleepUntil(created.revealDeadline.toNumber());
+      await finalizeAs(topic, 115_000_000);
+      // agent2's wrong-direction penalty joins agent3's forfeit
+      const finalized = await preview();
+      expect(finalized).to.be.greaterThan(30_000_000);
+
+      const sig = await settleAs(topic, vault, authority, agents);
+      expect((await eventsOf(sig)).topicSettled.loserPool.toNumber()).to.equal(finalized);
+    });
+  });
+
+  // ─── Test 82: Self Refund ────────────────────────────────────────
+
+  describe("self_refund", () => {
+    const selfRefund = (topic: PublicKey, vault: PublicKey, agent: Keypair) =>
+      program.methods
+        .selfRefund()
+        .accounts({
+          participant: agent.publicKey,
+          topic,
+          commitment: deriveCommitment(topic, agent.publicKey),
+          stakePayer: agent.publicKey,
+          vault,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([agent])
+        .rpc();
+
+    it("rejects a self-refund timeout shorter than the refund timeout", async () => {
+      try {
+        await createTestTopic(780, { options: { refundTimeout: new BN(60), selfRefundTimeout: new BN(30) } });
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidDeadlines");
+      }
+    });
+
+    it("returns a revealer's stake once the timeout passes, and only once", async function () {
+      this.timeout(30000 + MIN_REFUND_TIMEOUT * 1000);
+      const stake = 30_000_000;
+      const [topic, vault] = await createTestTopic(781, {
+        options: { refundTimeout: new BN(MIN_REFUND_TIMEOUT), selfRefundTimeout: new BN(MIN_REFUND_TIMEOUT) },
+      });
+      const agents = [agent1, agent2, agent3];
+      const predictions = [100_000_000, 110_000_000, 90_000_000];
+      const salts = agents.map(() => randomSalt());
+      for (let i = 0; i < agents.length; i++) {
+        await commitTo(topic, vault, agents[i], predictions[i], salts[i], stake);
+      }
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      // agent3 never reveals
+      await revealTo(topic, agent1, predictions[0], salts[0]);
+      await revealTo(topic, agent2, predictions[1], salts[1]);
+      await sleepUntil(created.revealDeadline.toNumber());
+      await finalizeAs(topic, 104_000_000);
+
+      try {
+        await selfRefund(topic, vault, agent1);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("RefundTimeoutNotReached");
+      }
+
+      const { finalizedAt } = await program.account.topic.fetch(topic);
+      await sleepUntil(finalizedAt.toNumber() + MIN_REFUND_TIMEOUT);
+      const before = await provider.connection.getBalance(agent1.publicKey);
+      await selfRefund(topic, vault, agent1);
+      // The participant pays the fee; the rest of the change is the stake
+      expect((await provider.connection.getBalance(agent1.publicKey)) - before).to.be.closeTo(stake, 10_000);
+      expect((await program.account.commitment.fetch(deriveCommitment(topic, agent1.publicKey))).settled).to.be.true;
+
+      for (const [agent, error] of [
+        [agent1, "CommitmentAlreadySettled"],
+        [agent3, "NotRevealed"],
+      ] as [Keypair, string][]) {
+        try {
+          await selfRefund(topic, vault, agent);
+          expect.fail("Should have thrown");
+        } catch (err: any) {
+          expect(err.toString()).to.include(error);
+        }
+      }
+
+      // A later settle pays the others and skips the self-refunded commitment
+      const settledBefore = await provider.connection.getBalance(agent1.publicKey);
+      await settleAs(topic, vault, authority, agents);
+      expect(await provider.connection.getBalance(agent1.publicKey)).to.equal(settledBefore);
+      expect((await program.account.topic.fetch(topic)).status).to.have.property("settled");
+    });
+  });
+
+  // ─── Test 83: Reveal Rebate From The Protocol Fee ────────────────
+
+  describe("reveal rebate from the protocol fee", () => {
+    it("pays each revealer out of the authority's sweep", async function () {
+      this.timeout(60000);
+      // agent1 and agent2 reveal the same value and agent3 forfeits; under
+      // the sweep policy its stake is protocol fee
+      async function settleWithRebate(id: number, revealRebate: number) {
+        const [topic, vault] = await createTestTopic(id, {
+          options: { unanimousPolicy: { sweep: {} }, revealRebate: new BN(revealRebate) },
+        });
+        const agents = [agent1, agent2, agent3];
+        const salts = agents.map(() => randomSalt());
+        for (let i = 0; i < agents.length; i++) {
+          await commitTo(topic, vault, agents[i], 100_000_000, salts[i], 30_000_000);
+        }
+        const created = await program.account.topic.fetch(topic);
+        await sleepUntil(created.commitDeadline.toNumber());
+        await revealTo(topic, agent1, 100_000_000, salts[0]);
+        await revealTo(topic, agent2, 100_000_000, salts[1]);
+        await sleepUntil(created.revealDeadline.toNumber());
+        await finalizeAs(topic, 104_000_000);
+
+        const before = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+        const sig = await settleAs(topic, vault, authority, agents);
+        const after = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+        return { paid: after.map((bal, i) => bal - before[i]), settled: (await eventsOf(sig)).topicSettled };
+      }
+
+      const plain = await settleWithRebate(790, 0);
+      const rebated = await settleWithRebate(791, 1_000_000);
+      expect(plain.paid).to.deep.equal([30_000_000, 30_000_000, 0]);
+      expect(rebated.paid).to.deep.equal([31_000_000, 31_000_000, 0]);
+      expect(rebated.settled.protocolFee.toNumber()).to.equal(plain.settled.protocolFee.toNumber() - 2_000_000);
+      expect(rebated.settled.loserPool.toNumber()).to.equal(plain.settled.loserPool.toNumber());
+    });
+  });
+
+  // ─── Test 84: Settled Flag Across Read Paths ─────────────────────
+
+  describe("settled flag after settle", () => {
+    it("reads the same through the account and participant_status", async function () {
+      this.timeout(30000);
+      const agents = [agent1, agent2, agent3];
+      const predictions = [100_000_000, 110_000_000, 90_000_000];
+      const [topic, vault] = await createTestTopic(800);
+      const salts = agents.map(() => randomSalt());
+      for (let i = 0; i < agents.length; i++) {
+        await commitTo(topic, vault, agents[i], predictions[i], salts[i], 10_000_000);
+      }
+      const created = await program.account.topic.fetch(topic);
+      await sleepUntil(created.commitDeadline.toNumber());
+      // agent3 never reveals but is settled all the same
+      await revealTo(topic, agent1, predictions[0], salts[0]);
+      await revealTo(topic, agent2, predictions[1], salts[1]);
+      await sleepUntil(created.revealDeadline.toNumber());
+      await finalizeAs(topic, 104_000_000);
+      await settleAs(topic, vault, authority, agents);
+
+      for (let i = 0; i < agents.length; i++) {
+        const commitment = deriveCommitment(topic, agents[i].publicKey);
+        const account = await program.account.commitment.fetch(commitment);
+        expect(account.settled).to.be.true;
+        expect(account.stakeAmount.toNumber()).to.equal(10_000_000);
+        expect(account.revealed).to.equal(i < 2);
+        expect(account.predictionValue.toNumber()).to.equal(i < 2 ? predictions[i] : 0);
+
+        const status = await program.methods
+          .participantStatus(agents[i].publicKey, 0)
+          .accounts({ topic, commitment })
+          .view();
+        expect(status.settled).to.be.true;
+        expect(status.revealed).to.equal(account.revealed);
+        expect(status.stakeAmount.toNumber()).to.equal(10_000_000);
+      }
+    });
+  });
+
+  // ─── Test 85: Minimum Distinct Participants ──────────────────────
+
+  describe("min_participants", () => {
+    it("counts a participant once however many commitments they hold", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(810, { options: { minParticipants: 3 } });
+      const salts = [randomSalt(), randomSalt()];
+      // agent1 commits twice, agent2 once; agent3 commits and cancels
+      for (let nonce = 0; nonce < 2; nonce++) {
+        await program.methods
+          .commit(computeHash(100_000_000, salts[nonce], agent1.publicKey), new BN(10_000_000), null, null, false, null)
+          .accounts({
+            participant: agent1.publicKey,
+            rentPayer: agent1.publicKey,
+            topic,
+            participantState: deriveParticipantState(topic, agent1.publicKey),
+            commitment: deriveCommitment(topic, agent1.publicKey, nonce),
+            vault,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([agent1])
+          .rpc();
+      }
+      await commitTo(topic, vault, agent2, 100_000_000, randomSalt(), 10_000_000);
+      await commitTo(topic, vault, agent3, 100_000_000, randomSalt(), 10_000_000);
+      expect((await program.account.topic.fetch(topic)).participantCount).to.equal(3);
+      await program.methods
+        .cancelCommitment()
+        .accounts({
+          participant: agent3.publicKey,
+          topic,
+          commitment: deriveCommitment(topic, agent3.publicKey),
+          rentPayer: agent3.publicKey,
+          stakePayer: agent3.publicKey,
+          participantState: deriveParticipantState(topic, agent3.publicKey),
+          vault,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([agent3])
+        .rpc();
+
+      const created = await program.account.topic.fetch(topic);
+      expect(created.commitmentCount).to.equal(3);
+      expect(created.participantCount).to.equal(2);
+
+      // One short of the threshold: the oracle cannot finalize, the
+      // authority voids and everyone is refunded
+      await sleepUntil(created.revealDeadline.toNumber());
+      try {
+        await finalizeAs(topic, 100_000_000);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("NotEnoughParticipants");
+      }
+      const commitments = [
+        [deriveCommitment(topic, agent1.publicKey, 0), agent1.publicKey],
+        [deriveCommitment(topic, agent1.publicKey, 1), agent1.publicKey],
+        [deriveCommitment(topic, agent2.publicKey), agent2.publicKey],
+      ];
+      await program.methods
+        .voidTopic()
+        .accounts({
+          authority: authority.publicKey,
+          topic,
+          vault,
+          topicConfig: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts([
+          ...commitments.flatMap(([commitment, participant]) => [
+            { pubkey: commitment, isSigner: false, isWritable: true },
+            { pubkey: participant, isSigner: false, isWritable: true },
+            { pubkey: participant, isSigner: false, isWritable: true },
+            { pubkey: participant, isSigner: false, isWritable: true },
+          ]),
+          // agent3 canceled out but still has a participant state to close
+          ...[agent1, agent2, agent3].flatMap((a) => [
+            { pubkey: deriveParticipantState(topic, a.publicKey), isSigner: false, isWritable: true },
+            { pubkey: a.publicKey, isSigner: false, isWritable: true },
+          ]),
+        ])
+        .signers([authority])
+        .rpc();
+      expect(await provider.connection.getAccountInfo(topic)).to.be.null;
+    });
+
+    it("finalizes once the threshold is met", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(811, { options: { minParticipants: 3 } });
+      for (const agent of [agent1, agent2, agent3]) {
+        await commitTo(topic, vault, agent, 100_000_000, randomSalt(), 10_000_000);
+      }
+      const created = await program.account.topic.fetch(topic);
+      expect(created.participantCount).to.equal(3);
+      await sleepUntil(created.revealDeadline.toNumber());
+      await finalizeAs(topic, 100_000_000);
+      expect((await program.account.topic.fetch(topic)).status).to.deep.equal({ finalized: {} });
+    });
+  });
+
+  // ─── Test 86: Settle Payout Accounts ─────────────────────────────
+
+  describe("settle payout accounts", () => {
+    it("rejects a payout account that is not the commitment's participant", async function () {
+      this.timeout(60000);
+      const stranger = Keypair.generate();
+      await airdrop(stranger.publicKey, 1);
+      const agents = [agent1, agent2];
+      const [topic, vault] = await runToFinalized(820, agents, [100_000_000, 110_000_000], 104_000_000, {
+        options: { publicSettle: true },
+      });
+
+      try {
+        await program.methods
+          .settle()
+          .accounts({
+            authority: stranger.publicKey,
+            topic,
+            topicAuthority: authority.publicKey,
+            vault,
+            truthHistory: await truthHistoryOf(topic),
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts(
+            agents.flatMap((a) => [
+              { pubkey: deriveCommitment(topic, a.publicKey), isSigner: false, isWritable: true },
+              { pubkey: stranger.publicKey, isSigner: false, isWritable: true },
+            ])
+          )
+          .signers([stranger])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidCommitmentAccount");
+      }
+
+      await settleAs(topic, vault, stranger, agents);
+      expect((await program.account.topic.fetch(topic)).status).to.have.property("settled");
+    });
+  });
+
+  // ─── Test 87: Snapshot Matches Settle ────────────────────────────
+
+  describe("snapshot consensus consistency", () => {
+    it("settles to the same consensus and payouts with or without a snapshot", async function () {
+      this.timeout(60000);
+      const agents = [agent1, agent2, agent3];
+      const predictions = [100_000_000, 106_000_000, 130_000_000];
+      const snapped = await createTestTopic(830);
+      const plain = await createTestTopic(831);
+      const salts = agents.map(() => randomSalt());
+      for (const [topic, vault] of [snapped, plain]) {
+        for (let i = 0; i < agents.length; i++) {
+          await commitTo(topic, vault, agents[i], predictions[i], salts[i], 20_000_000);
+        }
+      }
+      const created = await program.account.topic.fetch(plain[0]);
+      await sleepUntil(created.commitDeadline.toNumber());
+      for (const [topic] of [snapped, plain]) {
+        for (let i = 0; i < agents.length; i++) {
+          await revealTo(topic, agents[i], predictions[i], salts[i]);
+        }
+      }
+      await sleepUntil(created.revealDeadline.toNumber());
+
+      await finalizeAs(snapped[0], 110_000_000);
+      await program.methods
+        .snapshotConsensus()
+        .accounts({ topic: snapped[0] })
+        .remainingAccounts(
+          agents.map((a) => ({ pubkey: deriveCommitment(snapped[0], a.publicKey), isSigner: false, isWritable: false }))
+        )
+        .rpc();
+      await finalizeAs(plain[0], 110_000_000);
+
+      const results: { consensus: string; paid: number[] }[] = [];
+      for (const [topic, vault] of [snapped, plain]) {
+        const before = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+        const sig = await settleAs(topic, vault, authority, agents);
+        const after = await Promise.all(agents.map((a) => provider.connection.getBalance(a.publicKey)));
+        results.push({
+          consensus: (await eventsOf(sig)).topicSettled.consensus.toString(),
+          paid: after.map((bal, i) => bal - before[i]),
+        });
+      }
+      expect(results[0]).to.deep.equal(results[1]);
+    });
+  });
+
+  // ─── Test 88: Submit Order After Cancels ─────────────────────────
+
+  describe("submit order after cancels", () => {
+    it("never reuses an order, so a later committer still gets its reveal turn", async function () {
+      this.timeout(60000);
+      const [topic, vault] = await createTestTopic(840, {
+        commitIn: 8,
+        revealIn: 20,
+        options: { orderedReveal: true, revealTurnSeconds: 2 },
+      });
+      const agents = [agent1, agent2, agent3];
+      const salts = agents.map(() => randomSalt());
+      for (let i = 0; i < agents.length; i++) {
+        await commitTo(topic, vault, agents[i], 100_000_000, salts[i], 10_000_000);
+      }
+      // agent1 (order 0) and agent2 (order 1) cancel
+      for (const agent of [agent1, agent2]) {
+        await program.methods
+          .cancelCommitment()
+          .accounts({
+            participant: agent.publicKey,
+            topic,
+            commitment: deriveCommitment(topic, agent.publicKey),
+            rentPayer: agent.publicKey,
+            stakePayer: agent.publicKey,
+            participantState: deriveParticipantState(topic, agent.publicKey),
+            vault,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([agent])
+          .rpc();
+      }
+      const created = await program.account.topic.fetch(topic);
+      expect(created.commitmentCount).to.equal(1);
+      expect(created.nextSubmitOrder).to.equal(3);
+      expect((await program.account.commitment.fetch(deriveCommitment(topic, agent3.publicKey))).submitOrder).to.equal(2);
+
+      // The canceled orders are skipped like any unrevealed turn
+      await sleepUntil(created.commitDeadline.toNumber());
+      const skip = () => program.methods.skipRevealTurn().accounts({ topic }).rpc();
+      for (const order of [0, 1]) {
+        const turn = await program.account.topic.fetch(topic);
+        expect(turn.nextExpectedRevealOrder).to.equal(order);
+        await sleepUntil(Math.max(turn.revealTurnStartedAt.toNumber(), created.commitDeadline.toNumber()) + 2);
+        await skip();
+      }
+      await revealTo(topic, agent3, 100_000_000, salts[2]);
+      const revealed = await program.account.topic.fetch(topic);
+      expect(revealed.nextExpectedRevealOrder).to.equal(3);
+      expect(revealed.revealCount).to.equal(1);
+    });
+  });
+
+  // ─── Test 89: Void Leaves Nothing Behind ─────────────────────────
+
+  describe("void cleanup", () => {
+    it("closes participant states and the config, so the id can be created afresh", async function () {
+      this.timeout(30000);
+      const [topic, vault] = await createTestTopic(851);
+      await addAllowedValues(topic, [100_000_000, 110_000_000]);
+      await commitTo(topic, vault, agent1, 100_000_000, randomSalt(), 10_000_000);
+      await commitTo(topic, vault, agent2, 110_000_000, randomSalt(), 10_000_000);
+
+      const voidWith = (topicConfig: PublicKey | null, states: Keypair[]) =>
+        program.methods
+          .voidTopic()
+          .accounts({ authority: authority.publicKey, topic, vault, topicConfig, systemProgram: SystemProgram.programId })
+          .remainingAccounts([
+            ...[agent1, agent2].flatMap((a) => [
+              { pubkey: deriveCommitment(topic, a.publicKey), isSigner: false, isWritable: true },
+              { pubkey: a.publicKey, isSigner: false, isWritable: true },
+              { pubkey: a.publicKey, isSigner: false, isWritable: true },
+              { pubkey: a.publicKey, isSigner: false, isWritable: true },
+            ]),
+            ...states.flatMap((a) => [
+              { pubkey: deriveParticipantState(topic, a.publicKey), isSigner: false, isWritable: true },
+              { pubkey: a.publicKey, isSigner: false, isWritable: true },
+            ]),
+          ])
+          .signers([authority])
+          .rpc();
+
+      // The config must be closed too
+      try {
+        await voidWith(null, [agent1, agent2]);
+        expect.fail("Should have thrown");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidTopicConfig");
+      }
+      // So must every participant still holding a commitment, each once
+      for (const states of [[agent1], [agent1, agent1]]) {
+        try {
+          await voidWith(deriveTopicConfig(topic), states);
+          expect.fail("Should have thrown");
+        } catch (err: any) {
+          expect(err.toString()).to.match(/MissingCommitments|AccountNotInitialized|AccountOwnedByWrongProgram/);
+        }
+      }
+
+      await voidWith(deriveTopicConfig(topic), [agent1, agent2]);
+      for (const account of [
+        topic,
+        deriveTopicConfig(topic),
+        deriveParticipantState(topic, agent1.publicKey),
+        deriveParticipantState(topic, agent2.publicKey),
+      ]) {
+        expect(await provider.connection.getAccountInfo(account)).to.be.null;
+      }
+
+      // Re-created, the topic starts clean: no grid, and nonces from 0
+      const [again, againVault] = await createTestTopic(851);
+      expect(again.equals(topic)).to.be.true;
+      expect((await program.account.topic.fetch(again)).config.equals(PublicKey.default)).to.be.true;
+      await commitTo(again, againVault, agent1, 123_000_000, randomSalt(), 10_000_000);
+      const state = await program.account.participantState.fetch(deriveParticipantState(again, agent1.publicKey));
+      expect(state.commitNonce).to.equal(1);
+      expect(state.openCommitments).to.equal(1);
+    });
+  });
 });